@@ -0,0 +1,219 @@
+use clap::{Args, ValueEnum};
+use crate::{algorithms::polybius_square::PolybiusSquare, alphabet::{POLYBIUS_ALPHABET_5X5, POLYBIUS_ALPHABET_6X6}, Operations};
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum AdfgvxVariant {
+    Adfgx,
+    Adfgvx
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct AdfgvxCipherAlg {
+    /// Encode or Decode Operation
+    #[arg(short, long)]
+    pub operation: Operations,
+    /// ADFGX (5x5 square) or ADFGVX (6x6 square with digits)
+    #[arg(short, long, default_value = "adfgvx")]
+    pub variant: AdfgvxVariant,
+    /// The keyword used to fill the substitution square
+    #[arg(short, long, default_value = "")]
+    pub square_key: String,
+    /// The keyword of the columnar transposition
+    #[arg(short, long)]
+    pub key: String,
+    /// The message to encode or decode
+    #[arg(short, long)]
+    pub message: String
+}
+
+impl AdfgvxCipherAlg {
+    pub fn execute(self) -> () {
+        match self.operation {
+            Operations::Encrypt => {
+                match self.encode() {
+                    Ok(encoded_message) => println!("{}", encoded_message),
+                    Err(error) => println!("{}", error)
+                }
+            }
+            Operations::Decrypt => {
+                match self.decode() {
+                    Ok(decoded_message) => println!("{}", decoded_message),
+                    Err(error) => println!("{}", error)
+                }
+            }
+        }
+    }
+
+    fn encode(&self) -> Result<String, String> {
+        self.check_key()?;
+        let square: PolybiusSquare = self.square();
+        let labels: Vec<char> = self.labels();
+
+        let substituted: Vec<char> = square.normalize(&self.message)
+                                           .iter()
+                                           .flat_map(|c: &char| {
+                                                let (row, col): (usize, usize) = square.coordinates(*c).unwrap();
+                                                [labels[row], labels[col]]
+                                           })
+                                           .collect();
+
+        return Ok(columnar_transposition(&substituted, &self.key).iter().collect());
+    }
+
+    fn decode(&self) -> Result<String, String> {
+        self.check_key()?;
+        let square: PolybiusSquare = self.square();
+        let labels: Vec<char> = self.labels();
+        let ciphertext: Vec<char> = self.message.to_ascii_uppercase().chars().filter(|c: &char| labels.contains(c)).collect();
+
+        if !ciphertext.len().is_multiple_of(2) {
+            return Err("the message must have an even number of letters".to_string());
+        }
+
+        return Ok(columnar_transposition_inverse(&ciphertext, &self.key)
+                    .chunks(2)
+                    .map(|pair: &[char]| {
+                        let row: usize = labels.iter().position(|x: &char| *x == pair[0]).unwrap();
+                        let col: usize = labels.iter().position(|x: &char| *x == pair[1]).unwrap();
+                        square.symbol_at(row, col)
+                    })
+                    .collect());
+    }
+
+    // Without a single column the transposition would drop the whole message
+    fn check_key(&self) -> Result<(), String> {
+        if column_order(&self.key).is_empty() {
+            return Err("the transposition key must have at least one letter or digit".to_string());
+        }
+        return Ok(());
+    }
+
+    fn square(&self) -> PolybiusSquare {
+        match self.variant {
+            AdfgvxVariant::Adfgx => PolybiusSquare::new(&self.square_key, POLYBIUS_ALPHABET_5X5),
+            AdfgvxVariant::Adfgvx => PolybiusSquare::new(&self.square_key, POLYBIUS_ALPHABET_6X6)
+        }
+    }
+
+    fn labels(&self) -> Vec<char> {
+        match self.variant {
+            AdfgvxVariant::Adfgx => "ADFGX".chars().collect(),
+            AdfgvxVariant::Adfgvx => "ADFGVX".chars().collect()
+        }
+    }
+}
+
+
+
+
+fn column_order(key: &str) -> Vec<usize> {
+    let key_chars: Vec<char> = key.to_ascii_uppercase().chars().filter(|c: &char| c.is_ascii_alphanumeric()).collect();
+    let mut order: Vec<usize> = (0..key_chars.len()).collect();
+
+    order.sort_by_key(|i: &usize| key_chars[*i]);
+    return order;
+}
+
+
+
+
+pub fn columnar_transposition(text: &[char], key: &str) -> Vec<char> {
+    let order: Vec<usize> = column_order(key);
+    let columns: usize = order.len().max(1);
+
+    return order.iter()
+                .flat_map(|col: &usize| text.iter().skip(*col).step_by(columns).copied())
+                .collect();
+}
+
+
+
+
+pub fn columnar_transposition_inverse(text: &[char], key: &str) -> Vec<char> {
+    let order: Vec<usize> = column_order(key);
+    let columns: usize = order.len().max(1);
+    let mut plain: Vec<char> = vec![' '; text.len()];
+    let mut cursor: usize = 0;
+
+    for col in order {
+        let column_len: usize = text.len() / columns + if col < text.len() % columns { 1 } else { 0 };
+
+        for (row, c) in text[cursor..cursor + column_len].iter().enumerate() {
+            plain[row * columns + col] = *c;
+        }
+        cursor += column_len;
+    }
+    return plain;
+}
+
+
+
+
+#[cfg(test)]
+mod adfgvx_cipher_test {
+    use super::*;
+
+    #[test]
+    fn columnar_transposition_test() -> () {
+        let text: Vec<char> = "WEAREDISCOVERED".chars().collect();
+
+        assert_eq!(columnar_transposition(&text, "ZEBRAS").iter().collect::<String>(), "EVACDESERODEWIR");
+        assert_eq!(columnar_transposition_inverse(&columnar_transposition(&text, "ZEBRAS"), "ZEBRAS"), text);
+    }
+
+    #[test]
+    fn adfgx_test() -> () {
+        let instance_1: AdfgvxCipherAlg = AdfgvxCipherAlg {
+            operation: Operations::Encrypt,
+            variant: AdfgvxVariant::Adfgx,
+            square_key: "BTALPDHOZKQFVSNGJCUXMREWY".to_string(),
+            key: "CARGO".to_string(),
+            message: "attack at once".to_string()
+        };
+        assert_eq!(instance_1.encode(), Ok("FAXDFADDDGDGFFFAFAXAFAFX".to_string()));
+
+        let instance_2: AdfgvxCipherAlg = AdfgvxCipherAlg {
+            operation: Operations::Decrypt,
+            message: "FAXDF ADDDG DGFFF AFAX AFAFX".to_string(),
+            ..instance_1
+        };
+        assert_eq!(instance_2.decode(), Ok("ATTACKATONCE".to_string()));
+    }
+
+    #[test]
+    fn adfgvx_test() -> () {
+        let instance_1: AdfgvxCipherAlg = AdfgvxCipherAlg {
+            operation: Operations::Encrypt,
+            variant: AdfgvxVariant::Adfgvx,
+            square_key: "NA1C3H8TB2OME5WRPD4F6G7I9J0KLQSUVXYZ".to_string(),
+            key: "PRIVACY".to_string(),
+            message: "attack at 1200am".to_string()
+        };
+        assert_eq!(instance_1.encode(), Ok("DGDDDAGDDGAFADDFDADVDVFAADVX".to_string()));
+
+        let instance_2: AdfgvxCipherAlg = AdfgvxCipherAlg {
+            operation: Operations::Decrypt,
+            message: "DGDD DAGD DGAF ADDF DADV DVFA ADVX".to_string(),
+            ..instance_1
+        };
+        assert_eq!(instance_2.decode(), Ok("ATTACKAT1200AM".to_string()));
+    }
+
+    #[test]
+    fn empty_key_test() -> () {
+        let instance_1: AdfgvxCipherAlg = AdfgvxCipherAlg {
+            operation: Operations::Encrypt,
+            variant: AdfgvxVariant::Adfgvx,
+            square_key: String::new(),
+            key: String::new(),
+            message: "attack at 1200am".to_string()
+        };
+        assert!(instance_1.encode().is_err());
+
+        let instance_2: AdfgvxCipherAlg = AdfgvxCipherAlg { key: " -!".to_string(), ..instance_1.clone() };
+        assert!(instance_2.encode().is_err());
+
+        let instance_3: AdfgvxCipherAlg = AdfgvxCipherAlg { operation: Operations::Decrypt, message: "DGDD".to_string(), ..instance_2.clone() };
+        assert!(instance_3.decode().is_err());
+    }
+}
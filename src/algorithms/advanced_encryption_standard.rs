@@ -1,9 +1,292 @@
 use clap::Args;
+use crate::{algorithms::block_cipher::{cbc_decrypt, cbc_encrypt, BlockCipher}, key_derivation::pbkdf2::{random_salt, PasswordHeader, PasswordKdf, DEFAULT_ITERATIONS}, ops::{bytes_to_hex, decode_key, hex_to_bytes, KeyFormat}, tables::{AES_INVERSE_SBOX, AES_ROUND_CONSTANTS, AES_SBOX}, Operations};
+
+const AES_BLOCK_SIZE: usize = 16;
 
 #[derive(Debug, Args, Clone)]
-pub struct AdvancedEncryptionStandardAlg {}
+pub struct AdvancedEncryptionStandardAlg {
+    /// Encode or Decode Operation
+    #[arg(short, long)]
+    pub operation: Operations,
+    /// The secret key (16, 24 or 32 bytes)
+    #[arg(short, long, conflicts_with = "password", required_unless_present = "password")]
+    pub key: Option<String>,
+    /// Encoding of the given key
+    #[arg(long, default_value = "hex")]
+    pub key_format: KeyFormat,
+    /// Derive the key from a passphrase (the KDF, its cost and the salt are stored in front of the ciphertext)
+    #[arg(short, long)]
+    pub password: Option<String>,
+    /// Password KDF used when encrypting with --password
+    #[arg(long, value_enum, default_value = "pbkdf2")]
+    pub kdf: PasswordKdf,
+    /// PBKDF2 iterations used when encrypting with --password
+    #[arg(short, long, default_value_t = DEFAULT_ITERATIONS)]
+    pub iterations: u32,
+    /// Key size in bits of a password derived key (128, 192 or 256)
+    #[arg(long, default_value_t = 256)]
+    pub key_size: usize,
+    /// The message to encode, or the hex ciphertext (IV first) to decode
+    #[arg(short, long)]
+    pub message: String
+}
 impl AdvancedEncryptionStandardAlg {
     pub fn execute(self) -> () {
-        
+        match self.operation {
+            Operations::Encrypt => {
+                match self.encrypt() {
+                    Ok(ciphertext) => println!("{}", bytes_to_hex(&ciphertext)),
+                    Err(error) => println!("{}", error)
+                }
+            }
+            Operations::Decrypt => {
+                match self.decrypt() {
+                    Ok(plain) => println!("{}", String::from_utf8_lossy(&plain)),
+                    Err(error) => println!("{}", error)
+                }
+            }
+        }
+    }
+
+    fn cipher(&self, header: &Option<PasswordHeader>) -> Result<Aes, String> {
+        match (&self.key, &self.password, header) {
+            (Some(key), _, _) => Aes::new(&decode_key(key, &self.key_format)?),
+            (None, Some(_), _) if ![128, 192, 256].contains(&self.key_size) => Err("AES key size must be 128, 192 or 256 bits".to_string()),
+            (None, Some(password), Some(header)) => Aes::new(&header.key(password, self.key_size / 8)?),
+            (None, _, _) => Err("a key or password is required".to_string())
+        }
+    }
+
+    // CBC with a random IV: the output is [password header] || IV || ciphertext
+    fn encrypt(&self) -> Result<Vec<u8>, String> {
+        let header: Option<PasswordHeader> = self.password.as_ref().map(|_| PasswordHeader::new(&self.kdf, self.iterations));
+        let iv: Vec<u8> = random_salt(AES_BLOCK_SIZE);
+        let ciphertext: Vec<u8> = cbc_encrypt(&self.cipher(&header)?, &iv, self.message.as_bytes());
+
+        return Ok([header.map(|header: PasswordHeader| header.to_bytes()).unwrap_or_default(), iv, ciphertext].concat());
+    }
+
+    fn decrypt(&self) -> Result<Vec<u8>, String> {
+        let data: Vec<u8> = hex_to_bytes(&self.message)?;
+        let (header, data): (Option<PasswordHeader>, &[u8]) = if self.password.is_some() { PasswordHeader::split(&data).map(|(header, data)| (Some(header), data))? } else { (None, &data) };
+
+        if data.len() < AES_BLOCK_SIZE {
+            return Err("ciphertext must start with a 16 byte IV".to_string());
+        }
+        let (iv, ciphertext): (&[u8], &[u8]) = data.split_at(AES_BLOCK_SIZE);
+
+        return cbc_decrypt(&self.cipher(&header)?, iv, ciphertext);
+    }
+}
+
+// Multiplication in GF(2^8) reduced by x^8 + x^4 + x^3 + x + 1
+fn gf_multiply(mut a: u8, mut b: u8) -> u8 {
+    let mut product: u8 = 0;
+
+    while b != 0 {
+        if b & 1 == 1 {
+            product ^= a;
+        }
+        a = if a & 0x80 == 0x80 { (a << 1) ^ 0x1b } else { a << 1 };
+        b >>= 1;
+    }
+    return product;
+}
+
+
+
+
+// The state is stored column by column, as the bytes of the block
+fn shift_rows(state: &mut [u8], inverse: bool) -> () {
+    let original: Vec<u8> = state.to_vec();
+
+    for row in 0..4 {
+        for column in 0..4 {
+            let source: usize = if inverse { (column + 4 - row) % 4 } else { (column + row) % 4 };
+            state[4 * column + row] = original[4 * source + row];
+        }
+    }
+}
+
+
+
+
+fn mix_columns(state: &mut [u8], inverse: bool) -> () {
+    let coefficients: [u8; 4] = if inverse { [0x0e, 0x0b, 0x0d, 0x09] } else { [0x02, 0x03, 0x01, 0x01] };
+
+    for column in state.chunks_mut(4) {
+        let original: Vec<u8> = column.to_vec();
+
+        for row in 0..4 {
+            column[row] = (0..4).fold(0, |acc: u8, k: usize| acc ^ gf_multiply(original[(row + k) % 4], coefficients[k]));
+        }
+    }
+}
+
+
+
+
+fn add_round_key(state: &mut [u8], round_key: &[u8; 16]) -> () {
+    for (byte, key_byte) in state.iter_mut().zip(round_key) {
+        *byte ^= key_byte;
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aes {
+    round_keys: Vec<[u8; 16]>
+}
+impl Aes {
+    pub fn new(key: &[u8]) -> Result<Aes, String> {
+        if ![16, 24, 32].contains(&key.len()) {
+            return Err("AES key must be 16, 24 or 32 bytes".to_string());
+        }
+
+        let key_words: usize = key.len() / 4;
+        let rounds: usize = key_words + 6;
+        let mut words: Vec<[u8; 4]> = key.chunks(4).map(|chunk: &[u8]| chunk.try_into().unwrap()).collect();
+
+        for i in key_words..4 * (rounds + 1) {
+            let mut word: [u8; 4] = words[i - 1];
+
+            if i % key_words == 0 {
+                word = [AES_SBOX[word[1] as usize] ^ AES_ROUND_CONSTANTS[i / key_words - 1], AES_SBOX[word[2] as usize], AES_SBOX[word[3] as usize], AES_SBOX[word[0] as usize]];
+            } else if key_words > 6 && i % key_words == 4 {
+                word = word.map(|byte: u8| AES_SBOX[byte as usize]);
+            }
+
+            let previous: [u8; 4] = words[i - key_words];
+            words.push([word[0] ^ previous[0], word[1] ^ previous[1], word[2] ^ previous[2], word[3] ^ previous[3]]);
+        }
+
+        return Ok(Aes {
+            round_keys: words.chunks(4).map(|round: &[[u8; 4]]| round.concat().try_into().unwrap()).collect()
+        });
+    }
+
+    fn rounds(&self) -> usize {
+        return self.round_keys.len() - 1;
+    }
+}
+
+impl BlockCipher for Aes {
+    fn block_size(&self) -> usize {
+        return 16;
+    }
+
+    fn encrypt_block(&self, block: &mut [u8]) -> () {
+        add_round_key(block, &self.round_keys[0]);
+
+        for round in 1..=self.rounds() {
+            for byte in block.iter_mut() {
+                *byte = AES_SBOX[*byte as usize];
+            }
+            shift_rows(block, false);
+            if round != self.rounds() {
+                mix_columns(block, false);
+            }
+            add_round_key(block, &self.round_keys[round]);
+        }
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) -> () {
+        add_round_key(block, &self.round_keys[self.rounds()]);
+
+        for round in (0..self.rounds()).rev() {
+            shift_rows(block, true);
+            for byte in block.iter_mut() {
+                *byte = AES_INVERSE_SBOX[*byte as usize];
+            }
+            add_round_key(block, &self.round_keys[round]);
+            if round != 0 {
+                mix_columns(block, true);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod advanced_encryption_standard_test {
+    use crate::{key_derivation::pbkdf2::PASSWORD_HEADER_LENGTH, ops::{bytes_to_hex, hex_to_bytes}};
+    use super::*;
+
+    fn check_fips_197(key: &str, expected: &str) -> () {
+        let cipher: Aes = Aes::new(&hex_to_bytes(key).unwrap()).unwrap();
+        let mut block: Vec<u8> = hex_to_bytes("00112233445566778899aabbccddeeff").unwrap();
+
+        cipher.encrypt_block(&mut block);
+        assert_eq!(bytes_to_hex(&block), expected);
+
+        cipher.decrypt_block(&mut block);
+        assert_eq!(bytes_to_hex(&block), "00112233445566778899aabbccddeeff");
+    }
+
+    #[test]
+    fn fips_197_test() -> () {
+        check_fips_197("000102030405060708090a0b0c0d0e0f", "69c4e0d86a7b0430d8cdb78070b4c55a");
+        check_fips_197("000102030405060708090a0b0c0d0e0f1011121314151617", "dda97ca4864cdfe06eaf70a0ec0d7191");
+        check_fips_197("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f", "8ea2b7ca516745bfeafc49904b496089");
+    }
+
+    #[test]
+    fn key_length_test() -> () {
+        assert!(Aes::new(&[0; 15]).is_err());
+        assert!(Aes::new(&[0; 20]).is_err());
+    }
+
+    #[test]
+    fn sp_800_38a_cbc_test() -> () {
+        let cipher: Aes = Aes::new(&hex_to_bytes("2b7e151628aed2a6abf7158809cf4f3c").unwrap()).unwrap();
+        let iv: Vec<u8> = hex_to_bytes("000102030405060708090a0b0c0d0e0f").unwrap();
+        let plaintext: Vec<u8> = hex_to_bytes("6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51").unwrap();
+
+        let ciphertext: Vec<u8> = cbc_encrypt(&cipher, &iv, &plaintext);
+        assert_eq!(bytes_to_hex(&ciphertext[..32]), "7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b2");
+        assert_eq!(cbc_decrypt(&cipher, &iv, &ciphertext), Ok(plaintext));
+    }
+
+    #[test]
+    fn execute_round_trip_test() -> () {
+        let instance_1: AdvancedEncryptionStandardAlg = AdvancedEncryptionStandardAlg {
+            operation: Operations::Encrypt,
+            key: None,
+            key_format: KeyFormat::Hex,
+            password: Some("correct horse".to_string()),
+            kdf: PasswordKdf::Pbkdf2,
+            iterations: 10,
+            key_size: 128,
+            message: "attack at dawn".to_string()
+        };
+        let ciphertext: Vec<u8> = instance_1.encrypt().unwrap();
+        assert_eq!(ciphertext.len(), PASSWORD_HEADER_LENGTH + 2 * AES_BLOCK_SIZE);
+        assert_ne!(ciphertext, instance_1.encrypt().unwrap());
+
+        let instance_2: AdvancedEncryptionStandardAlg = AdvancedEncryptionStandardAlg {
+            operation: Operations::Decrypt,
+            message: bytes_to_hex(&ciphertext),
+            ..instance_1.clone()
+        };
+        assert_eq!(instance_2.decrypt(), Ok(b"attack at dawn".to_vec()));
+
+        let instance_3: AdvancedEncryptionStandardAlg = AdvancedEncryptionStandardAlg {
+            password: Some("wrong horse".to_string()),
+            ..instance_2.clone()
+        };
+        assert_ne!(instance_3.decrypt(), Ok(b"attack at dawn".to_vec()));
+
+        let instance_4: AdvancedEncryptionStandardAlg = AdvancedEncryptionStandardAlg {
+            key: Some("000102030405060708090a0b0c0d0e0f1011121314151617".to_string()),
+            password: None,
+            ..instance_1.clone()
+        };
+        let instance_5: AdvancedEncryptionStandardAlg = AdvancedEncryptionStandardAlg {
+            operation: Operations::Decrypt,
+            message: bytes_to_hex(&instance_4.encrypt().unwrap()),
+            ..instance_4.clone()
+        };
+        assert_eq!(instance_5.decrypt(), Ok(b"attack at dawn".to_vec()));
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,107 @@
+use clap::Args;
+use crate::{algorithms::polybius_square::{fractionate, unfractionate, PolybiusSquare}, alphabet::POLYBIUS_ALPHABET_5X5, Operations};
+
+#[derive(Debug, Args, Clone)]
+pub struct BifidCipherAlg {
+    /// Encode or Decode Operation
+    #[arg(short, long)]
+    pub operation: Operations,
+    /// The keyword used to fill the Polybius square
+    #[arg(short, long, default_value = "")]
+    pub key: String,
+    /// Number of letters fractionated together (0 uses the whole message)
+    #[arg(short, long, default_value_t = 0)]
+    pub period: usize,
+    /// The message to encode or decode
+    #[arg(short, long)]
+    pub message: String
+}
+
+impl BifidCipherAlg {
+    pub fn execute(self) -> () {
+        match self.operation {
+            Operations::Encrypt => {
+                println!("{}", self.encode());
+            }
+            Operations::Decrypt => {
+                println!("{}", self.decode());
+            }
+        }
+    }
+
+    fn encode(&self) -> String {
+        let square: PolybiusSquare = PolybiusSquare::new(&self.key, POLYBIUS_ALPHABET_5X5);
+        let coordinates: Vec<Vec<usize>> = self.message_coordinates(&square);
+
+        return fractionate(&coordinates, self.period)
+                .iter()
+                .map(|coord: &Vec<usize>| square.symbol_at(coord[0], coord[1]))
+                .collect();
+    }
+
+    fn decode(&self) -> String {
+        let square: PolybiusSquare = PolybiusSquare::new(&self.key, POLYBIUS_ALPHABET_5X5);
+        let coordinates: Vec<Vec<usize>> = self.message_coordinates(&square);
+
+        return unfractionate(&coordinates, self.period)
+                .iter()
+                .map(|coord: &Vec<usize>| square.symbol_at(coord[0], coord[1]))
+                .collect();
+    }
+
+    fn message_coordinates(&self, square: &PolybiusSquare) -> Vec<Vec<usize>> {
+        return square.normalize(&self.message)
+                     .iter()
+                     .map(|c: &char| {
+                        let (row, col): (usize, usize) = square.coordinates(*c).unwrap();
+                        vec![row, col]
+                     })
+                     .collect();
+    }
+}
+
+#[cfg(test)]
+mod bifid_cipher_test {
+    use super::*;
+
+    #[test]
+    fn encode_test() -> () {
+        let instance_1: BifidCipherAlg = BifidCipherAlg {
+            operation: Operations::Encrypt,
+            key: "BGWKZQPNDSIOAXEFCLUMTHYVR".to_string(),
+            period: 0,
+            message: "flee at once".to_string()
+        };
+        assert_eq!(instance_1.encode(), "UAEOLWRINS");
+    }
+
+    #[test]
+    fn decode_test() -> () {
+        let instance_1: BifidCipherAlg = BifidCipherAlg {
+            operation: Operations::Decrypt,
+            key: "BGWKZQPNDSIOAXEFCLUMTHYVR".to_string(),
+            period: 0,
+            message: "UAEOLWRINS".to_string()
+        };
+        assert_eq!(instance_1.decode(), "FLEEATONCE");
+    }
+
+    #[test]
+    fn period_round_trip_test() -> () {
+        let instance_1: BifidCipherAlg = BifidCipherAlg {
+            operation: Operations::Encrypt,
+            key: "playfair example".to_string(),
+            period: 5,
+            message: "defend the east wall of the castle".to_string()
+        };
+        let encoded: String = instance_1.encode();
+
+        let instance_2: BifidCipherAlg = BifidCipherAlg {
+            operation: Operations::Decrypt,
+            key: instance_1.key.clone(),
+            period: 5,
+            message: encoded
+        };
+        assert_eq!(instance_2.decode(), "DEFENDTHEEASTWALLOFTHECASTLE");
+    }
+}
@@ -0,0 +1,81 @@
+pub trait BlockCipher {
+    fn block_size(&self) -> usize;
+    fn encrypt_block(&self, block: &mut [u8]) -> ();
+    fn decrypt_block(&self, block: &mut [u8]) -> ();
+}
+
+
+
+
+pub fn pkcs7_pad(data: &[u8], block_size: usize) -> Vec<u8> {
+    let padding: usize = block_size - data.len() % block_size;
+    let mut padded: Vec<u8> = data.to_vec();
+    padded.extend(vec![padding as u8; padding]);
+
+    return padded;
+}
+
+
+
+
+pub fn pkcs7_unpad(data: &[u8], block_size: usize) -> Result<Vec<u8>, String> {
+    let padding: usize = *data.last().ok_or("ciphertext is empty")? as usize;
+
+    if !data.len().is_multiple_of(block_size) || padding == 0 || padding > block_size || data[data.len() - padding..].iter().any(|byte: &u8| *byte as usize != padding) {
+        return Err("invalid padding".to_string());
+    }
+    return Ok(data[..data.len() - padding].to_vec());
+}
+
+
+
+
+pub fn cbc_encrypt(cipher: &dyn BlockCipher, iv: &[u8], plaintext: &[u8]) -> Vec<u8> {
+    let mut data: Vec<u8> = pkcs7_pad(plaintext, cipher.block_size());
+    let mut previous: Vec<u8> = iv.to_vec();
+
+    for block in data.chunks_mut(cipher.block_size()) {
+        for (byte, previous_byte) in block.iter_mut().zip(&previous) {
+            *byte ^= previous_byte;
+        }
+        cipher.encrypt_block(block);
+        previous = block.to_vec();
+    }
+    return data;
+}
+
+
+
+
+pub fn cbc_decrypt(cipher: &dyn BlockCipher, iv: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, String> {
+    if ciphertext.is_empty() || !ciphertext.len().is_multiple_of(cipher.block_size()) {
+        return Err(format!("ciphertext must be a whole number of {} byte blocks", cipher.block_size()));
+    }
+
+    let mut data: Vec<u8> = ciphertext.to_vec();
+    let mut previous: Vec<u8> = iv.to_vec();
+
+    for block in data.chunks_mut(cipher.block_size()) {
+        let encrypted: Vec<u8> = block.to_vec();
+        cipher.decrypt_block(block);
+        for (byte, previous_byte) in block.iter_mut().zip(&previous) {
+            *byte ^= previous_byte;
+        }
+        previous = encrypted;
+    }
+    return pkcs7_unpad(&data, cipher.block_size());
+}
+
+#[cfg(test)]
+mod block_cipher_test {
+    use super::*;
+
+    #[test]
+    fn pkcs7_test() -> () {
+        assert_eq!(pkcs7_pad(b"YELLOW SUBMARINE", 20), b"YELLOW SUBMARINE\x04\x04\x04\x04".to_vec());
+        assert_eq!(pkcs7_pad(b"12345678", 8), b"12345678\x08\x08\x08\x08\x08\x08\x08\x08".to_vec());
+        assert_eq!(pkcs7_unpad(b"ICE ICE BABY\x04\x04\x04\x04", 16), Ok(b"ICE ICE BABY".to_vec()));
+        assert!(pkcs7_unpad(b"ICE ICE BABY\x05\x05\x05\x05", 16).is_err());
+        assert!(pkcs7_unpad(b"ICE ICE BABY\x01\x02\x03\x04", 16).is_err());
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{algorithms::block_cipher::BlockCipher, tables::{BLOWFISH_P_ARRAY, BLOWFISH_S_BOXES}};
+
+const BLOWFISH_ROUNDS: usize = 16;
+
+// Cycles through the bytes of a key or salt, four at a time, as big endian words
+pub struct WordStream<'a> {
+    data: &'a [u8],
+    position: usize
+}
+impl<'a> WordStream<'a> {
+    pub fn new(data: &'a [u8]) -> WordStream<'a> {
+        return WordStream { data: data, position: 0 };
+    }
+
+    pub fn next_word(&mut self) -> u32 {
+        let mut word: u32 = 0;
+
+        for _ in 0..4 {
+            word = (word << 8) | self.data[self.position] as u32;
+            self.position = (self.position + 1) % self.data.len();
+        }
+        return word;
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Blowfish {
+    p: [u32; 18],
+    s: [[u32; 256]; 4]
+}
+impl Blowfish {
+    // The digits of pi, before any key is mixed in
+    pub fn initial_state() -> Blowfish {
+        return Blowfish { p: BLOWFISH_P_ARRAY, s: BLOWFISH_S_BOXES };
+    }
+
+    pub fn new(key: &[u8]) -> Result<Blowfish, String> {
+        if key.is_empty() || key.len() > 56 {
+            return Err("Blowfish key must be 1 to 56 bytes".to_string());
+        }
+
+        let mut blowfish: Blowfish = Blowfish::initial_state();
+        blowfish.expand_key(key, &[]);
+
+        return Ok(blowfish);
+    }
+
+    fn feistel(&self, x: u32) -> u32 {
+        let [a, b, c, d]: [u8; 4] = x.to_be_bytes();
+
+        return (self.s[0][a as usize].wrapping_add(self.s[1][b as usize]) ^ self.s[2][c as usize]).wrapping_add(self.s[3][d as usize]);
+    }
+
+    pub fn encrypt_words(&self, mut left: u32, mut right: u32) -> (u32, u32) {
+        for round in 0..BLOWFISH_ROUNDS {
+            left ^= self.p[round];
+            right ^= self.feistel(left);
+            (left, right) = (right, left);
+        }
+        return (right ^ self.p[17], left ^ self.p[16]);
+    }
+
+    fn decrypt_words(&self, mut left: u32, mut right: u32) -> (u32, u32) {
+        for round in (2..BLOWFISH_ROUNDS + 2).rev() {
+            left ^= self.p[round];
+            right ^= self.feistel(left);
+            (left, right) = (right, left);
+        }
+        return (right ^ self.p[0], left ^ self.p[1]);
+    }
+
+    // Key schedule, with the salt of EksBlowfish xored into each block before it is encrypted
+    // (an empty salt gives the plain Blowfish key schedule)
+    pub fn expand_key(&mut self, key: &[u8], salt: &[u8]) -> () {
+        let mut key_words: WordStream = WordStream::new(key);
+        for p_i in self.p.iter_mut() {
+            *p_i ^= key_words.next_word();
+        }
+
+        let mut salt_words: Option<WordStream> = if salt.is_empty() { None } else { Some(WordStream::new(salt)) };
+        let mut block: (u32, u32) = (0, 0);
+
+        for i in 0..(18 + 4 * 256) / 2 {
+            if let Some(words) = salt_words.as_mut() {
+                block = (block.0 ^ words.next_word(), block.1 ^ words.next_word());
+            }
+            block = self.encrypt_words(block.0, block.1);
+
+            if i < 9 {
+                self.p[2 * i] = block.0;
+                self.p[2 * i + 1] = block.1;
+            } else {
+                let index: usize = 2 * (i - 9);
+                self.s[index / 256][index % 256] = block.0;
+                self.s[index / 256][index % 256 + 1] = block.1;
+            }
+        }
+    }
+}
+
+impl BlockCipher for Blowfish {
+    fn block_size(&self) -> usize {
+        return 8;
+    }
+
+    fn encrypt_block(&self, block: &mut [u8]) -> () {
+        let (left, right): (u32, u32) = self.encrypt_words(u32::from_be_bytes(block[0..4].try_into().unwrap()), u32::from_be_bytes(block[4..8].try_into().unwrap()));
+
+        block[0..4].copy_from_slice(&left.to_be_bytes());
+        block[4..8].copy_from_slice(&right.to_be_bytes());
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) -> () {
+        let (left, right): (u32, u32) = self.decrypt_words(u32::from_be_bytes(block[0..4].try_into().unwrap()), u32::from_be_bytes(block[4..8].try_into().unwrap()));
+
+        block[0..4].copy_from_slice(&left.to_be_bytes());
+        block[4..8].copy_from_slice(&right.to_be_bytes());
+    }
+}
+
+#[cfg(test)]
+mod blowfish_test {
+    use crate::ops::{bytes_to_hex, hex_to_bytes};
+    use super::*;
+
+    fn check_block(key: &str, plaintext: &str, ciphertext: &str) -> () {
+        let cipher: Blowfish = Blowfish::new(&hex_to_bytes(key).unwrap()).unwrap();
+        let mut block: Vec<u8> = hex_to_bytes(plaintext).unwrap();
+
+        cipher.encrypt_block(&mut block);
+        assert_eq!(bytes_to_hex(&block), ciphertext);
+
+        cipher.decrypt_block(&mut block);
+        assert_eq!(bytes_to_hex(&block), plaintext);
+    }
+
+    #[test]
+    fn eric_young_vectors_test() -> () {
+        check_block("0000000000000000", "0000000000000000", "4ef997456198dd78");
+        check_block("ffffffffffffffff", "ffffffffffffffff", "51866fd5b85ecb8a");
+        check_block("3000000000000000", "1000000000000001", "7d856f9a613063f2");
+        check_block("0123456789abcdef", "1111111111111111", "61f9c3802281b096");
+        check_block("fedcba9876543210", "0123456789abcdef", "0aceab0fc6a0a28d");
+        assert!(Blowfish::new(&[]).is_err());
+    }
+}
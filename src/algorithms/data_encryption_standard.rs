@@ -1,76 +1,85 @@
 use clap::Args;
-use crate::{ops::{bits_into_string, byte_parity_verify, is_a_7bit_chunk, is_a_8bit_chunk, is_a_8bytes_block, left_shift_vec, make_64bits_blocks, permutation, string_into_bits}, tables::{DES_EXPANSION_TABLE, DES_FINAL_PERMUTATION_TABLE, DES_INITIAL_PERMUTATION_TABLE, DES_ITERATION_LEFT_SHIFT_TABLE, DES_PERMUTED_CHOICE_1, DES_PERMUTED_CHOICE_2}, Operations};
+use crate::{algorithms::block_cipher::{cbc_decrypt, cbc_encrypt, BlockCipher}, key_derivation::pbkdf2::{random_salt, PasswordHeader, PasswordKdf, DEFAULT_ITERATIONS}, ops::{bits_into_bytes, bytes_into_bits, bytes_to_hex, hex_to_bytes, is_a_7bit_chunk, permutation}, tables::{DES_EXPANSION_TABLE, DES_FINAL_PERMUTATION_TABLE, DES_INITIAL_PERMUTATION_TABLE, DES_ITERATION_LEFT_SHIFT_TABLE, DES_PERMUTATION_TABLE, DES_PERMUTED_CHOICE_1, DES_PERMUTED_CHOICE_2, DES_S_BOX_1_TABLE, DES_S_BOX_2_TABLE, DES_S_BOX_3_TABLE, DES_S_BOX_4_TABLE, DES_S_BOX_5_TABLE, DES_S_BOX_6_TABLE, DES_S_BOX_7_TABLE, DES_S_BOX_8_TABLE}, Operations};
+
+const DES_BLOCK_SIZE: usize = 8;
 
 #[derive(Debug, Args, Clone)]
 pub struct DataEncryptionStandardAlg {
     /// Encode or Decode Operation
     #[arg(short, long)]
     pub operation: Operations,
-    /// The secret word for encrypt
+    /// The secret word for encrypt: 8 characters for DES, 16 or 24 for Triple DES
+    #[arg(short, long, conflicts_with = "password", required_unless_present = "password")]
+    pub key: Option<String>,
+    /// Derive the key from a passphrase (the KDF, its cost and the salt are stored in front of the ciphertext)
     #[arg(short, long)]
-    pub key: String,
-    /// The message to encode or decode
+    pub password: Option<String>,
+    /// Password KDF used when encrypting with --password
+    #[arg(long, value_enum, default_value = "pbkdf2")]
+    pub kdf: PasswordKdf,
+    /// PBKDF2 iterations used when encrypting with --password
+    #[arg(short, long, default_value_t = DEFAULT_ITERATIONS)]
+    pub iterations: u32,
+    /// Length in bytes of a password derived key: 8 for DES, 16 or 24 for Triple DES
+    #[arg(long, default_value_t = 24)]
+    pub key_length: usize,
+    /// The message to encode, or the hex ciphertext (IV first) to decode
     #[arg(short, long)]
     pub message: String
 }
 impl DataEncryptionStandardAlg {
-    
+
     pub fn execute(self) -> () {
-        
+
         match self.operation {
             Operations::Encrypt => {
-                self.encrypt();
+                match self.encrypt() {
+                    Ok(ciphertext) => println!("{}", bytes_to_hex(&ciphertext)),
+                    Err(error) => println!("{}", error)
+                }
             }
             Operations::Decrypt => {
-                self.decrypt();
+                match self.decrypt() {
+                    Ok(plain) => println!("{}", String::from_utf8_lossy(&plain)),
+                    Err(error) => println!("{}", error)
+                }
             }
         }
     }
 
-    fn encrypt(self) {
-        // Prepare Keys
-        let mut keys_for_16_rounds: Vec<PermutedChoice>;
-
-        let key_in_bits: Vec<u8> = string_into_bits(self.key.clone());
-        let key_in_blocks: Vec<&[u8]> = key_in_bits.chunks(8).collect();
-        let verify_blocks_have_64bits: Vec<[u8; 8]> = key_in_blocks.iter().map(|chunk: &&[u8]| is_a_8bit_chunk(chunk)).collect();
-        let verify_key_parity:  [[u8; 8]; 8] = is_a_8bytes_block(verify_blocks_have_64bits.iter().map(|chunk: &[u8; 8]| byte_parity_verify(*chunk)).collect());
-        
-        let c_0_d_0: PermutedChoice = PermutedChoice::permuted_choice_1(verify_key_parity);
-        let mut left_key: Vec<u8> = c_0_d_0.permuted_choice_c.concat();
-        let mut right_key: Vec<u8> = c_0_d_0.permuted_choice_d.concat();
-        let mut shift_n: i32;
-
-        for round in 0..=16 {
-            shift_n = DES_ITERATION_LEFT_SHIFT_TABLE[round] as i32;
-            
-            left_key Vec<[u8;]>= left_shift_vec(left_key, shift_n as usize).chunks(7).collect();
-            right_key = left_shift_vec(right_key, shift_n as usize).chunks(7).collect();
-
-            keys_for_16_rounds.push(PermutedChoice {
-                permuted_choice_c: left_key,  
-                permuted_choice_d: right_key
-            })
-        }
-
-
-        /////////////////////////////////
+    fn cipher(&self, header: &Option<PasswordHeader>) -> Result<Box<dyn BlockCipher>, String> {
+        let key: Vec<u8> = match (&self.key, &self.password, header) {
+            (Some(key), _, _) => key.as_bytes().to_vec(),
+            (None, Some(password), Some(header)) => header.key(password, self.key_length)?,
+            (None, _, _) => return Err("a key or password is required".to_string())
+        };
 
+        if key.len() == 8 {
+            return Ok(Box::new(DesCipher::new(&key)?));
+        }
+        return Ok(Box::new(TripleDes::new(&key)?));
+    }
 
+    // CBC with a random IV, as for AES: the output is [password header] || IV || ciphertext
+    fn encrypt(&self) -> Result<Vec<u8>, String> {
+        let header: Option<PasswordHeader> = self.password.as_ref().map(|_| PasswordHeader::new(&self.kdf, self.iterations));
+        let iv: Vec<u8> = random_salt(DES_BLOCK_SIZE);
+        let ciphertext: Vec<u8> = cbc_encrypt(self.cipher(&header)?.as_ref(), &iv, self.message.as_bytes());
 
+        return Ok([header.map(|header: PasswordHeader| header.to_bytes()).unwrap_or_default(), iv, ciphertext].concat());
+    }
 
-        let blocks: Vec<[[u8; 8]; 8]> = make_64bits_blocks(string_into_bits(self.clone().message));
-        let permuted_blocks: Vec<Vec<u8>> = blocks.iter().map(|block: &[[u8; 8]; 8]| permutation(block.clone().concat(), DES_INITIAL_PERMUTATION_TABLE.concat())).collect();
-        let perm_sep_blocks: Vec<BlockLeftRight> = permuted_blocks.iter().map(|block:&Vec<u8>| {BlockLeftRight::separate(block)}).collect();
-        
+    fn decrypt(&self) -> Result<Vec<u8>, String> {
+        let data: Vec<u8> = hex_to_bytes(&self.message)?;
+        let (header, data): (Option<PasswordHeader>, &[u8]) = if self.password.is_some() { PasswordHeader::split(&data).map(|(header, data)| (Some(header), data))? } else { (None, &data) };
 
-        let output_blocks: Vec<Vec<u8>> = permuted_blocks.iter().map(|block: &Vec<u8>| permutation(block.clone(), DES_FINAL_PERMUTATION_TABLE.concat())).collect();
-        println!("{}", bits_into_string(output_blocks.concat()));
+        if data.len() < DES_BLOCK_SIZE {
+            return Err("ciphertext must start with an 8 byte IV".to_string());
         }
-    
-    fn decrypt(self) {}
+        let (iv, ciphertext): (&[u8], &[u8]) = data.split_at(DES_BLOCK_SIZE);
 
-    
+        return cbc_decrypt(self.cipher(&header)?.as_ref(), iv, ciphertext);
+    }
 }
 
 struct BlockLeftRight {
@@ -81,8 +90,8 @@ impl BlockLeftRight {
     fn separate(block: &[u8]) -> BlockLeftRight {
         let left: Vec<u8> = block[0..(block.len())/2].to_vec();
         let right: Vec<u8> = block[(block.len())/2..block.len()].to_vec();
-        
-        return 
+
+        return
             BlockLeftRight {
                 left: left,
                 right: right
@@ -93,82 +102,252 @@ impl BlockLeftRight {
 
 #[derive(PartialEq, Debug)]
 struct PermutedChoice {
-    permuted_choice_c: [u8; 28],
-    permuted_choice_d: [u8; 28]
+    permuted_choice_c: [[u8; 7]; 4],
+    permuted_choice_d: [[u8; 7]; 4]
 }
 impl PermutedChoice {
-    fn permuted_choice_1(parity_verified_key: [[u8; 8]; 8]) -> PermutedChoice {
-        let parity_verified_key_concat = parity_verified_key.concat();
-        let permuted_key: Vec<u8> = permutation(parity_verified_key_concat, DES_PERMUTED_CHOICE_1.concat());
+    fn permuted_choice_1(key: [[u8; 8]; 8]) -> PermutedChoice {
+        let permuted_key: Vec<u8> = permutation(key.concat(), DES_PERMUTED_CHOICE_1.concat());
 
         let sep_key: BlockLeftRight = BlockLeftRight::separate(&permuted_key);
-        let mut left_chunks: Vec<u8> = sep_key.left.chunks(28).map(|x| is_a_7bit_chunk(x)).collect();
-        let mut right_chunks: Vec<u8> = sep_key.right.chunks(28).map(|x| is_a_7bit_chunk(x)).collect();
-
+        let left_chunks: Vec<[u8; 7]> = sep_key.left.chunks(7).map(|x: &[u8]| is_a_7bit_chunk(x)).collect();
+        let right_chunks: Vec<[u8; 7]> = sep_key.right.chunks(7).map(|x: &[u8]| is_a_7bit_chunk(x)).collect();
 
         return PermutedChoice {
             permuted_choice_c:  left_chunks.try_into().unwrap(),
             permuted_choice_d:  right_chunks.try_into().unwrap()
         }
     }
-    
-    fn permuted_choice_2(parity_verified_key: [[u8; 8]; 8]) -> PermutedChoice {
-        let parity_verified_key_concat = parity_verified_key.concat();
-        let permuted_key: Vec<u8> = permutation(parity_verified_key_concat, DES_PERMUTED_CHOICE_2.concat());
 
-        let sep_key: BlockLeftRight = BlockLeftRight::separate(&permuted_key);
-        let mut left_chunks: Vec<[u8; 7]> = sep_key.left.chunks(7).map(|x| is_a_7bit_chunk(x)).collect();
-        let mut right_chunks: Vec<[u8; 7]> = sep_key.right.chunks(7).map(|x| is_a_7bit_chunk(x)).collect();
+    // The 16 round keys: C and D rotate left on each round, then PC-2 picks 48 of their 56 bits
+    fn key_schedule(self) -> Vec<Vec<u8>> {
+        let mut c: Vec<u8> = self.permuted_choice_c.concat();
+        let mut d: Vec<u8> = self.permuted_choice_d.concat();
+        let mut subkeys: Vec<Vec<u8>> = Vec::new();
 
-        while left_chunks.len() < 4 {
-            left_chunks.push([0,0,0,0,0,0,0]);
+        for shift in DES_ITERATION_LEFT_SHIFT_TABLE {
+            c.rotate_left(shift as usize);
+            d.rotate_left(shift as usize);
+
+            subkeys.push(permutation([c.clone(), d.clone()].concat(), DES_PERMUTED_CHOICE_2.concat()));
         }
+        return subkeys;
+    }
+}
+
+const DES_S_BOXES: [[[u8; 16]; 4]; 8] = [DES_S_BOX_1_TABLE, DES_S_BOX_2_TABLE, DES_S_BOX_3_TABLE, DES_S_BOX_4_TABLE, DES_S_BOX_5_TABLE, DES_S_BOX_6_TABLE, DES_S_BOX_7_TABLE, DES_S_BOX_8_TABLE];
 
-        while right_chunks.len() < 4 {
-            right_chunks.push([0,0,0,0,0,0,0]);
+// f(R, K): expansion to 48 bits, key mixing, the eight S-boxes and the P permutation
+fn cipher_function(right: &[u8], subkey: &[u8], expansion: &[u8]) -> Vec<u8> {
+    let expanded: Vec<u8> = permutation(right.to_vec(), expansion.to_vec());
+    let mixed: Vec<u8> = expanded.iter().zip(subkey).map(|(bit, key_bit): (&u8, &u8)| bit ^ key_bit).collect();
+
+    let substituted: Vec<u8> = mixed
+        .chunks(6)
+        .zip(DES_S_BOXES)
+        .flat_map(|(six_bits, s_box): (&[u8], [[u8; 16]; 4])| {
+            let row: usize = (2 * six_bits[0] + six_bits[5]) as usize;
+            let column: usize = (8 * six_bits[1] + 4 * six_bits[2] + 2 * six_bits[3] + six_bits[4]) as usize;
+            let value: u8 = s_box[row][column];
+
+            [(value >> 3) & 1, (value >> 2) & 1, (value >> 1) & 1, value & 1]
+        })
+        .collect();
+
+    return permutation(substituted, DES_PERMUTATION_TABLE.concat());
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DesCipher {
+    subkeys: Vec<Vec<u8>>,
+    expansion: Vec<u8>
+}
+impl DesCipher {
+    pub fn new(key: &[u8]) -> Result<DesCipher, String> {
+        return DesCipher::with_expansion(key, DES_EXPANSION_TABLE.concat());
+    }
+
+    // crypt(3) perturbs the expansion table with its salt, everything else is plain DES
+    pub fn with_expansion(key: &[u8], expansion: Vec<u8>) -> Result<DesCipher, String> {
+        if key.len() != 8 {
+            return Err("DES key must be 8 bytes".to_string());
         }
 
-        return PermutedChoice {
-            permuted_choice_c:  left_chunks.try_into().unwrap(),
-            permuted_choice_d:  right_chunks.try_into().unwrap()
+        let key_bits: [[u8; 8]; 8] = key.iter().map(|byte: &u8| bytes_into_bits(&[*byte]).try_into().unwrap()).collect::<Vec<[u8; 8]>>().try_into().unwrap();
+
+        return Ok(DesCipher {
+            subkeys: PermutedChoice::permuted_choice_1(key_bits).key_schedule(),
+            expansion: expansion
+        });
+    }
+
+    fn feistel(&self, block: &mut [u8], decrypt: bool) -> () {
+        let permuted: Vec<u8> = permutation(bytes_into_bits(block), DES_INITIAL_PERMUTATION_TABLE.concat());
+        let BlockLeftRight { mut left, mut right } = BlockLeftRight::separate(&permuted);
+
+        for round in 0..16 {
+            let subkey: &Vec<u8> = if decrypt { &self.subkeys[15 - round] } else { &self.subkeys[round] };
+            let new_right: Vec<u8> = left.iter().zip(cipher_function(&right, subkey, &self.expansion)).map(|(bit, f_bit): (&u8, u8)| bit ^ f_bit).collect();
+
+            left = right;
+            right = new_right;
         }
+
+        let output: Vec<u8> = permutation([right, left].concat(), DES_FINAL_PERMUTATION_TABLE.concat());
+        block.copy_from_slice(&bits_into_bytes(&output));
     }
 }
 
-/*
-fn cipher_function_des(left_right: BlockLeftRight, key: &[u8]) -> BlockLeftRight {}
+impl BlockCipher for DesCipher {
+    fn block_size(&self) -> usize {
+        return DES_BLOCK_SIZE;
+    }
 
-fn ks_generate_key() -> () {}
+    fn encrypt_block(&self, block: &mut [u8]) -> () {
+        self.feistel(block, false);
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) -> () {
+        self.feistel(block, true);
+    }
+}
 
 
 
-fn key_derivation(key: String) -> [[u8; 8]; 8] {
-    let key_57bits: &[u8] = &string_into_bits(key)[0..=57];
 
-    
+// Encrypt-decrypt-encrypt with two (K1, K2, K1) or three independent keys
+#[derive(Debug, Clone, PartialEq)]
+pub struct TripleDes {
+    ciphers: [DesCipher; 3]
+}
+impl TripleDes {
+    pub fn new(key: &[u8]) -> Result<TripleDes, String> {
+        let third_key: &[u8] = match key.len() {
+            16 => &key[0..8],
+            24 => &key[16..24],
+            _ => return Err("Triple DES key must be 16 or 24 bytes".to_string())
+        };
+
+        return Ok(TripleDes {
+            ciphers: [DesCipher::new(&key[0..8])?, DesCipher::new(&key[8..16])?, DesCipher::new(third_key)?]
+        });
+    }
+}
+
+impl BlockCipher for TripleDes {
+    fn block_size(&self) -> usize {
+        return DES_BLOCK_SIZE;
+    }
+
+    fn encrypt_block(&self, block: &mut [u8]) -> () {
+        self.ciphers[0].encrypt_block(block);
+        self.ciphers[1].decrypt_block(block);
+        self.ciphers[2].encrypt_block(block);
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) -> () {
+        self.ciphers[2].decrypt_block(block);
+        self.ciphers[1].encrypt_block(block);
+        self.ciphers[0].decrypt_block(block);
+    }
 }
-*/
 
 #[cfg(test)]
 mod data_encryption_standard_test {
+    use crate::{key_derivation::pbkdf2::PASSWORD_HEADER_LENGTH, ops::make_64bits_blocks};
     use super::*;
 
     #[test]
     fn test_permuted_choice() -> () {
         assert_eq!(PermutedChoice::permuted_choice_1([[0; 8];8]), PermutedChoice { permuted_choice_c: [[0; 7];4], permuted_choice_d: [[0; 7];4]});
         let vec_test: [[u8; 8]; 8] = make_64bits_blocks((1..=64).collect())[0];
-        assert_eq!(PermutedChoice::permuted_choice_1(vec_test), PermutedChoice { 
-            permuted_choice_c:                                     
-            [[57, 49, 41, 33, 25, 17, 09], 
+        assert_eq!(PermutedChoice::permuted_choice_1(vec_test), PermutedChoice {
+            permuted_choice_c:
+            [[57, 49, 41, 33, 25, 17, 09],
              [01, 58, 50, 42, 34, 26, 18],
              [10, 02, 59, 51, 43, 35, 27],
              [19, 11, 03, 60, 52, 44, 36]],
 
-            permuted_choice_d: 
+            permuted_choice_d:
             [[63, 55, 47, 39, 31, 23, 15],
              [07, 62, 54, 46, 38, 30, 22],
              [14, 06, 61, 53, 45, 37, 29],
              [21, 13, 05, 28, 20, 12, 04]]
         });
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_des_block() -> () {
+        let cipher: DesCipher = DesCipher::new(&hex_to_bytes("133457799bbcdff1").unwrap()).unwrap();
+        let mut block: Vec<u8> = hex_to_bytes("0123456789abcdef").unwrap();
+
+        cipher.encrypt_block(&mut block);
+        assert_eq!(bytes_to_hex(&block), "85e813540f0ab405");
+
+        cipher.decrypt_block(&mut block);
+        assert_eq!(bytes_to_hex(&block), "0123456789abcdef");
+    }
+
+    #[test]
+    fn test_triple_des_block() -> () {
+        let cipher: TripleDes = TripleDes::new(&hex_to_bytes("0123456789abcdef23456789abcdef01456789abcdef0123").unwrap()).unwrap();
+        let mut block: Vec<u8> = b"The qufc".to_vec();
+
+        cipher.encrypt_block(&mut block);
+        assert_eq!(bytes_to_hex(&block), "a826fd8ce53b855f");
+
+        cipher.decrypt_block(&mut block);
+        assert_eq!(block, b"The qufc".to_vec());
+        assert!(TripleDes::new(&[0; 8]).is_err());
+    }
+
+    #[test]
+    fn test_execute_round_trip() -> () {
+        let instance_1: DataEncryptionStandardAlg = DataEncryptionStandardAlg {
+            operation: Operations::Encrypt,
+            key: Some("8bytekey".to_string()),
+            password: None,
+            kdf: PasswordKdf::Pbkdf2,
+            iterations: 10,
+            key_length: 24,
+            message: "attack at dawn".to_string()
+        };
+        let ciphertext: String = bytes_to_hex(&instance_1.encrypt().unwrap());
+        assert_eq!(ciphertext.len(), 2 * (DES_BLOCK_SIZE + 16));
+        assert_ne!(bytes_to_hex(&instance_1.encrypt().unwrap()), ciphertext);
+
+        let instance_2: DataEncryptionStandardAlg = DataEncryptionStandardAlg {
+            operation: Operations::Decrypt,
+            message: ciphertext,
+            ..instance_1.clone()
+        };
+        assert_eq!(instance_2.decrypt().unwrap(), b"attack at dawn".to_vec());
+
+        let repeated: Vec<u8> = DataEncryptionStandardAlg { message: "identical block!".repeat(2), ..instance_1.clone() }.encrypt().unwrap();
+        assert_ne!(repeated[8..24], repeated[24..40]);
+
+        let instance_3: DataEncryptionStandardAlg = DataEncryptionStandardAlg {
+            key: Some("short".to_string()),
+            ..instance_1.clone()
+        };
+        assert!(instance_3.encrypt().is_err());
+
+        let instance_4: DataEncryptionStandardAlg = DataEncryptionStandardAlg {
+            key: None,
+            password: Some("passphrase".to_string()),
+            ..instance_1.clone()
+        };
+        let ciphertext: Vec<u8> = instance_4.encrypt().unwrap();
+        assert_eq!(ciphertext.len(), PASSWORD_HEADER_LENGTH + DES_BLOCK_SIZE + 16);
+
+        let instance_5: DataEncryptionStandardAlg = DataEncryptionStandardAlg {
+            operation: Operations::Decrypt,
+            message: bytes_to_hex(&ciphertext),
+            ..instance_4.clone()
+        };
+        assert_eq!(instance_5.decrypt().unwrap(), b"attack at dawn".to_vec());
+    }
+}
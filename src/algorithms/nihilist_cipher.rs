@@ -0,0 +1,124 @@
+use clap::Args;
+use crate::{algorithms::polybius_square::PolybiusSquare, alphabet::POLYBIUS_ALPHABET_5X5, Operations};
+
+#[derive(Debug, Args, Clone)]
+pub struct NihilistCipherAlg {
+    /// Encode or Decode Operation
+    #[arg(short, long)]
+    pub operation: Operations,
+    /// The keyword used to fill the Polybius square
+    #[arg(short, long, default_value = "")]
+    pub square_key: String,
+    /// The secret word added to the message
+    #[arg(short, long)]
+    pub key: String,
+    /// The message to encode or decode
+    #[arg(short, long)]
+    pub message: String
+}
+
+impl NihilistCipherAlg {
+    pub fn execute(self) -> () {
+        match self.operation {
+            Operations::Encrypt => {
+                match self.encode() {
+                    Ok(encoded_message) => println!("{}", encoded_message),
+                    Err(error) => println!("{}", error)
+                }
+            }
+            Operations::Decrypt => {
+                match self.decode() {
+                    Ok(decoded_message) => println!("{}", decoded_message),
+                    Err(error) => println!("{}", error)
+                }
+            }
+        }
+    }
+
+    fn encode(&self) -> Result<String, String> {
+        let square: PolybiusSquare = PolybiusSquare::new(&self.square_key, POLYBIUS_ALPHABET_5X5);
+        let key_values: Vec<usize> = square_values(&square, &self.key);
+
+        if key_values.is_empty() {
+            return Err("the key must have at least one letter".to_string());
+        }
+
+        return Ok(square_values(&square, &self.message)
+                    .iter()
+                    .zip(key_values.iter().cycle())
+                    .map(|(x, y)| (x + y).to_string())
+                    .collect::<Vec<String>>()
+                    .join(" "));
+    }
+
+    fn decode(&self) -> Result<String, String> {
+        let square: PolybiusSquare = PolybiusSquare::new(&self.square_key, POLYBIUS_ALPHABET_5X5);
+        let key_values: Vec<usize> = square_values(&square, &self.key);
+        let mut decoded_message: String = String::new();
+
+        if key_values.is_empty() {
+            return Err("the key must have at least one letter".to_string());
+        }
+
+        for (number, key_value) in self.message.split_whitespace().zip(key_values.iter().cycle()) {
+            let value: usize = number.parse::<usize>().map_err(|_| format!("invalid number {}", number))?;
+            let plain_value: usize = value.checked_sub(*key_value).ok_or(format!("invalid number {}", number))?;
+            let (row, col): (usize, usize) = (plain_value / 10, plain_value % 10);
+
+            if row < 1 || row > square.side || col < 1 || col > square.side {
+                return Err(format!("invalid number {}", number));
+            }
+            decoded_message.push(square.symbol_at(row - 1, col - 1));
+        }
+        return Ok(decoded_message);
+    }
+}
+
+
+
+
+fn square_values(square: &PolybiusSquare, text: &str) -> Vec<usize> {
+    return square.normalize(text)
+                 .iter()
+                 .map(|c: &char| {
+                    let (row, col): (usize, usize) = square.coordinates(*c).unwrap();
+                    (row + 1) * 10 + col + 1
+                 })
+                 .collect();
+}
+
+
+
+
+#[cfg(test)]
+mod nihilist_cipher_test {
+    use super::*;
+
+    #[test]
+    fn encode_test() -> () {
+        let instance_1: NihilistCipherAlg = NihilistCipherAlg {
+            operation: Operations::Encrypt,
+            square_key: "ZEBRAS".to_string(),
+            key: "RUSSIAN".to_string(),
+            message: "DYNAMITE WINTER PALACE".to_string()
+        };
+        assert_eq!(instance_1.encode(), Ok("37 106 62 36 67 47 86 26 104 53 62 77 27 55 57 66 55 36 54 27".to_string()));
+    }
+
+    #[test]
+    fn decode_test() -> () {
+        let instance_1: NihilistCipherAlg = NihilistCipherAlg {
+            operation: Operations::Decrypt,
+            square_key: "ZEBRAS".to_string(),
+            key: "RUSSIAN".to_string(),
+            message: "37 106 62 36 67 47 86 26 104 53 62 77 27 55 57 66 55 36 54 27".to_string()
+        };
+        assert_eq!(instance_1.decode(), Ok("DYNAMITEWINTERPALACE".to_string()));
+
+        let instance_2: NihilistCipherAlg = NihilistCipherAlg {
+            message: "37 10".to_string(),
+            ..instance_1
+        };
+        assert!(instance_2.decode().is_err());
+    }
+}
@@ -0,0 +1,281 @@
+use std::{fs, io::Write};
+use clap::{Args, ValueEnum};
+use rand::{rngs::OsRng, RngCore};
+use crate::ops::{bytes_to_hex, hex_to_bytes, write_new_file};
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum OneTimePadOperations {
+    Generate,
+    Encrypt,
+    Decrypt
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct OneTimePadAlg {
+    /// Generate a new pad, Encode or Decode Operation
+    #[arg(short, long)]
+    pub operation: OneTimePadOperations,
+    /// Path of the pad file (used ranges are recorded in <pad>.used, which must exist to encrypt)
+    #[arg(short, long)]
+    pub pad: String,
+    /// Size in bytes of the pad to generate
+    #[arg(short, long, default_value_t = 4096)]
+    pub size: usize,
+    /// Pad offset to start from (encrypt picks the next unused offset when omitted)
+    #[arg(long)]
+    pub offset: Option<usize>,
+    /// The message to encode, or the hex ciphertext to decode
+    #[arg(short, long)]
+    pub message: Option<String>
+}
+
+impl OneTimePadAlg {
+    pub fn execute(self) -> () {
+        match self.operation {
+            OneTimePadOperations::Generate => {
+                match generate_pad(&self.pad, self.size) {
+                    Ok(()) => println!("generated {} byte pad in {}", self.size, self.pad),
+                    Err(error) => println!("{}", error)
+                }
+            }
+            OneTimePadOperations::Encrypt => {
+                match self.encode() {
+                    Ok((offset, ciphertext)) => {
+                        println!("offset: {}", offset);
+                        println!("{}", bytes_to_hex(&ciphertext));
+                    }
+                    Err(error) => println!("{}", error)
+                }
+            }
+            OneTimePadOperations::Decrypt => {
+                match self.decode() {
+                    Ok(decoded_message) => println!("{}", decoded_message),
+                    Err(error) => println!("{}", error)
+                }
+            }
+        }
+    }
+
+    fn encode(&self) -> Result<(usize, Vec<u8>), String> {
+        let message: Vec<u8> = self.message.clone().ok_or("a message is required to encrypt")?.into_bytes();
+        let pad: Vec<u8> = fs::read(&self.pad).map_err(|e| format!("could not read pad {}: {}", self.pad, e))?;
+        let used_ranges: Vec<(usize, usize)> = read_used_ranges(&self.pad)?;
+        let offset: usize = self.offset.unwrap_or(next_unused_offset(&used_ranges));
+        let end: usize = offset.checked_add(message.len()).ok_or("offset out of range")?;
+        let range: (usize, usize) = (offset, end);
+
+        if range.1 > pad.len() {
+            return Err(format!("pad exhausted: {} bytes needed from offset {}, pad has {} bytes", message.len(), offset, pad.len()));
+        }
+
+        if used_ranges.iter().any(|used: &(usize, usize)| ranges_overlap(*used, range)) {
+            return Err(format!("refusing to reuse pad bytes {}..{}", range.0, range.1));
+        }
+
+        record_used_range(&self.pad, range)?;
+
+        return Ok((offset, xor_with_pad(&message, &pad[range.0..range.1])));
+    }
+
+    fn decode(&self) -> Result<String, String> {
+        let ciphertext: Vec<u8> = hex_to_bytes(&self.message.clone().ok_or("a hex ciphertext is required to decrypt")?)?;
+        let offset: usize = self.offset.ok_or("the offset printed at encryption is required to decrypt")?;
+        let pad: Vec<u8> = fs::read(&self.pad).map_err(|e| format!("could not read pad {}: {}", self.pad, e))?;
+
+        let end: usize = offset.checked_add(ciphertext.len()).ok_or("offset out of range")?;
+
+        if end > pad.len() {
+            return Err(format!("the pad has only {} bytes", pad.len()));
+        }
+
+        let plain: Vec<u8> = xor_with_pad(&ciphertext, &pad[offset..end]);
+
+        return String::from_utf8(plain).map_err(|_| "decoded message is not valid text, check pad and offset".to_string());
+    }
+}
+
+
+
+
+fn used_ranges_path(pad_path: &str) -> String {
+    return format!("{}.used", pad_path);
+}
+
+
+
+
+pub fn generate_pad(pad_path: &str, size: usize) -> Result<(), String> {
+    let mut pad: Vec<u8> = vec![0; size];
+    OsRng.fill_bytes(&mut pad);
+
+    write_new_file(pad_path, &pad, 0o600)?;
+    return write_new_file(&used_ranges_path(pad_path), b"", 0o600);
+}
+
+
+
+
+pub fn read_used_ranges(pad_path: &str) -> Result<Vec<(usize, usize)>, String> {
+    let sidecar: String = used_ranges_path(pad_path);
+
+    // without the sidecar there is no record of which pad bytes were already used, so nothing is assumed fresh
+    let content: String = fs::read_to_string(&sidecar).map_err(|e| format!("could not read {}, the pad's used ranges are unknown: {}", sidecar, e))?;
+
+    return content.lines()
+                  .filter(|line: &&str| !line.trim().is_empty())
+                  .map(|line: &str| {
+                    let bounds: Vec<Option<usize>> = line.trim().split('-').map(|x: &str| x.parse::<usize>().ok()).collect();
+
+                    match bounds.as_slice() {
+                        [Some(start), Some(end)] => Ok((*start, *end)),
+                        _ => Err(format!("corrupted line in {}: {}", sidecar, line))
+                    }
+                  })
+                  .collect();
+}
+
+
+
+
+fn record_used_range(pad_path: &str, range: (usize, usize)) -> Result<(), String> {
+    let sidecar: String = used_ranges_path(pad_path);
+    let mut file: fs::File = fs::OpenOptions::new()
+                                .append(true)
+                                .open(&sidecar)
+                                .map_err(|e| format!("could not open {}: {}", sidecar, e))?;
+
+    return writeln!(file, "{}-{}", range.0, range.1).map_err(|e| format!("could not write {}: {}", sidecar, e));
+}
+
+
+
+
+pub fn next_unused_offset(used_ranges: &[(usize, usize)]) -> usize {
+    return used_ranges.iter().map(|range: &(usize, usize)| range.1).max().unwrap_or(0);
+}
+
+
+
+
+pub fn ranges_overlap(a: (usize, usize), b: (usize, usize)) -> bool {
+    return a.0 < b.1 && b.0 < a.1;
+}
+
+
+
+
+fn xor_with_pad(data: &[u8], pad: &[u8]) -> Vec<u8> {
+    return data.iter().zip(pad.iter()).map(|(x, y)| x ^ y).collect();
+}
+
+
+
+
+#[cfg(test)]
+mod one_time_pad_test {
+    use super::*;
+
+    fn temp_pad_path(name: &str) -> String {
+        let path: String = std::env::temp_dir().join(format!("otp_test_{}_{}", name, std::process::id())).to_string_lossy().to_string();
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(used_ranges_path(&path));
+        return path;
+    }
+
+    #[test]
+    fn ranges_overlap_test() -> () {
+        assert!(ranges_overlap((0, 10), (5, 15)));
+        assert!(ranges_overlap((5, 15), (0, 10)));
+        assert!(ranges_overlap((0, 10), (2, 3)));
+        assert!(!ranges_overlap((0, 10), (10, 20)));
+        assert!(!ranges_overlap((10, 20), (0, 10)));
+        assert_eq!(next_unused_offset(&[(0, 10), (30, 40), (10, 12)]), 40);
+        assert_eq!(next_unused_offset(&[]), 0);
+    }
+
+    #[test]
+    fn encode_decode_test() -> () {
+        let pad_path: String = temp_pad_path("round_trip");
+        generate_pad(&pad_path, 64).unwrap();
+        assert!(generate_pad(&pad_path, 64).is_err());
+
+        let instance_1: OneTimePadAlg = OneTimePadAlg {
+            operation: OneTimePadOperations::Encrypt,
+            pad: pad_path.clone(),
+            size: 0,
+            offset: None,
+            message: Some("laroye".to_string())
+        };
+        let (offset_1, ciphertext_1): (usize, Vec<u8>) = instance_1.clone().encode().unwrap();
+        let (offset_2, ciphertext_2): (usize, Vec<u8>) = instance_1.clone().encode().unwrap();
+        assert_eq!((offset_1, offset_2), (0, 6));
+        assert_ne!(ciphertext_1, ciphertext_2);
+
+        let instance_2: OneTimePadAlg = OneTimePadAlg {
+            operation: OneTimePadOperations::Decrypt,
+            offset: Some(offset_2),
+            message: Some(bytes_to_hex(&ciphertext_2)),
+            ..instance_1.clone()
+        };
+        assert_eq!(instance_2.decode(), Ok("laroye".to_string()));
+        assert_eq!(read_used_ranges(&pad_path), Ok(vec![(0, 6), (6, 12)]));
+
+        let _ = fs::remove_file(&pad_path);
+        let _ = fs::remove_file(used_ranges_path(&pad_path));
+    }
+
+    #[test]
+    fn reuse_refused_test() -> () {
+        let pad_path: String = temp_pad_path("reuse");
+        generate_pad(&pad_path, 16).unwrap();
+
+        let instance_1: OneTimePadAlg = OneTimePadAlg {
+            operation: OneTimePadOperations::Encrypt,
+            pad: pad_path.clone(),
+            size: 0,
+            offset: Some(4),
+            message: Some("secret".to_string())
+        };
+        assert!(instance_1.clone().encode().is_ok());
+        assert!(instance_1.clone().encode().is_err());
+
+        let instance_2: OneTimePadAlg = OneTimePadAlg {
+            offset: None,
+            message: Some("too long for the pad".to_string()),
+            ..instance_1.clone()
+        };
+        assert!(instance_2.encode().is_err());
+
+        let instance_3: OneTimePadAlg = OneTimePadAlg { offset: Some(usize::MAX - 2), ..instance_1.clone() };
+        assert!(instance_3.encode().is_err());
+
+        let instance_4: OneTimePadAlg = OneTimePadAlg { operation: OneTimePadOperations::Decrypt, message: Some("00112233".to_string()), ..instance_3.clone() };
+        assert!(instance_4.decode().is_err());
+
+        let _ = fs::remove_file(&pad_path);
+        let _ = fs::remove_file(used_ranges_path(&pad_path));
+    }
+
+    #[test]
+    fn missing_sidecar_refused_test() -> () {
+        let pad_path: String = temp_pad_path("sidecar");
+        generate_pad(&pad_path, 16).unwrap();
+        fs::remove_file(used_ranges_path(&pad_path)).unwrap();
+
+        let instance_1: OneTimePadAlg = OneTimePadAlg {
+            operation: OneTimePadOperations::Encrypt,
+            pad: pad_path.clone(),
+            size: 0,
+            offset: None,
+            message: Some("secret".to_string())
+        };
+        assert!(instance_1.clone().encode().is_err());
+        assert!(read_used_ranges(&pad_path).is_err());
+
+        #[cfg(unix)]
+        assert_eq!(std::os::unix::fs::PermissionsExt::mode(&fs::metadata(&pad_path).unwrap().permissions()) & 0o777, 0o600);
+
+        let _ = fs::remove_file(&pad_path);
+    }
+}
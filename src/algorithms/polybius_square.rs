@@ -0,0 +1,219 @@
+use clap::Args;
+use crate::{alphabet::POLYBIUS_ALPHABET_5X5, Operations};
+
+#[derive(Debug, Args, Clone)]
+pub struct PolybiusSquareAlg {
+    /// Encode or Decode Operation
+    #[arg(short, long)]
+    pub operation: Operations,
+    /// The keyword used to fill the square (empty for the plain square)
+    #[arg(short, long, default_value = "")]
+    pub key: String,
+    /// The message to encode or decode
+    #[arg(short, long)]
+    pub message: String
+}
+
+impl PolybiusSquareAlg {
+    pub fn execute(self) -> () {
+        match self.operation {
+            Operations::Encrypt => {
+                println!("{}", self.encode());
+            }
+            Operations::Decrypt => {
+                match self.decode() {
+                    Ok(decoded_message) => println!("{}", decoded_message),
+                    Err(error) => println!("{}", error)
+                }
+            }
+        }
+    }
+
+    fn encode(&self) -> String {
+        let square: PolybiusSquare = PolybiusSquare::new(&self.key, POLYBIUS_ALPHABET_5X5);
+
+        return square.normalize(&self.message)
+                     .iter()
+                     .map(|c: &char| {
+                        let (row, col): (usize, usize) = square.coordinates(*c).unwrap();
+                        format!("{}{}", row + 1, col + 1)
+                     })
+                     .collect::<Vec<String>>()
+                     .join(" ");
+    }
+
+    fn decode(&self) -> Result<String, String> {
+        let square: PolybiusSquare = PolybiusSquare::new(&self.key, POLYBIUS_ALPHABET_5X5);
+        let digits: Vec<usize> = self.message.chars().filter_map(|c: char| c.to_digit(10)).map(|d: u32| d as usize).collect();
+
+        if !digits.len().is_multiple_of(2) {
+            return Err("the message must have an even number of digits".to_string());
+        }
+
+        let mut decoded_message: String = String::new();
+
+        for pair in digits.chunks(2) {
+            if pair[0] < 1 || pair[0] > square.side || pair[1] < 1 || pair[1] > square.side {
+                return Err(format!("invalid coordinates {}{}", pair[0], pair[1]));
+            }
+            decoded_message.push(square.symbol_at(pair[0] - 1, pair[1] - 1));
+        }
+        return Ok(decoded_message);
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolybiusSquare {
+    pub side: usize,
+    pub symbols: Vec<char>
+}
+impl PolybiusSquare {
+    pub fn new(key: &str, alphabet: &str) -> PolybiusSquare {
+        let symbols: Vec<char> = keyed_alphabet(key, alphabet);
+        let mut side: usize = 1;
+
+        while side * side < symbols.len() {
+            side += 1;
+        }
+
+        return PolybiusSquare {
+            side: side,
+            symbols: symbols
+        };
+    }
+
+    pub fn normalize(&self, text: &str) -> Vec<char> {
+        return text.chars().filter_map(|c: char| normalize_symbol(c, &self.symbols)).collect();
+    }
+
+    pub fn coordinates(&self, c: char) -> Option<(usize, usize)> {
+        let position: usize = self.symbols.iter().position(|x: &char| *x == c)?;
+
+        return Some((position / self.side, position % self.side));
+    }
+
+    pub fn symbol_at(&self, row: usize, col: usize) -> char {
+        return self.symbols[row * self.side + col];
+    }
+}
+
+
+
+
+pub fn normalize_symbol(c: char, alphabet: &[char]) -> Option<char> {
+    let mut upper: char = c.to_ascii_uppercase();
+
+    if upper == 'J' && !alphabet.contains(&'J') {
+        upper = 'I';
+    }
+
+    if alphabet.contains(&upper) {
+        return Some(upper);
+    }
+    return None;
+}
+
+
+
+
+pub fn keyed_alphabet(key: &str, alphabet: &str) -> Vec<char> {
+    let alphabet_chars: Vec<char> = alphabet.chars().collect();
+    let mut symbols: Vec<char> = Vec::new();
+
+    for c in key.chars().chain(alphabet.chars()) {
+        if let Some(symbol) = normalize_symbol(c, &alphabet_chars) {
+            if !symbols.contains(&symbol) {
+                symbols.push(symbol);
+            }
+        }
+    }
+    return symbols;
+}
+
+
+
+
+pub fn fractionate(coordinates: &[Vec<usize>], period: usize) -> Vec<Vec<usize>> {
+    let mut fractionated: Vec<Vec<usize>> = Vec::new();
+    let block_len: usize = if period == 0 { coordinates.len().max(1) } else { period };
+
+    for block in coordinates.chunks(block_len) {
+        let dimensions: usize = block[0].len();
+        let stream: Vec<usize> = (0..dimensions).flat_map(|d: usize| block.iter().map(move |coord: &Vec<usize>| coord[d])).collect();
+
+        fractionated.extend(stream.chunks(dimensions).map(|chunk: &[usize]| chunk.to_vec()));
+    }
+    return fractionated;
+}
+
+
+
+
+pub fn unfractionate(coordinates: &[Vec<usize>], period: usize) -> Vec<Vec<usize>> {
+    let mut unfractionated: Vec<Vec<usize>> = Vec::new();
+    let block_len: usize = if period == 0 { coordinates.len().max(1) } else { period };
+
+    for block in coordinates.chunks(block_len) {
+        let dimensions: usize = block[0].len();
+        let stream: Vec<usize> = block.concat();
+
+        for i in 0..block.len() {
+            unfractionated.push((0..dimensions).map(|d: usize| stream[d * block.len() + i]).collect());
+        }
+    }
+    return unfractionated;
+}
+
+
+
+
+#[cfg(test)]
+mod polybius_square_test {
+    use super::*;
+
+    #[test]
+    fn keyed_alphabet_test() -> () {
+        assert_eq!(keyed_alphabet("", POLYBIUS_ALPHABET_5X5).iter().collect::<String>(), "ABCDEFGHIKLMNOPQRSTUVWXYZ");
+        assert_eq!(keyed_alphabet("zebras", POLYBIUS_ALPHABET_5X5).iter().collect::<String>(), "ZEBRASCDFGHIKLMNOPQTUVWXY");
+        assert_eq!(keyed_alphabet("Jujitsu", POLYBIUS_ALPHABET_5X5).iter().collect::<String>(), "IUTSABCDEFGHKLMNOPQRVWXYZ");
+    }
+
+    #[test]
+    fn encode_test() -> () {
+        let instance_1: PolybiusSquareAlg = PolybiusSquareAlg {
+            operation: Operations::Encrypt,
+            key: "".to_string(),
+            message: "Hello World".to_string()
+        };
+        assert_eq!(instance_1.encode(), "23 15 31 31 34 52 34 42 31 14");
+    }
+
+    #[test]
+    fn decode_test() -> () {
+        let instance_1: PolybiusSquareAlg = PolybiusSquareAlg {
+            operation: Operations::Decrypt,
+            key: "".to_string(),
+            message: "23 15 31 31 34 52 34 42 31 14".to_string()
+        };
+        assert_eq!(instance_1.decode(), Ok("HELLOWORLD".to_string()));
+
+        let instance_2: PolybiusSquareAlg = PolybiusSquareAlg {
+            operation: Operations::Decrypt,
+            key: "".to_string(),
+            message: "23 16".to_string()
+        };
+        assert!(instance_2.decode().is_err());
+    }
+
+    #[test]
+    fn fractionate_test() -> () {
+        let coordinates: Vec<Vec<usize>> = vec![vec![0, 1], vec![2, 3], vec![4, 0]];
+
+        assert_eq!(fractionate(&coordinates, 0), vec![vec![0, 2], vec![4, 1], vec![3, 0]]);
+        assert_eq!(unfractionate(&fractionate(&coordinates, 0), 0), coordinates);
+        assert_eq!(unfractionate(&fractionate(&coordinates, 2), 2), coordinates);
+    }
+}
@@ -0,0 +1,225 @@
+use clap::{Args, ValueEnum};
+use rand::RngCore;
+use crate::ops::{bytes_to_hex, decode_key, hex_to_bytes, KeyFormat};
+
+const BIAS_POSITIONS: usize = 16;
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum Rc4Operations {
+    Encrypt,
+    Decrypt,
+    AnalyzeBias
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct Rc4CipherAlg {
+    /// Encode, Decode or keystream bias analysis Operation
+    #[arg(short, long)]
+    pub operation: Rc4Operations,
+    /// The secret key (1 to 256 bytes)
+    #[arg(short, long)]
+    pub key: Option<String>,
+    /// Encoding of the given key
+    #[arg(long, default_value = "text")]
+    pub key_format: KeyFormat,
+    /// Keystream bytes discarded before use (RC4-drop[n], 768 or 3072 are common)
+    #[arg(short, long, default_value_t = 0)]
+    pub drop: usize,
+    /// Number of random keys used by the bias analysis
+    #[arg(short, long, default_value_t = 100000)]
+    pub samples: usize,
+    /// The message to encode, or the hex ciphertext to decode
+    #[arg(short, long)]
+    pub message: Option<String>
+}
+
+impl Rc4CipherAlg {
+    pub fn execute(self) -> () {
+        match self.operation {
+            Rc4Operations::Encrypt => {
+                match self.encode() {
+                    Ok(ciphertext) => println!("{}", bytes_to_hex(&ciphertext)),
+                    Err(error) => println!("{}", error)
+                }
+            }
+            Rc4Operations::Decrypt => {
+                match self.decode() {
+                    Ok(plain) => println!("{}", String::from_utf8_lossy(&plain)),
+                    Err(error) => println!("{}", error)
+                }
+            }
+            Rc4Operations::AnalyzeBias => {
+                BiasReport::collect(self.samples, self.drop).print();
+            }
+        }
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, String> {
+        let message: &String = self.message.as_ref().ok_or("a message is required to encrypt")?;
+
+        return Ok(self.cipher()?.apply_keystream(message.as_bytes()));
+    }
+
+    fn decode(&self) -> Result<Vec<u8>, String> {
+        let ciphertext: Vec<u8> = hex_to_bytes(self.message.as_ref().ok_or("a hex ciphertext is required to decrypt")?)?;
+
+        return Ok(self.cipher()?.apply_keystream(&ciphertext));
+    }
+
+    fn cipher(&self) -> Result<Rc4, String> {
+        let key: Vec<u8> = decode_key(self.key.as_ref().ok_or("a key is required")?, &self.key_format)?;
+
+        if key.is_empty() || key.len() > 256 {
+            return Err("the key must have between 1 and 256 bytes".to_string());
+        }
+
+        let mut cipher: Rc4 = Rc4::new(&key);
+        cipher.discard(self.drop);
+
+        return Ok(cipher);
+    }
+}
+
+
+
+
+#[derive(Debug, Clone)]
+pub struct Rc4 {
+    state: [u8; 256],
+    i: u8,
+    j: u8
+}
+impl Rc4 {
+    // Key-scheduling algorithm (KSA)
+    pub fn new(key: &[u8]) -> Rc4 {
+        let mut state: [u8; 256] = [0; 256];
+        let mut j: u8 = 0;
+
+        for i in 0..256 {
+            state[i] = i as u8;
+        }
+
+        for i in 0..256 {
+            j = j.wrapping_add(state[i]).wrapping_add(key[i % key.len()]);
+            state.swap(i, j as usize);
+        }
+
+        return Rc4 { state: state, i: 0, j: 0 };
+    }
+
+    // Pseudo-random generation algorithm (PRGA)
+    pub fn next_byte(&mut self) -> u8 {
+        self.i = self.i.wrapping_add(1);
+        self.j = self.j.wrapping_add(self.state[self.i as usize]);
+        self.state.swap(self.i as usize, self.j as usize);
+
+        let index: u8 = self.state[self.i as usize].wrapping_add(self.state[self.j as usize]);
+        return self.state[index as usize];
+    }
+
+    pub fn discard(&mut self, n: usize) -> () {
+        for _ in 0..n {
+            self.next_byte();
+        }
+    }
+
+    pub fn apply_keystream(&mut self, data: &[u8]) -> Vec<u8> {
+        return data.iter().map(|byte: &u8| byte ^ self.next_byte()).collect();
+    }
+}
+
+
+
+
+pub struct BiasReport {
+    pub samples: usize,
+    pub drop: usize,
+    pub counts: Vec<[u64; 256]>
+}
+impl BiasReport {
+    pub fn collect(samples: usize, drop: usize) -> BiasReport {
+        let mut rng = rand::thread_rng();
+        let mut counts: Vec<[u64; 256]> = vec![[0; 256]; BIAS_POSITIONS];
+        let mut key: [u8; 16] = [0; 16];
+
+        for _ in 0..samples {
+            rng.fill_bytes(&mut key);
+
+            let mut cipher: Rc4 = Rc4::new(&key);
+            cipher.discard(drop);
+
+            for position in 0..BIAS_POSITIONS {
+                counts[position][cipher.next_byte() as usize] += 1;
+            }
+        }
+
+        return BiasReport { samples: samples, drop: drop, counts: counts };
+    }
+
+    // Probability of `value` at keystream byte `position` (1-based) relative to the uniform 1/256
+    pub fn ratio(&self, position: usize, value: u8) -> f64 {
+        return self.counts[position - 1][value as usize] as f64 * 256.0 / self.samples.max(1) as f64;
+    }
+
+    pub fn print(&self) -> () {
+        println!("RC4 keystream bias over {} random 128-bit keys (drop {})", self.samples, self.drop);
+        println!("Mantin-Shamir, second byte == 0: {:.3}x the uniform probability 1/256 (biased RC4 gives ~2x)", self.ratio(2, 0));
+        println!("first byte == 0: {:.3}x", self.ratio(1, 0));
+        println!();
+        println!("{:>8} {:>6} {:>10} {:>12}", "position", "value", "ratio", "Z_r == r");
+
+        for position in 1..=BIAS_POSITIONS {
+            let most_biased: u8 = (0..=255u8).max_by(|a: &u8, b: &u8| self.ratio(position, *a).partial_cmp(&self.ratio(position, *b)).unwrap()).unwrap();
+
+            println!("{:>8} {:>6} {:>9.3}x {:>11.3}x", position, most_biased, self.ratio(position, most_biased), self.ratio(position, position as u8));
+        }
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod rc4_cipher_test {
+    use super::*;
+
+    #[test]
+    fn rc4_keystream_test() -> () {
+        assert_eq!(bytes_to_hex(&Rc4::new(b"Key").apply_keystream(b"Plaintext")), "bbf316e8d940af0ad3");
+        assert_eq!(bytes_to_hex(&Rc4::new(b"Wiki").apply_keystream(b"pedia")), "1021bf0420");
+        assert_eq!(bytes_to_hex(&Rc4::new(b"Secret").apply_keystream(b"Attack at dawn")), "45a01f645fc35b383552544b9bf5");
+
+        // RFC 6229, 40-bit key 0x0102030405
+        let mut cipher: Rc4 = Rc4::new(&[1, 2, 3, 4, 5]);
+        assert_eq!(bytes_to_hex(&cipher.apply_keystream(&[0; 16])), "b2396305f03dc027ccc3524a0a1118a8");
+        cipher.discard(752);
+        assert_eq!(bytes_to_hex(&cipher.apply_keystream(&[0; 16])), "eb62638d4f0ba1fe9fca20e05bf8ff2b");
+    }
+
+    #[test]
+    fn rc4_drop_test() -> () {
+        let instance_1: Rc4CipherAlg = Rc4CipherAlg {
+            operation: Rc4Operations::Encrypt,
+            key: Some("0102030405".to_string()),
+            key_format: KeyFormat::Hex,
+            drop: 3072,
+            samples: 0,
+            message: Some("laroye".to_string())
+        };
+        let ciphertext: Vec<u8> = instance_1.encode().unwrap();
+        assert_eq!(bytes_to_hex(&ciphertext[0..4]), bytes_to_hex(&[0xec ^ b'l', 0x0e ^ b'a', 0x11 ^ b'r', 0xc4 ^ b'o']));
+
+        let instance_2: Rc4CipherAlg = Rc4CipherAlg {
+            operation: Rc4Operations::Decrypt,
+            message: Some(bytes_to_hex(&ciphertext)),
+            ..instance_1
+        };
+        assert_eq!(instance_2.decode(), Ok(b"laroye".to_vec()));
+    }
+
+    #[test]
+    fn bias_report_test() -> () {
+        assert!(BiasReport::collect(50000, 0).ratio(2, 0) > 1.5);
+        assert!(BiasReport::collect(50000, 768).ratio(2, 0) < 1.5);
+    }
+}
@@ -0,0 +1,358 @@
+use std::fs;
+use clap::{Args, ValueEnum};
+use crate::{ops::{bytes_to_hex, hex_to_bytes}, Operations};
+
+const SIGMA: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574]; // "expand 32-byte k"
+const TAU: [u32; 4] = [0x61707865, 0x3120646e, 0x79622d36, 0x6b206574];   // "expand 16-byte k"
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum Salsa20Variant {
+    #[value(name = "salsa20")]
+    Salsa20,
+    #[value(name = "salsa20-12")]
+    Salsa20R12,
+    #[value(name = "salsa20-8")]
+    Salsa20R8,
+    #[value(name = "xsalsa20")]
+    XSalsa20
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct Salsa20CipherAlg {
+    /// Encode or Decode Operation
+    #[arg(short, long)]
+    pub operation: Operations,
+    /// Salsa20/20, the reduced-round Salsa20/12 and Salsa20/8, or XSalsa20 with 24-byte nonces
+    #[arg(short, long, default_value = "salsa20")]
+    pub variant: Salsa20Variant,
+    /// Hex key, 32 bytes (16 bytes also accepted by Salsa20)
+    #[arg(short, long)]
+    pub key: String,
+    /// Hex nonce, 8 bytes (24 bytes for XSalsa20)
+    #[arg(short, long)]
+    pub nonce: String,
+    /// Byte offset of the keystream (and of the file) where processing starts
+    #[arg(long, default_value_t = 0)]
+    pub offset: u64,
+    /// Number of bytes to process from the file (until the end when omitted)
+    #[arg(short, long)]
+    pub length: Option<usize>,
+    /// The message to encode, or the hex ciphertext to decode
+    #[arg(short, long, conflicts_with = "file")]
+    pub message: Option<String>,
+    /// Raw input file
+    #[arg(short, long)]
+    pub file: Option<String>,
+    /// Write the raw result to this file instead of printing it
+    #[arg(long)]
+    pub output: Option<String>
+}
+
+impl Salsa20CipherAlg {
+    pub fn execute(self) -> () {
+        let result: Result<Vec<u8>, String> = self.read_input().and_then(|input: Vec<u8>| {
+            let cipher: Salsa20 = self.cipher()?;
+            Ok(cipher.apply_keystream_at(self.offset, &input))
+        });
+
+        match (result, &self.output) {
+            (Ok(output), Some(path)) => {
+                match fs::write(path, &output) {
+                    Ok(()) => println!("{} bytes written to {}", output.len(), path),
+                    Err(error) => println!("could not write {}: {}", path, error)
+                }
+            }
+            (Ok(output), None) => {
+                match self.operation {
+                    Operations::Encrypt => println!("{}", bytes_to_hex(&output)),
+                    Operations::Decrypt => println!("{}", String::from_utf8_lossy(&output))
+                }
+            }
+            (Err(error), _) => println!("{}", error)
+        }
+    }
+
+    fn read_input(&self) -> Result<Vec<u8>, String> {
+        if let Some(path) = &self.file {
+            let content: Vec<u8> = fs::read(path).map_err(|e| format!("could not read {}: {}", path, e))?;
+            let start: usize = (self.offset as usize).min(content.len());
+            let end: usize = match self.length {
+                Some(length) => start.saturating_add(length).min(content.len()),
+                None => content.len()
+            };
+            return Ok(content[start..end].to_vec());
+        }
+
+        let message: &String = self.message.as_ref().ok_or("a message or a file is required")?;
+
+        match self.operation {
+            Operations::Encrypt => Ok(message.as_bytes().to_vec()),
+            Operations::Decrypt => hex_to_bytes(message)
+        }
+    }
+
+    fn cipher(&self) -> Result<Salsa20, String> {
+        let key: Vec<u8> = hex_to_bytes(&self.key)?;
+        let nonce: Vec<u8> = hex_to_bytes(&self.nonce)?;
+
+        match self.variant {
+            Salsa20Variant::XSalsa20 => {
+                let key: [u8; 32] = key.try_into().map_err(|_| "XSalsa20 needs a 32 byte key")?;
+                let nonce: [u8; 24] = nonce.try_into().map_err(|_| "XSalsa20 needs a 24 byte nonce")?;
+                return Ok(Salsa20::new_xsalsa20(&key, &nonce));
+            }
+            _ => {
+                if key.len() != 16 && key.len() != 32 {
+                    return Err("Salsa20 needs a 16 or 32 byte key".to_string());
+                }
+                let nonce: [u8; 8] = nonce.try_into().map_err(|_| "Salsa20 needs an 8 byte nonce")?;
+                let rounds: usize = match self.variant {
+                    Salsa20Variant::Salsa20R12 => 12,
+                    Salsa20Variant::Salsa20R8 => 8,
+                    _ => 20
+                };
+                return Ok(Salsa20::new(&key, &nonce, rounds));
+            }
+        }
+    }
+}
+
+
+
+
+#[derive(Debug, Clone)]
+pub struct Salsa20 {
+    input: [u32; 16],
+    rounds: usize
+}
+impl Salsa20 {
+    pub fn new(key: &[u8], nonce: &[u8; 8], rounds: usize) -> Salsa20 {
+        let key_words: Vec<u32> = le_words(key);
+        let (constants, high_key): ([u32; 4], &[u32]) = if key_words.len() == 8 {
+            (SIGMA, &key_words[4..8])
+        } else {
+            (TAU, &key_words[0..4])
+        };
+        let nonce_words: Vec<u32> = le_words(nonce);
+
+        return Salsa20 {
+            input: [constants[0], key_words[0], key_words[1], key_words[2],
+                    key_words[3], constants[1], nonce_words[0], nonce_words[1],
+                    0, 0, constants[2], high_key[0],
+                    high_key[1], high_key[2], high_key[3], constants[3]],
+            rounds: rounds
+        };
+    }
+
+    pub fn new_xsalsa20(key: &[u8; 32], nonce: &[u8; 24]) -> Salsa20 {
+        let subkey: [u8; 32] = hsalsa20(key, nonce[0..16].try_into().unwrap());
+
+        return Salsa20::new(&subkey, nonce[16..24].try_into().unwrap(), 20);
+    }
+
+    pub fn keystream_block(&self, counter: u64) -> [u8; 64] {
+        let mut input: [u32; 16] = self.input;
+        input[8] = counter as u32;
+        input[9] = (counter >> 32) as u32;
+
+        let output: [u32; 16] = salsa20_core(&input, self.rounds);
+        let mut block: [u8; 64] = [0; 64];
+
+        for (i, word) in output.iter().enumerate() {
+            block[4 * i..4 * i + 4].copy_from_slice(&word.to_le_bytes());
+        }
+        return block;
+    }
+
+    // Seeks to the 64-byte block holding `offset`, so data can start at any byte of the stream
+    pub fn apply_keystream_at(&self, offset: u64, data: &[u8]) -> Vec<u8> {
+        let mut output: Vec<u8> = Vec::with_capacity(data.len());
+        let mut counter: u64 = offset / 64;
+        let mut position: usize = (offset % 64) as usize;
+        let mut block: [u8; 64] = self.keystream_block(counter);
+
+        for byte in data {
+            if position == 64 {
+                counter += 1;
+                position = 0;
+                block = self.keystream_block(counter);
+            }
+            output.push(byte ^ block[position]);
+            position += 1;
+        }
+        return output;
+    }
+}
+
+
+
+
+fn le_words(bytes: &[u8]) -> Vec<u32> {
+    return bytes.chunks(4).map(|chunk: &[u8]| u32::from_le_bytes(chunk.try_into().unwrap())).collect();
+}
+
+
+
+
+fn quarter_round(x: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) -> () {
+    x[b] ^= x[a].wrapping_add(x[d]).rotate_left(7);
+    x[c] ^= x[b].wrapping_add(x[a]).rotate_left(9);
+    x[d] ^= x[c].wrapping_add(x[b]).rotate_left(13);
+    x[a] ^= x[d].wrapping_add(x[c]).rotate_left(18);
+}
+
+
+
+
+fn double_rounds(x: &mut [u32; 16], rounds: usize) -> () {
+    for _ in 0..rounds / 2 {
+        // column round
+        quarter_round(x, 0, 4, 8, 12);
+        quarter_round(x, 5, 9, 13, 1);
+        quarter_round(x, 10, 14, 2, 6);
+        quarter_round(x, 15, 3, 7, 11);
+        // row round
+        quarter_round(x, 0, 1, 2, 3);
+        quarter_round(x, 5, 6, 7, 4);
+        quarter_round(x, 10, 11, 8, 9);
+        quarter_round(x, 15, 12, 13, 14);
+    }
+}
+
+
+
+
+pub fn salsa20_core(input: &[u32; 16], rounds: usize) -> [u32; 16] {
+    let mut x: [u32; 16] = *input;
+
+    double_rounds(&mut x, rounds);
+
+    for i in 0..16 {
+        x[i] = x[i].wrapping_add(input[i]);
+    }
+    return x;
+}
+
+
+
+
+pub fn hsalsa20(key: &[u8; 32], nonce: &[u8; 16]) -> [u8; 32] {
+    let key_words: Vec<u32> = le_words(key);
+    let nonce_words: Vec<u32> = le_words(nonce);
+    let mut x: [u32; 16] = [SIGMA[0], key_words[0], key_words[1], key_words[2],
+                            key_words[3], SIGMA[1], nonce_words[0], nonce_words[1],
+                            nonce_words[2], nonce_words[3], SIGMA[2], key_words[4],
+                            key_words[5], key_words[6], key_words[7], SIGMA[3]];
+
+    double_rounds(&mut x, 20);
+
+    let mut subkey: [u8; 32] = [0; 32];
+
+    for (i, index) in [0, 5, 10, 15, 6, 7, 8, 9].iter().enumerate() {
+        subkey[4 * i..4 * i + 4].copy_from_slice(&x[*index].to_le_bytes());
+    }
+    return subkey;
+}
+
+
+
+
+#[cfg(test)]
+mod salsa20_cipher_test {
+    use super::*;
+
+    fn key_80() -> Vec<u8> {
+        let mut key: Vec<u8> = vec![0; 32];
+        key[0] = 0x80;
+        return key;
+    }
+
+    #[test]
+    fn salsa20_estream_test() -> () {
+        // eSTREAM Set 1, vector 0, 256 and 128 bit keys
+        assert_eq!(bytes_to_hex(&Salsa20::new(&key_80(), &[0; 8], 20).keystream_block(0)),
+                   "e3be8fdd8beca2e3ea8ef9475b29a6e7003951e1097a5c38d23b7a5fad9f6844b22c97559e2723c7cbbd3fe4fc8d9a0744652a83e72a9c461876af4d7ef1a117");
+        assert_eq!(bytes_to_hex(&Salsa20::new(&key_80()[0..16], &[0; 8], 20).keystream_block(0)),
+                   "4dfa5e481da23ea09a31022050859936da52fcee218005164f267cb65f5cfd7f2b4f97e0ff16924a52df269515110a07f9e460bc65ef95da58f740b7d1dbb0aa");
+    }
+
+    #[test]
+    fn salsa20_reduced_rounds_test() -> () {
+        // eSTREAM Salsa20/12 and Salsa20/8 Set 1, vector 0, 256 bit key
+        assert_eq!(bytes_to_hex(&Salsa20::new(&key_80(), &[0; 8], 12).keystream_block(0)),
+                   "afe411ed1c4e07e4d0cde3b33e31ec190fa4cc796a58bafb848ead8d07d02cd2d4b6f9f30cb0b57007e3733895cc8d1060107975acaeeb689b6cf614ab64a3d6");
+        assert_eq!(bytes_to_hex(&Salsa20::new(&key_80(), &[0; 8], 8).keystream_block(0)),
+                   "b1f599e9b0d96df436ae31f5ef589565b92d245db5a1d4c7a78e5e8d0146f8a49d326c1a3bf50c052c9c8f114dc74972c4469591e31c9ed11927aa9871f38583");
+    }
+
+    #[test]
+    fn salsa20_seek_test() -> () {
+        let key: Vec<u8> = (0..32).collect();
+        let cipher: Salsa20 = Salsa20::new(&key, &[3, 1, 4, 1, 5, 9, 2, 6], 20);
+
+        assert_eq!(bytes_to_hex(&cipher.apply_keystream_at(1000, &[0; 32])), "026539df25f2216daa5f680d6e58d393d95fab652452fce54bf7393bb60b179f");
+
+        let message: Vec<u8> = (0..=255).cycle().take(300).collect();
+        let ciphertext: Vec<u8> = cipher.apply_keystream_at(0, &message);
+        assert_eq!(cipher.apply_keystream_at(77, &ciphertext[77..]), message[77..].to_vec());
+    }
+
+    #[test]
+    fn xsalsa20_nacl_test() -> () {
+        let shared: Vec<u8> = hex_to_bytes("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742").unwrap();
+        let first_key: [u8; 32] = hsalsa20(&shared.try_into().unwrap(), &[0; 16]);
+        assert_eq!(bytes_to_hex(&first_key), "1b27556473e985d462cd51197a9a46c76009549eac6474f206c4ee0844f68389");
+
+        let nonce: Vec<u8> = hex_to_bytes("69696ee955b62b73cd62bda875fc73d68219e0036b7a0b37").unwrap();
+        let cipher: Salsa20 = Salsa20::new_xsalsa20(&first_key, &nonce.try_into().unwrap());
+        assert_eq!(bytes_to_hex(&cipher.apply_keystream_at(0, &[0; 32])), "eea6a7251c1e72916d11c2cb214d3c252539121d8e234e652d651fa4c8cff880");
+    }
+
+    #[test]
+    fn salsa20_cli_round_trip_test() -> () {
+        let instance_1: Salsa20CipherAlg = Salsa20CipherAlg {
+            operation: Operations::Encrypt,
+            variant: Salsa20Variant::XSalsa20,
+            key: bytes_to_hex(&[7; 32]),
+            nonce: bytes_to_hex(&[9; 24]),
+            offset: 0,
+            length: None,
+            message: Some("laroye".to_string()),
+            file: None,
+            output: None
+        };
+        let ciphertext: Vec<u8> = instance_1.cipher().unwrap().apply_keystream_at(0, &instance_1.read_input().unwrap());
+
+        let instance_2: Salsa20CipherAlg = Salsa20CipherAlg {
+            operation: Operations::Decrypt,
+            offset: 2,
+            message: Some(bytes_to_hex(&ciphertext[2..])),
+            ..instance_1
+        };
+        assert_eq!(instance_2.cipher().unwrap().apply_keystream_at(2, &instance_2.read_input().unwrap()), b"roye".to_vec());
+    }
+
+    #[test]
+    fn salsa20_file_range_test() -> () {
+        let path: String = std::env::temp_dir().join(format!("salsa20_test_{}", std::process::id())).to_string_lossy().to_string();
+        fs::write(&path, b"laroye").unwrap();
+
+        let instance_1: Salsa20CipherAlg = Salsa20CipherAlg {
+            operation: Operations::Encrypt,
+            variant: Salsa20Variant::Salsa20,
+            key: bytes_to_hex(&[7; 32]),
+            nonce: bytes_to_hex(&[9; 8]),
+            offset: 2,
+            length: Some(usize::MAX),
+            message: None,
+            file: Some(path.clone()),
+            output: None
+        };
+        assert_eq!(instance_1.read_input(), Ok(b"roye".to_vec()));
+
+        let instance_2: Salsa20CipherAlg = Salsa20CipherAlg { offset: u64::MAX, ..instance_1.clone() };
+        assert_eq!(instance_2.read_input(), Ok(Vec::new()));
+
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,87 @@
+use clap::Args;
+use crate::{algorithms::polybius_square::{fractionate, keyed_alphabet, normalize_symbol, unfractionate}, alphabet::TRIFID_ALPHABET, Operations};
+
+#[derive(Debug, Args, Clone)]
+pub struct TrifidCipherAlg {
+    /// Encode or Decode Operation
+    #[arg(short, long)]
+    pub operation: Operations,
+    /// The keyword used to fill the 3x3x3 cube
+    #[arg(short, long, default_value = "")]
+    pub key: String,
+    /// Number of letters fractionated together (0 uses the whole message)
+    #[arg(short, long, default_value_t = 5)]
+    pub period: usize,
+    /// The message to encode or decode
+    #[arg(short, long)]
+    pub message: String
+}
+
+impl TrifidCipherAlg {
+    pub fn execute(self) -> () {
+        match self.operation {
+            Operations::Encrypt => {
+                println!("{}", self.encode());
+            }
+            Operations::Decrypt => {
+                println!("{}", self.decode());
+            }
+        }
+    }
+
+    fn encode(&self) -> String {
+        let cube: Vec<char> = keyed_alphabet(&self.key, TRIFID_ALPHABET);
+
+        return fractionate(&self.message_coordinates(&cube), self.period)
+                .iter()
+                .map(|coord: &Vec<usize>| cube[coord[0] * 9 + coord[1] * 3 + coord[2]])
+                .collect();
+    }
+
+    fn decode(&self) -> String {
+        let cube: Vec<char> = keyed_alphabet(&self.key, TRIFID_ALPHABET);
+
+        return unfractionate(&self.message_coordinates(&cube), self.period)
+                .iter()
+                .map(|coord: &Vec<usize>| cube[coord[0] * 9 + coord[1] * 3 + coord[2]])
+                .collect();
+    }
+
+    fn message_coordinates(&self, cube: &[char]) -> Vec<Vec<usize>> {
+        return self.message
+                   .chars()
+                   .filter_map(|c: char| normalize_symbol(c, cube))
+                   .map(|c: char| {
+                        let position: usize = cube.iter().position(|x: &char| *x == c).unwrap();
+                        vec![position / 9, (position / 3) % 3, position % 3]
+                   })
+                   .collect();
+    }
+}
+
+#[cfg(test)]
+mod trifid_cipher_test {
+    use super::*;
+
+    #[test]
+    fn encode_test() -> () {
+        let instance_1: TrifidCipherAlg = TrifidCipherAlg {
+            operation: Operations::Encrypt,
+            key: "FELIX MARIE DELASTELLE".to_string(),
+            period: 5,
+            message: "Aide-toi, le ciel t'aidera".to_string()
+        };
+        assert_eq!(instance_1.encode(), "FMJFVOISSUFTFPUFEQQC");
+    }
+
+    #[test]
+    fn decode_test() -> () {
+        let instance_1: TrifidCipherAlg = TrifidCipherAlg {
+            operation: Operations::Decrypt,
+            key: "FELIX MARIE DELASTELLE".to_string(),
+            period: 5,
+            message: "FMJFVOISSUFTFPUFEQQC".to_string()
+        };
+        assert_eq!(instance_1.decode(), "AIDETOILECIELTAIDERA");
+    }
+}
@@ -59,7 +59,7 @@ impl VigenereCipherAlg {
     fn extend_key(self) -> Self {
         let mut count: usize = 0;
         let mut extended_key: String = String::new();
-        let original_key_vec: Vec<char> = self.key.chars().into_iter().map(|x: char| x).collect::<Vec<char>>();
+        let original_key_vec: Vec<char> = self.key.chars().map(|x: char| x).collect::<Vec<char>>();
 
         for _ in 1..=self.message.len() {
             extended_key = format!("{}{}", extended_key, original_key_vec[count]);
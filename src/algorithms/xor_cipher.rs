@@ -1,120 +1,223 @@
+use std::fs;
 use clap::Args;
-use crate::{alphabet::get_text_bin_value, Operations};
-use rand::{Rng, distributions::Alphanumeric};
+use crate::{key_derivation::pbkdf2::{PasswordHeader, PasswordKdf, DEFAULT_ITERATIONS}, ops::{bytes_to_hex, decode_key, encode_key, hex_to_bytes, KeyFormat}, Operations};
+use rand::{Rng, RngCore, distributions::Alphanumeric, rngs::OsRng};
 
 #[derive(Debug, Args, Clone, PartialEq)]
 pub struct XorCipherAlg {
     /// Encode or Decode Operation
     #[arg(short, long)]
     pub operation: Operations,
-    /// The secret word for encrypt
-    #[arg(short, long)]
+    /// The secret word for encrypt (a random key is generated when omitted)
+    #[arg(short, long, conflicts_with = "password")]
     pub key: Option<String>,
-    /// The message to encode or decode
+    /// Derive the key from a passphrase (the KDF, its cost and the salt are stored in front of the ciphertext)
+    #[arg(short, long)]
+    pub password: Option<String>,
+    /// Password KDF used when encrypting with --password
+    #[arg(long, value_enum, default_value = "pbkdf2")]
+    pub kdf: PasswordKdf,
+    /// PBKDF2 iterations used when encrypting with --password
+    #[arg(short, long, default_value_t = DEFAULT_ITERATIONS)]
+    pub iterations: u32,
+    /// Encoding of the given key and of the generated key
+    #[arg(long, default_value = "text")]
+    pub key_format: KeyFormat,
+    /// Length in bytes of a generated or derived key
+    #[arg(long, default_value_t = 16)]
+    pub key_length: usize,
+    /// File to write the generated key to (printed on stdout when omitted)
+    #[arg(long)]
+    pub key_output: Option<String>,
+    /// The message to encode, or the hex ciphertext to decode
     #[arg(short, long)]
     pub message: String,
 }
 impl XorCipherAlg {
     pub fn execute(self) -> () {
-        let mut rand_i = rand::thread_rng(); 
-        let key_len: usize; 
-        let mut rand_key: String = String::new();
-        
-        if self.key.clone().is_some() {
-            rand_key = self.key.clone().unwrap();
+        match self.operation {
+            Operations::Encrypt => {
+                match self.encrypt() {
+                    Ok(ciphertext) => println!("{}", bytes_to_hex(&ciphertext)),
+                    Err(error) => println!("{}", error)
+                }
+            }
+            Operations::Decrypt => {
+                match self.decrypt() {
+                    Ok(plain) => println!("{}", String::from_utf8_lossy(&plain)),
+                    Err(error) => println!("{}", error)
+                }
+            }
         }
+    }
 
-        if self.key.is_none() {
-            key_len = rand_i.gen();
+    fn encrypt(&self) -> Result<Vec<u8>, String> {
+        let header: Option<PasswordHeader> = self.password.as_ref().map(|_| PasswordHeader::new(&self.kdf, self.iterations));
+        let key: Vec<u8> = self.resolve_key(&header)?;
 
-            rand_key = {rand_i
-                .sample_iter(&Alphanumeric)
-                .take(key_len)
-                .map(|x: u8| char::from(x).to_string())
-                .collect::<Vec<String>>()
-                .concat()
-            };
+        return Ok([header.map(|header: PasswordHeader| header.to_bytes()).unwrap_or_default(), self.encode_decode(self.message.as_bytes(), &key)].concat());
+    }
+
+    fn decrypt(&self) -> Result<Vec<u8>, String> {
+        let data: Vec<u8> = hex_to_bytes(&self.message)?;
+        let (header, ciphertext): (Option<PasswordHeader>, &[u8]) = if self.password.is_some() { PasswordHeader::split(&data).map(|(header, data)| (Some(header), data))? } else { (None, &data) };
+        let key: Vec<u8> = self.resolve_key(&header)?;
+
+        return Ok(self.encode_decode(ciphertext, &key));
+    }
+
+    fn resolve_key(&self, header: &Option<PasswordHeader>) -> Result<Vec<u8>, String> {
+        if let Some(key) = &self.key {
+            let key_bytes: Vec<u8> = decode_key(key, &self.key_format)?;
+
+            if key_bytes.is_empty() {
+                return Err("the key can not be empty".to_string());
+            }
+            return Ok(key_bytes);
         }
 
-        let new_key_instance = XorCipherAlg {
-            key: Some(rand_key),
-            operation: self.operation,
-            message: self.message
-        };
+        if self.key_length == 0 {
+            return Err("the key length must be at least 1 byte".to_string());
+        }
 
-        match new_key_instance.operation {
-            Operations::Encrypt => {
-                new_key_instance.encode_decode();
+        if let (Some(password), Some(header)) = (&self.password, header) {
+            return header.key(password, self.key_length);
+        }
+
+        if self.operation == Operations::Decrypt {
+            return Err("a key or password is required to decrypt".to_string());
+        }
+
+        let generated_key: Vec<u8> = generate_key(self.key_length, &self.key_format);
+        self.export_key(&generated_key)?;
+
+        return Ok(generated_key);
+    }
+
+    fn export_key(&self, key: &[u8]) -> Result<(), String> {
+        let encoded_key: String = encode_key(key, &self.key_format);
+
+        match &self.key_output {
+            Some(path) => {
+                fs::write(path, format!("{}\n", encoded_key)).map_err(|e| format!("could not write key to {}: {}", path, e))?;
+                println!("key written to {}", path);
             }
-            Operations::Decrypt => {
-                new_key_instance.encode_decode();
+            None => {
+                println!("key: {}", encoded_key);
             }
         }
+        return Ok(());
     }
 
-    fn encode_decode(self) -> () {
-        let bin_values_message: Vec<Vec<u8>> = get_text_bin_value(self.clone().extend_key().key.unwrap()); 
-        let bin_values_key: Vec<Vec<u8>> = get_text_bin_value(self.message);
-
-        let encoded_message_bytes: Vec<Vec<u8>> = 
-            bin_values_message.iter()
-                .zip(bin_values_key.iter())
-                .map(|(vec_x, vec_y)| {
-                    
-                    vec_x.iter()
-                    .zip(vec_y.iter())
-                    .map(|(x, y)| {x ^ y})
-                    .collect::<Vec<u8>>()}
-
-                ).collect::<Vec<Vec<u8>>>();
-        
-        let encoded_message: String = encoded_message_bytes.iter().map(|x: &Vec<u8>| {String::from_utf8(x.clone()).unwrap()}).collect::<String>();
-        
-        println!("{:?}", encoded_message);
+    fn encode_decode(&self, message: &[u8], key: &[u8]) -> Vec<u8> {
+        return message.iter()
+                      .zip(extend_key(key, message.len()).iter())
+                      .map(|(x, y)| x ^ y)
+                      .collect();
     }
+}
 
-    fn extend_key(self) -> Self {
-        let mut count: usize = 0;
-        let mut extended_key: String = String::new();
-        let original_key_vec: Vec<char> = self.key.unwrap().chars().into_iter().map(|x: char| x).collect::<Vec<char>>();
 
-        for _ in 1..=self.message.len() {
-            extended_key = format!("{}{}", extended_key, original_key_vec[count]);
-            count += 1;
 
-            if count == original_key_vec.len() {
-                count = 0
-            }
-        } 
-        return Self {
-            operation: self.operation,
-            key: Some(extended_key),
-            message: self.message
-        };
+
+fn extend_key(key: &[u8], len: usize) -> Vec<u8> {
+    return key.iter().cycle().take(len).copied().collect();
+}
+
+
+
+
+pub fn generate_key(key_len: usize, format: &KeyFormat) -> Vec<u8> {
+    match format {
+        KeyFormat::Text => {
+            return OsRng.sample_iter(&Alphanumeric).take(key_len).collect();
+        }
+        _ => {
+            let mut key: Vec<u8> = vec![0; key_len];
+            OsRng.fill_bytes(&mut key);
+            return key;
+        }
     }
 }
 
+
+
+
 #[cfg(test)]
 mod xor_cipher_test {
     use super::*;
+    use crate::key_derivation::pbkdf2::PASSWORD_HEADER_LENGTH;
 
     #[test]
     fn xor_cipher_encode_test() -> () {
-        let instance_1: XorCipherAlg = 
+        let instance_1: XorCipherAlg =
             XorCipherAlg {
-                operation: Operations::Decrypt ,
+                operation: Operations::Encrypt,
                 key: Some("AAAAAAAAAAAA".to_string()),
-                message: "- 3.8$".to_string()
+                password: None,
+                kdf: PasswordKdf::Pbkdf2,
+                iterations: 10,
+                key_format: KeyFormat::Text,
+                key_length: 16,
+                key_output: None,
+                message: "laroye".to_string()
             };
 
-            let instance_2: XorCipherAlg = 
+        let key: Vec<u8> = instance_1.resolve_key(&None).unwrap();
+        let encoded: Vec<u8> = instance_1.encode_decode(instance_1.message.as_bytes(), &key);
+
+        assert_eq!(encoded, "- 3.8$".as_bytes());
+        assert_eq!(instance_1.encode_decode(&encoded, &key), "laroye".as_bytes());
+    }
+
+    #[test]
+    fn xor_cipher_key_test() -> () {
+        assert_eq!(generate_key(32, &KeyFormat::Hex).len(), 32);
+        assert!(generate_key(32, &KeyFormat::Text).iter().all(|x: &u8| x.is_ascii_alphanumeric()));
+
+        let instance_1: XorCipherAlg =
             XorCipherAlg {
-                operation: Operations::Encrypt ,
-                key: Some("AAAAAAAAAAAA".to_string()),
+                operation: Operations::Decrypt,
+                key: None,
+                password: None,
+                kdf: PasswordKdf::Pbkdf2,
+                iterations: 10,
+                key_format: KeyFormat::Hex,
+                key_length: 16,
+                key_output: None,
+                message: "2d20332e3824".to_string()
+            };
+        assert!(instance_1.resolve_key(&None).is_err());
+
+        let instance_2: XorCipherAlg = XorCipherAlg { key: Some("4141".to_string()), ..instance_1.clone() };
+        assert_eq!(instance_2.resolve_key(&None), Ok(vec![65, 65]));
+
+        let instance_3: XorCipherAlg = XorCipherAlg { password: Some("passphrase".to_string()), ..instance_1.clone() };
+        let salt: Option<PasswordHeader> = Some(PasswordHeader { kdf: PasswordKdf::Pbkdf2, cost: 10, salt: b"salt".to_vec() });
+        let pepper: Option<PasswordHeader> = Some(PasswordHeader { kdf: PasswordKdf::Pbkdf2, cost: 10, salt: b"pepper".to_vec() });
+        assert_eq!(instance_3.resolve_key(&salt), instance_3.resolve_key(&salt));
+        assert_ne!(instance_3.resolve_key(&salt), instance_3.resolve_key(&pepper));
+        assert_eq!(instance_3.resolve_key(&salt).unwrap().len(), 16);
+    }
+
+    #[test]
+    fn xor_cipher_password_test() -> () {
+        let instance_1: XorCipherAlg =
+            XorCipherAlg {
+                operation: Operations::Encrypt,
+                key: None,
+                password: Some("passphrase".to_string()),
+                kdf: PasswordKdf::Pbkdf2,
+                iterations: 10,
+                key_format: KeyFormat::Text,
+                key_length: 16,
+                key_output: None,
                 message: "laroye".to_string()
             };
-        
-        println!("{:?}", instance_1.execute());
-        println!("{:?}", instance_2.execute());
+        let ciphertext: Vec<u8> = instance_1.encrypt().unwrap();
+        assert_eq!(ciphertext.len(), PASSWORD_HEADER_LENGTH + 6);
+
+        let instance_2: XorCipherAlg = XorCipherAlg { operation: Operations::Decrypt, message: bytes_to_hex(&ciphertext), ..instance_1.clone() };
+        assert_eq!(instance_2.decrypt(), Ok(b"laroye".to_vec()));
     }
-}
\ No newline at end of file
+}
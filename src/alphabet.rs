@@ -1,8 +1,11 @@
 pub const ALPHABET: [&str; 26] = ["A","B","C","D","E","F","G","H","I","J","K","L","M","N","O","P","Q","R","S","T","U","V","W","X","Y","Z"];
 pub const ALPHABET_LEN: i8 = 26;
+pub const POLYBIUS_ALPHABET_5X5: &str = "ABCDEFGHIKLMNOPQRSTUVWXYZ";
+pub const POLYBIUS_ALPHABET_6X6: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+pub const TRIFID_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ+";
 
 pub fn get_letter_position(letter: &str) -> usize {
-    return ALPHABET.iter().position(|x: &&str| *x == letter.to_uppercase()).unwrap().try_into().unwrap();
+    return ALPHABET.iter().position(|x: &&str| *x == letter.to_uppercase()).unwrap();
 }
 
 pub fn get_text_values(text: String) -> Vec<usize> {
@@ -1,5 +1,10 @@
 use clap::{Parser, Subcommand};
-use crate::algorithms::{advanced_encryption_standard::AdvancedEncryptionStandardAlg, cesar_cipher::CesarCipherAlg, transposition_cipher::TranspositionCipherAlg, vigenere_cipher::VigenereCipherAlg, xor_cipher::XorCipherAlg, data_encryption_standard::DataEncryptionStandardAlg};
+use crate::algorithms::{adfgvx_cipher::AdfgvxCipherAlg, advanced_encryption_standard::AdvancedEncryptionStandardAlg, bifid_cipher::BifidCipherAlg, nihilist_cipher::NihilistCipherAlg, one_time_pad::OneTimePadAlg, polybius_square::PolybiusSquareAlg, rc4_cipher::Rc4CipherAlg, salsa20_cipher::Salsa20CipherAlg, trifid_cipher::TrifidCipherAlg, cesar_cipher::CesarCipherAlg, transposition_cipher::TranspositionCipherAlg, vigenere_cipher::VigenereCipherAlg, xor_cipher::XorCipherAlg, data_encryption_standard::DataEncryptionStandardAlg};
+use crate::asymmetric::{diffie_hellman::DhAlg, rsa::RsaAlg};
+use crate::attacks::{crack_hash::CrackHashAlg, length_extension::LengthExtensionAlg};
+use crate::hashes::{checksum::ChecksumAlg, hash_function::HashAlg, hmac::HmacAlg, non_crypto_hash::NonCryptoHashAlg};
+use crate::key_derivation::{argon2::Argon2Alg, bcrypt::BcryptAlg, hkdf::HkdfAlg, kbkdf::KbkdfAlg, pbkdf2::Pbkdf2Alg, scrypt::ScryptAlg, unix_crypt::UnixCryptAlg};
+use crate::macs::mac::MacAlg;
 
 
 #[derive(Debug, Parser)]
@@ -23,5 +28,53 @@ pub enum Algorithms {
     /// Operation using Data Encryption Standard
     DataEncryptionStandard(DataEncryptionStandardAlg),
     /// Operation using AES
-    AdvancedEncryptionStandard(AdvancedEncryptionStandardAlg)
+    AdvancedEncryptionStandard(AdvancedEncryptionStandardAlg),
+    /// Operation using Polybius Square
+    PolybiusSquare(PolybiusSquareAlg),
+    /// Operation using Bifid Cipher
+    BifidCipher(BifidCipherAlg),
+    /// Operation using Trifid Cipher
+    TrifidCipher(TrifidCipherAlg),
+    /// Operation using ADFGX / ADFGVX Cipher
+    AdfgvxCipher(AdfgvxCipherAlg),
+    /// Operation using Nihilist Cipher
+    NihilistCipher(NihilistCipherAlg),
+    /// Operation using One-Time Pad
+    OneTimePad(OneTimePadAlg),
+    /// Operation using RC4 / RC4-drop[n]
+    Rc4Cipher(Rc4CipherAlg),
+    /// Operation using Salsa20 / XSalsa20
+    Salsa20Cipher(Salsa20CipherAlg),
+    /// Generate RSA keys, encrypt or decrypt (raw, PKCS#1 v1.5 or OAEP), sign or verify (PKCS#1 v1.5 or PSS)
+    Rsa(RsaAlg),
+    /// Diffie-Hellman key pairs over the RFC 3526 / RFC 7919 groups and HKDF derived AES keys
+    Dh(DhAlg),
+    /// Hash a message, a file or stdin
+    Hash(HashAlg),
+    /// Operation using HMAC
+    Hmac(HmacAlg),
+    /// Checksums and non-cryptographic hashes (CRC, Adler-32, Fletcher, FNV-1a) and SipHash
+    NonCryptoHash(NonCryptoHashAlg),
+    /// Write or check sha256sum style checksum manifests
+    Checksum(ChecksumAlg),
+    /// Length extension attack on H(secret || message)
+    LengthExtension(LengthExtensionAlg),
+    /// Audit password hashes with a wordlist and mangling rules
+    CrackHash(CrackHashAlg),
+    /// Operation using CMAC, GMAC or Poly1305
+    Mac(MacAlg),
+    /// Derive a key with PBKDF2
+    Pbkdf2(Pbkdf2Alg),
+    /// Hash or verify a password with bcrypt
+    Bcrypt(BcryptAlg),
+    /// Derive a key or hash a password with scrypt
+    Scrypt(ScryptAlg),
+    /// Derive a key or hash a password with Argon2
+    Argon2(Argon2Alg),
+    /// Derive subkeys from a master secret with HKDF
+    Hkdf(HkdfAlg),
+    /// Derive subkeys from a master secret with the SP 800-108 KBKDF
+    Kbkdf(KbkdfAlg),
+    /// Unix crypt(3) password hashes (DES, $1$, $5$, $6$) and /etc/shadow checks
+    UnixCrypt(UnixCryptAlg)
 }
@@ -0,0 +1,183 @@
+use crate::{bignum::BigUint, ops::{base64_to_bytes, bytes_to_base64}};
+
+pub const TAG_INTEGER: u8 = 0x02;
+pub const TAG_BIT_STRING: u8 = 0x03;
+pub const TAG_OCTET_STRING: u8 = 0x04;
+pub const TAG_NULL: u8 = 0x05;
+pub const TAG_OBJECT_IDENTIFIER: u8 = 0x06;
+pub const TAG_SEQUENCE: u8 = 0x30;
+
+// Short form below 128, otherwise 0x80 | number of length bytes followed by the big endian length
+fn encode_length(length: usize) -> Vec<u8> {
+    if length < 0x80 {
+        return vec![length as u8];
+    }
+    let bytes: Vec<u8> = length.to_be_bytes().iter().skip_while(|byte: &&u8| **byte == 0).copied().collect();
+    return [vec![0x80 | bytes.len() as u8], bytes].concat();
+}
+
+
+
+
+pub fn encode_tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+    return [vec![tag], encode_length(content.len()), content.to_vec()].concat();
+}
+
+
+
+
+// INTEGER is two's complement, so a leading zero byte keeps values with the high bit set positive
+pub fn encode_integer(value: &BigUint) -> Vec<u8> {
+    let mut content: Vec<u8> = value.to_bytes_be();
+    if content.first().is_none_or(|byte: &u8| byte & 0x80 != 0) {
+        content.insert(0, 0);
+    }
+    return encode_tlv(TAG_INTEGER, &content);
+}
+
+
+
+
+pub fn encode_sequence(elements: &[Vec<u8>]) -> Vec<u8> {
+    return encode_tlv(TAG_SEQUENCE, &elements.concat());
+}
+
+
+
+
+// Reads DER elements one after the other from a buffer
+pub struct DerReader<'a> {
+    data: &'a [u8],
+    position: usize
+}
+impl<'a> DerReader<'a> {
+    pub fn new(data: &'a [u8]) -> DerReader<'a> {
+        return DerReader { data: data, position: 0 };
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.position >= self.data.len();
+    }
+
+    fn next_byte(&mut self) -> Result<u8, String> {
+        let byte: u8 = *self.data.get(self.position).ok_or("truncated DER element")?;
+        self.position += 1;
+        return Ok(byte);
+    }
+
+    // Content of the next element, which must carry the expected tag
+    pub fn read(&mut self, tag: u8) -> Result<&'a [u8], String> {
+        let found: u8 = self.next_byte()?;
+        if found != tag {
+            return Err(format!("expected DER tag {:02x}, found {:02x}", tag, found));
+        }
+
+        let first: u8 = self.next_byte()?;
+        let length: usize = if first < 0x80 {
+            first as usize
+        } else {
+            let count: usize = (first & 0x7f) as usize;
+            if count == 0 || count > 4 {
+                return Err("unsupported DER length".to_string());
+            }
+            let mut length: usize = 0;
+            for _ in 0..count {
+                length = (length << 8) | self.next_byte()? as usize;
+            }
+            length
+        };
+
+        let end: usize = self.position.checked_add(length).filter(|end: &usize| *end <= self.data.len()).ok_or("truncated DER element")?;
+        let content: &'a [u8] = &self.data[self.position..end];
+        self.position = end;
+        return Ok(content);
+    }
+
+    pub fn read_integer(&mut self) -> Result<BigUint, String> {
+        let content: &[u8] = self.read(TAG_INTEGER)?;
+        if content.is_empty() || content[0] & 0x80 != 0 {
+            return Err("expected a non negative DER integer".to_string());
+        }
+        return Ok(BigUint::from_bytes_be(content));
+    }
+
+    pub fn read_sequence(&mut self) -> Result<DerReader<'a>, String> {
+        return Ok(DerReader::new(self.read(TAG_SEQUENCE)?));
+    }
+}
+
+
+
+
+// RFC 7468 textual encoding: base64 of the DER in 64 character lines between BEGIN and END markers
+pub fn pem_encode(label: &str, der: &[u8]) -> String {
+    let base64: String = bytes_to_base64(der);
+    let lines: Vec<&str> = base64.as_bytes().chunks(64).map(|line: &[u8]| std::str::from_utf8(line).unwrap()).collect();
+
+    return format!("-----BEGIN {}-----\n{}\n-----END {}-----\n", label, lines.join("\n"), label);
+}
+
+
+
+
+// Label and DER of the first PEM block in the text
+pub fn pem_decode(pem: &str) -> Result<(String, Vec<u8>), String> {
+    let begin: usize = pem.find("-----BEGIN ").ok_or("no PEM BEGIN line found")?;
+    let rest: &str = &pem[begin + "-----BEGIN ".len()..];
+    let label_end: usize = rest.find("-----").ok_or("malformed PEM BEGIN line")?;
+    let label: &str = &rest[..label_end];
+
+    let body: &str = &rest[label_end + "-----".len()..];
+    let end_marker: String = format!("-----END {}-----", label);
+    let body_end: usize = body.find(&end_marker).ok_or(format!("missing {}", end_marker))?;
+
+    return Ok((label.to_string(), base64_to_bytes(body[..body_end].trim())?));
+}
+
+
+
+
+pub fn public_key_path(key_path: &str) -> String {
+    return format!("{}.pub", key_path);
+}
+
+#[cfg(test)]
+mod der_test {
+    use crate::ops::bytes_to_hex;
+    use super::*;
+
+    #[test]
+    fn encode_test() -> () {
+        assert_eq!(bytes_to_hex(&encode_integer(&BigUint::zero())), "020100");
+        assert_eq!(bytes_to_hex(&encode_integer(&BigUint::from_u64(127))), "02017f");
+        assert_eq!(bytes_to_hex(&encode_integer(&BigUint::from_u64(128))), "02020080");
+        assert_eq!(bytes_to_hex(&encode_integer(&BigUint::from_u64(65537))), "0203010001");
+        assert_eq!(bytes_to_hex(&encode_tlv(TAG_OCTET_STRING, &[0; 200])[..4]), "0481c800");
+        assert_eq!(bytes_to_hex(&encode_tlv(TAG_OCTET_STRING, &[0; 300])[..5]), "0482012c00");
+    }
+
+    #[test]
+    fn reader_test() -> () {
+        let der: Vec<u8> = encode_sequence(&[encode_integer(&BigUint::from_u64(65537)), encode_tlv(TAG_OCTET_STRING, &[7; 300])]);
+        let mut reader: DerReader = DerReader::new(&der);
+        let mut sequence: DerReader = reader.read_sequence().unwrap();
+        assert!(reader.is_empty());
+
+        assert_eq!(sequence.read_integer(), Ok(BigUint::from_u64(65537)));
+        assert!(sequence.read(TAG_INTEGER).is_err());
+
+        let mut truncated: DerReader = DerReader::new(&der[..der.len() - 1]);
+        assert!(truncated.read_sequence().is_err());
+        assert!(DerReader::new(&[0x02, 0x01, 0xff]).read_integer().is_err());
+    }
+
+    #[test]
+    fn pem_test() -> () {
+        let der: Vec<u8> = (0..100).collect();
+        let pem: String = pem_encode("TEST DATA", &der);
+        assert!(pem.starts_with("-----BEGIN TEST DATA-----\n"));
+        assert_eq!(pem.lines().nth(1).unwrap().len(), 64);
+        assert_eq!(pem_decode(&format!("comment\n{}", pem)), Ok(("TEST DATA".to_string(), der)));
+        assert!(pem_decode("-----BEGIN TEST DATA-----\nAAAA\n-----END OTHER-----").is_err());
+    }
+}
@@ -0,0 +1,451 @@
+use std::{collections::HashSet, fs, sync::{atomic::{AtomicBool, AtomicUsize, Ordering}, Mutex}, thread};
+use clap::{Args, ValueEnum};
+use crate::{hashes::hash_function::{digest, HashAlgorithms}, key_derivation::{pbkdf2::{pbkdf2, Pbkdf2Prf}, unix_crypt::{check_crypt_hash, verify_crypt}}, ops::{base64_to_bytes, hex_to_bytes}};
+
+const LEETSPEAK: [(char, char); 5] = [('a', '4'), ('e', '3'), ('i', '1'), ('o', '0'), ('s', '5')];
+// 10^6 suffixes per candidate is already slow with the cheapest hashes
+pub const MAX_DIGITS: u32 = 6;
+
+// (label, hash) pairs, the label being the user of a user:hash line or the hash itself
+type LabelledTargets = Vec<(String, HashTarget)>;
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum ManglingRules {
+    /// lower, UPPER, Capitalized and tOGGLED variants
+    Case,
+    /// a -> 4, e -> 3, i -> 1, o -> 0, s -> 5
+    Leet,
+    /// Digits appended, up to --digits of them
+    Digits
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HashTarget {
+    Digest { algorithm: HashAlgorithms, digest: Vec<u8> },
+    // DES, $1$, $5$, $6$ and bcrypt hashes, checked by the crypt(3) verifier
+    Crypt(String),
+    Pbkdf2 { prf: Pbkdf2Prf, iterations: u32, salt: Vec<u8>, hash: Vec<u8> }
+}
+impl HashTarget {
+    // Unsalted hex digests are told apart by length unless the algorithm is given
+    pub fn parse(hash: &str, algorithm: Option<&HashAlgorithms>) -> Result<HashTarget, String> {
+        if hash.starts_with("$pbkdf2") {
+            return parse_passlib_pbkdf2(hash);
+        }
+        if ["$1$", "$5$", "$6$", "$2a$", "$2b$", "$2y$"].iter().any(|prefix: &&str| hash.starts_with(prefix)) {
+            check_crypt_hash(hash)?;
+            return Ok(HashTarget::Crypt(hash.to_string()));
+        }
+        if hash.starts_with('$') {
+            return Err(format!("unsupported hash format {}", hash));
+        }
+        if hash.len() == 13 && algorithm.is_none() {
+            check_crypt_hash(hash)?;
+            return Ok(HashTarget::Crypt(hash.to_string()));
+        }
+
+        let digest: Vec<u8> = hex_to_bytes(hash)?;
+        let algorithm: HashAlgorithms = match algorithm {
+            Some(algorithm) => algorithm.clone(),
+            None => match digest.len() {
+                16 => HashAlgorithms::Md5,
+                20 => HashAlgorithms::Sha1,
+                28 => HashAlgorithms::Sha224,
+                32 => HashAlgorithms::Sha256,
+                48 => HashAlgorithms::Sha384,
+                64 => HashAlgorithms::Sha512,
+                _ => return Err(format!("can not guess the algorithm of a {} byte digest, use --algorithm", digest.len()))
+            }
+        };
+        if algorithm.hasher().output_size() != digest.len() {
+            return Err(format!("{} digest must be {} bytes", algorithm.name(), algorithm.hasher().output_size()));
+        }
+
+        return Ok(HashTarget::Digest { algorithm: algorithm, digest: digest });
+    }
+
+    fn matches(&self, candidate: &[u8]) -> bool {
+        match self {
+            HashTarget::Digest { algorithm, digest: expected } => digest(algorithm, candidate) == *expected,
+            HashTarget::Crypt(hash) => verify_crypt(candidate, hash).unwrap_or(false),
+            HashTarget::Pbkdf2 { prf, iterations, salt, hash } => pbkdf2(prf, candidate, salt, *iterations, hash.len()).is_ok_and(|derived: Vec<u8>| derived == *hash)
+        }
+    }
+}
+
+// passlib's $pbkdf2[-sha256|-sha512]$<iterations>$<salt>$<hash>, in base64 with . instead of +
+fn parse_passlib_pbkdf2(hash: &str) -> Result<HashTarget, String> {
+    let fields: Vec<&str> = hash.split('$').collect();
+    if fields.len() != 5 {
+        return Err("PBKDF2 hash must look like $pbkdf2-sha256$<iterations>$<salt>$<hash>".to_string());
+    }
+
+    let prf: Pbkdf2Prf = match fields[1] {
+        "pbkdf2" => Pbkdf2Prf::HmacSha1,
+        "pbkdf2-sha256" => Pbkdf2Prf::HmacSha256,
+        "pbkdf2-sha512" => Pbkdf2Prf::HmacSha512,
+        _ => return Err(format!("unsupported PBKDF2 variant ${}$", fields[1]))
+    };
+
+    let iterations: u32 = fields[2].parse::<u32>().map_err(|_| format!("invalid PBKDF2 iteration count {}", fields[2]))?;
+    let hash: Vec<u8> = base64_to_bytes(&fields[4].replace('.', "+"))?;
+    if iterations == 0 || hash.is_empty() {
+        return Err("PBKDF2 hash needs at least one iteration and a non empty hash".to_string());
+    }
+
+    return Ok(HashTarget::Pbkdf2 {
+        prf: prf,
+        iterations: iterations,
+        salt: base64_to_bytes(&fields[3].replace('.', "+"))?,
+        hash: hash
+    });
+}
+
+
+
+
+fn capitalize(word: &str) -> String {
+    let mut characters: std::str::Chars = word.chars();
+    return match characters.next() {
+        Some(first) => first.to_uppercase().chain(characters.flat_map(|c: char| c.to_lowercase())).collect(),
+        None => String::new()
+    };
+}
+
+
+
+
+fn toggle_case(word: &str) -> String {
+    return word.chars().flat_map(|c: char| -> Vec<char> {
+        if c.is_uppercase() { c.to_lowercase().collect() } else { c.to_uppercase().collect() }
+    }).collect();
+}
+
+
+
+
+fn leetspeak(word: &str) -> String {
+    return word.chars().map(|c: char| match LEETSPEAK.iter().find(|(letter, _): &&(char, char)| *letter == c.to_ascii_lowercase()) {
+        Some((_, digit)) => *digit,
+        None => c
+    }).collect();
+}
+
+
+
+
+// The word first, then its case variants, leetspeak forms of those and finally digit suffixes on everything.
+// Suffixed candidates are generated lazily since there are up to 1.1 million of them per base candidate
+pub fn mangle(word: &str, rules: &[ManglingRules], max_digits: u32) -> impl Iterator<Item = String> {
+    let mut candidates: Vec<String> = vec![word.to_string()];
+
+    if rules.contains(&ManglingRules::Case) {
+        candidates.extend([word.to_lowercase(), word.to_uppercase(), capitalize(word), toggle_case(word)]);
+    }
+    if rules.contains(&ManglingRules::Leet) {
+        let leet: Vec<String> = candidates.iter().map(|candidate: &String| leetspeak(candidate)).collect();
+        candidates.extend(leet);
+    }
+
+    let mut seen: HashSet<String> = HashSet::new();
+    candidates.retain(|candidate: &String| seen.insert(candidate.clone()));
+
+    let max_width: u32 = if rules.contains(&ManglingRules::Digits) { max_digits.min(MAX_DIGITS) } else { 0 };
+    let suffixed = candidates.clone().into_iter().flat_map(move |candidate: String| (1..=max_width).flat_map(move |width: u32| {
+        let candidate: String = candidate.clone();
+        (0..10u32.pow(width)).map(move |number: u32| format!("{}{:0width$}", candidate, number, width = width as usize))
+    }));
+    return candidates.into_iter().chain(suffixed);
+}
+
+
+
+
+// Threads take words one at a time and try every mangled candidate against the hashes that are still unknown.
+// A hash recovered by any thread is dropped from the others' pending lists before their next candidate
+pub fn crack(targets: &[HashTarget], words: &[String], rules: &[ManglingRules], max_digits: u32, threads: usize) -> Vec<Option<String>> {
+    let next: AtomicUsize = AtomicUsize::new(0);
+    let remaining: AtomicUsize = AtomicUsize::new(targets.len());
+    let solved: Vec<AtomicBool> = targets.iter().map(|_| AtomicBool::new(false)).collect();
+    let recovered: Mutex<Vec<Option<String>>> = Mutex::new(vec![None; targets.len()]);
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, words.len().max(1)) {
+            scope.spawn(|| loop {
+                let index: usize = next.fetch_add(1, Ordering::SeqCst);
+                if index >= words.len() || remaining.load(Ordering::SeqCst) == 0 {
+                    return;
+                }
+
+                let mut pending: Vec<usize> = (0..targets.len()).collect();
+                for candidate in mangle(&words[index], rules, max_digits) {
+                    if remaining.load(Ordering::SeqCst) == 0 {
+                        return;
+                    }
+                    pending.retain(|target: &usize| !solved[*target].load(Ordering::SeqCst));
+                    if pending.is_empty() {
+                        break;
+                    }
+
+                    for target in &pending {
+                        if targets[*target].matches(candidate.as_bytes()) {
+                            let mut found: std::sync::MutexGuard<Vec<Option<String>>> = recovered.lock().unwrap();
+                            if found[*target].is_none() {
+                                found[*target] = Some(candidate.clone());
+                                solved[*target].store(true, Ordering::SeqCst);
+                                remaining.fetch_sub(1, Ordering::SeqCst);
+                            }
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    return recovered.into_inner().unwrap();
+}
+
+
+
+
+// One hash per line, optionally as user:hash[:...] like /etc/shadow or a dump of user:hash pairs.
+// Shadow entries without a password hash (empty, or locked with * or !) have nothing to audit and are skipped
+pub fn parse_hash_line(line: &str) -> Option<(String, String)> {
+    let line: &str = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let (user, hash): (&str, &str) = match line.split_once(':') {
+        Some((user, rest)) => (user, rest.split(':').next().unwrap_or_default()),
+        None => (line, line)
+    };
+    if hash.is_empty() || hash.starts_with('*') || hash.starts_with('!') {
+        return None;
+    }
+    return Some((user.to_string(), hash.to_string()));
+}
+
+
+
+
+#[derive(Debug, Args, Clone)]
+pub struct CrackHashAlg {
+    /// Hash to audit (MD5/SHA hex digest, crypt(3), bcrypt or passlib PBKDF2), can be repeated
+    #[arg(long)]
+    pub hash: Vec<String>,
+    /// File with one hash or user:hash entry per line
+    #[arg(long, required_unless_present = "hash")]
+    pub hash_file: Option<String>,
+    /// Algorithm of hex digests (guessed from the length when omitted)
+    #[arg(short, long)]
+    pub algorithm: Option<HashAlgorithms>,
+    /// Wordlist with one candidate password per line
+    #[arg(short, long)]
+    pub wordlist: String,
+    /// Mangling rules applied to every word
+    #[arg(short, long, value_enum, value_delimiter = ',')]
+    pub rules: Vec<ManglingRules>,
+    /// Longest digit suffix tried by the digits rule
+    #[arg(short, long, default_value_t = 2, value_parser = clap::value_parser!(u32).range(0..=MAX_DIGITS as i64))]
+    pub digits: u32,
+    /// Number of threads (all cores when omitted)
+    #[arg(short, long)]
+    pub jobs: Option<usize>
+}
+
+impl CrackHashAlg {
+    pub fn execute(self) -> () {
+        let (labels, targets): (Vec<String>, Vec<HashTarget>) = match self.targets() {
+            Ok((targets, warnings)) => {
+                for warning in warnings {
+                    eprintln!("WARNING: {}", warning);
+                }
+                targets.into_iter().unzip()
+            }
+            Err(error) => {
+                println!("{}", error);
+                std::process::exit(1);
+            }
+        };
+        let words: Vec<String> = match fs::read(&self.wordlist) {
+            Ok(contents) => String::from_utf8_lossy(&contents).lines().map(|line: &str| line.to_string()).collect(),
+            Err(error) => {
+                println!("could not read wordlist {}: {}", self.wordlist, error);
+                std::process::exit(1);
+            }
+        };
+
+        let threads: usize = self.jobs.unwrap_or_else(|| thread::available_parallelism().map(|n| n.get()).unwrap_or(1));
+        let recovered: Vec<Option<String>> = crack(&targets, &words, &self.rules, self.digits, threads);
+
+        for (label, password) in labels.iter().zip(&recovered) {
+            match password {
+                Some(password) => println!("{}: {}", label, password),
+                None => println!("{}: not found", label)
+            }
+        }
+        println!("recovered {} of {} hashes", recovered.iter().filter(|password: &&Option<String>| password.is_some()).count(), recovered.len());
+    }
+
+    // A bad --hash is an error, a bad line in the hash file only a warning so one entry doesn't stop the audit
+    fn targets(&self) -> Result<(LabelledTargets, Vec<String>), String> {
+        let mut targets: LabelledTargets = Vec::new();
+        let mut warnings: Vec<String> = Vec::new();
+
+        for hash in &self.hash {
+            let target: HashTarget = HashTarget::parse(hash, self.algorithm.as_ref()).map_err(|error: String| format!("{}: {}", hash, error))?;
+            targets.push((hash.clone(), target));
+        }
+
+        if let Some(path) = &self.hash_file {
+            let contents: String = fs::read_to_string(path).map_err(|e| format!("could not read hash file {}: {}", path, e))?;
+
+            for (number, line) in contents.lines().enumerate() {
+                if let Some((label, hash)) = parse_hash_line(line) {
+                    match HashTarget::parse(&hash, self.algorithm.as_ref()) {
+                        Ok(target) => targets.push((label, target)),
+                        Err(error) => warnings.push(format!("{} line {}: skipping {}: {}", path, number + 1, label, error))
+                    }
+                }
+            }
+        }
+
+        if targets.is_empty() {
+            return Err("no hash to audit".to_string());
+        }
+        return Ok((targets, warnings));
+    }
+}
+
+#[cfg(test)]
+mod crack_hash_test {
+    use std::env;
+    use super::*;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        return list.iter().map(|word: &&str| word.to_string()).collect();
+    }
+
+    #[test]
+    fn parse_target_test() -> () {
+        assert_eq!(HashTarget::parse("f356cd9a88a3b66bbe072b7dc91550c3", None).unwrap(), HashTarget::Digest { algorithm: HashAlgorithms::Md5, digest: hex_to_bytes("f356cd9a88a3b66bbe072b7dc91550c3").unwrap() });
+        assert!(matches!(HashTarget::parse("f356cd9a88a3b66bbe072b7dc91550c3", Some(&HashAlgorithms::Md4)).unwrap(), HashTarget::Digest { algorithm: HashAlgorithms::Md4, .. }));
+        assert!(HashTarget::parse("f356cd9a88a3b66bbe072b7dc91550c3", Some(&HashAlgorithms::Sha1)).is_err());
+        assert!(HashTarget::parse("abcdef", None).is_err());
+        assert_eq!(HashTarget::parse("arlEKn0OzVJn.", None).unwrap(), HashTarget::Crypt("arlEKn0OzVJn.".to_string()));
+        assert!(HashTarget::parse("$y$j9T$salt$hash", None).is_err());
+        assert!(matches!(HashTarget::parse("$pbkdf2-sha256$1000$AQIDc2FsdHl5$MH3BbkqdqA98gwAJ49Yf4zYF3Zb2yihJ5EtF8FxOHi4", None).unwrap(), HashTarget::Pbkdf2 { prf: Pbkdf2Prf::HmacSha256, iterations: 1000, .. }));
+        assert!(HashTarget::parse("$pbkdf2-md5$1000$AQIDc2FsdHl5$MH3B", None).is_err());
+        assert!(HashTarget::parse("$pbkdf2-sha256$0$AQIDc2FsdHl5$MH3B", None).is_err());
+        assert!(HashTarget::parse("arlEKn0OzVJ!.", None).is_err());
+        assert!(HashTarget::parse("$1$saltsalt$tTWg0JeO", None).is_err());
+        assert!(HashTarget::parse("$2b$04$tooshort", None).is_err());
+        assert!(HashTarget::parse("$2b$40$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW", None).is_err());
+    }
+
+    #[test]
+    fn mangle_test() -> () {
+        assert_eq!(mangle("Password", &[], 2).collect::<Vec<String>>(), vec!["Password"]);
+        assert_eq!(mangle("pAssword", &[ManglingRules::Case], 2).collect::<Vec<String>>(), vec!["pAssword", "password", "PASSWORD", "Password", "PaSSWORD"]);
+        assert_eq!(mangle("letmein", &[ManglingRules::Leet], 2).collect::<Vec<String>>(), vec!["letmein", "l3tm31n"]);
+        assert_eq!(mangle("dragon", &[ManglingRules::Digits], 2).count(), 1 + 10 + 100);
+        assert!(mangle("summer", &[ManglingRules::Case, ManglingRules::Digits], 4).any(|candidate: String| candidate == "Summer2024"));
+        assert_eq!(mangle("dragon", &[ManglingRules::Digits], 20).last(), Some("dragon999999".to_string()));
+    }
+
+    fn parse_targets(hashes: &[&str]) -> Vec<HashTarget> {
+        return hashes.iter().map(|hash: &&str| HashTarget::parse(hash, None).unwrap()).collect();
+    }
+
+    #[test]
+    fn crack_test() -> () {
+        let targets: Vec<HashTarget> = parse_targets(&["f356cd9a88a3b66bbe072b7dc91550c3",
+                                                       "9d3ebb53437c7e639a2a94d505d9a449d59b7212",
+                                                       "000c285457fc971f862a79b786476c78812c8897063c6fa9c045f579a3b2d63f",
+                                                       "$pbkdf2-sha256$10$AQIDc2FsdHl5$J6P12iZvdJMFuBjKajgOJWr2sr9LixVVFAx4tu1Vhak",
+                                                       "$pbkdf2$5$AQIDc2FsdHl5$55YYlhL/yrRucGaofA.eDlB9Ll4",
+                                                       "0000000000000000000000000000000000000000"]);
+        let recovered: Vec<Option<String>> = crack(&targets, &words(&["password", "letmein", "monkey", "dragon"]), &[ManglingRules::Case, ManglingRules::Leet, ManglingRules::Digits], 2, 4);
+
+        assert_eq!(recovered, vec![Some("PASSWORD7".to_string()), Some("l3tm31n".to_string()), Some("monkey".to_string()),
+                                   Some("Dragon42".to_string()), Some("p455w0rd".to_string()), None]);
+    }
+
+    // Slow hashes are only tried against the plain words
+    #[test]
+    fn crack_crypt_test() -> () {
+        let targets: Vec<HashTarget> = parse_targets(&["arlEKn0OzVJn.",
+                                                       "$1$saltsalt$tTWg0JeO/sYmHvtKmZE8c.",
+                                                       "$2a$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW",
+                                                       "$5$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc5"]);
+        let recovered: Vec<Option<String>> = crack(&targets, &words(&["test", "foob", "U*U", "Hello world!"]), &[], 0, 2);
+
+        assert_eq!(recovered, vec![Some("foob".to_string()), Some("test".to_string()), Some("U*U".to_string()), Some("Hello world!".to_string())]);
+    }
+
+    #[test]
+    fn hash_file_test() -> () {
+        assert_eq!(parse_hash_line("alice:$1$saltsalt$tTWg0JeO/sYmHvtKmZE8c.:19000:0:99999:7:::"), Some(("alice".to_string(), "$1$saltsalt$tTWg0JeO/sYmHvtKmZE8c.".to_string())));
+        assert_eq!(parse_hash_line("f356cd9a88a3b66bbe072b7dc91550c3"), Some(("f356cd9a88a3b66bbe072b7dc91550c3".to_string(), "f356cd9a88a3b66bbe072b7dc91550c3".to_string())));
+        assert_eq!(parse_hash_line("# exported test users"), None);
+        assert_eq!(parse_hash_line("root:*:19000:0:99999:7:::"), None);
+        assert_eq!(parse_hash_line("daemon:!"), None);
+        assert_eq!(parse_hash_line("guest::19000:0:99999:7:::"), None);
+
+        let path: String = env::temp_dir().join(format!("crack_hash_test_{}.txt", std::process::id())).to_string_lossy().to_string();
+        fs::write(&path, "bob:000c285457fc971f862a79b786476c78812c8897063c6fa9c045f579a3b2d63f\n\ncarol:nothex\n").unwrap();
+
+        let instance_1: CrackHashAlg = CrackHashAlg {
+            hash: vec!["arlEKn0OzVJn.".to_string()],
+            hash_file: None,
+            algorithm: None,
+            wordlist: "words.txt".to_string(),
+            rules: vec![],
+            digits: 2,
+            jobs: Some(1)
+        };
+        assert_eq!(instance_1.targets().unwrap().0.len(), 1);
+
+        let instance_2: CrackHashAlg = CrackHashAlg {
+            hash_file: Some(path.clone()),
+            ..instance_1.clone()
+        };
+        let (targets, warnings): (LabelledTargets, Vec<String>) = instance_2.targets().unwrap();
+        assert_eq!(targets.len(), 2);
+        assert_eq!(targets[1].0, "bob");
+        assert_eq!(warnings, vec![format!("{} line 3: skipping carol: invalid hex digits no", path)]);
+
+        let instance_3: CrackHashAlg = CrackHashAlg {
+            hash: vec!["nothex".to_string()],
+            ..instance_2.clone()
+        };
+        assert!(instance_3.targets().unwrap_err().starts_with("nothex: "));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn shadow_file_test() -> () {
+        let path: String = env::temp_dir().join(format!("crack_hash_shadow_{}", std::process::id())).to_string_lossy().to_string();
+        fs::write(&path, "root:*:19000:0:99999:7:::\ndaemon:!\nlocked:!$1$saltsalt$tTWg0JeO/sYmHvtKmZE8c.:19000:0:99999:7:::\nalice:$1$saltsalt$tTWg0JeO/sYmHvtKmZE8c.:19000:0:99999:7:::\n").unwrap();
+
+        let instance_1: CrackHashAlg = CrackHashAlg {
+            hash: vec![],
+            hash_file: Some(path.clone()),
+            algorithm: None,
+            wordlist: "words.txt".to_string(),
+            rules: vec![],
+            digits: 2,
+            jobs: Some(1)
+        };
+        let (targets, warnings): (LabelledTargets, Vec<String>) = instance_1.targets().unwrap();
+        assert_eq!(targets, vec![("alice".to_string(), HashTarget::Crypt("$1$saltsalt$tTWg0JeO/sYmHvtKmZE8c.".to_string()))]);
+        assert!(warnings.is_empty());
+
+        fs::write(&path, "root:*:19000:0:99999:7:::\n").unwrap();
+        assert!(instance_1.targets().is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
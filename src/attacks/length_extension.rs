@@ -0,0 +1,164 @@
+use clap::{Args, ValueEnum};
+use crate::{hashes::{hash_function::{digest, HashAlgorithms, HashFunction}, md5::Md5Compression, merkle_damgard::{CompressionFunction, MerkleDamgard}, sha1::Sha1Compression, sha2::Sha256Compression}, ops::{bytes_to_hex, hex_to_bytes}};
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum LengthExtensionAlgorithms {
+    Md5,
+    Sha1,
+    Sha256
+}
+impl LengthExtensionAlgorithms {
+    fn hash_algorithm(&self) -> HashAlgorithms {
+        match self {
+            LengthExtensionAlgorithms::Md5 => HashAlgorithms::Md5,
+            LengthExtensionAlgorithms::Sha1 => HashAlgorithms::Sha1,
+            LengthExtensionAlgorithms::Sha256 => HashAlgorithms::Sha256
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Forgery {
+    pub message: Vec<u8>,
+    pub digest: Vec<u8>
+}
+
+fn words_be(digest: &[u8]) -> Vec<u32> {
+    return digest.chunks(4).map(|chunk: &[u8]| u32::from_be_bytes(chunk.try_into().unwrap())).collect();
+}
+
+
+
+
+fn words_le(digest: &[u8]) -> Vec<u32> {
+    return digest.chunks(4).map(|chunk: &[u8]| u32::from_le_bytes(chunk.try_into().unwrap())).collect();
+}
+
+
+
+
+// The digest is the whole chaining state, so hashing can resume right after the padding of secret || message
+fn extend<C: CompressionFunction>(core: C, original_length: usize, suffix: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let glue_padding: Vec<u8> = MerkleDamgard::new(core.clone()).padding(original_length as u128);
+
+    let mut hasher: MerkleDamgard<C> = MerkleDamgard::from_state(core, (original_length + glue_padding.len()) as u128);
+    hasher.update(suffix);
+
+    return (glue_padding, hasher.finalize());
+}
+
+
+
+
+pub fn forge(algorithm: &LengthExtensionAlgorithms, known_digest: &[u8], message: &[u8], secret_length: usize, suffix: &[u8]) -> Result<Forgery, String> {
+    let output_size: usize = algorithm.hash_algorithm().hasher().output_size();
+    if known_digest.len() != output_size {
+        return Err(format!("{} digest must be {} bytes", algorithm.hash_algorithm().name(), output_size));
+    }
+
+    let original_length: usize = secret_length + message.len();
+    let (glue_padding, forged_digest): (Vec<u8>, Vec<u8>) = match algorithm {
+        LengthExtensionAlgorithms::Md5 => extend(Md5Compression { state: words_le(known_digest).try_into().unwrap() }, original_length, suffix),
+        LengthExtensionAlgorithms::Sha1 => extend(Sha1Compression { state: words_be(known_digest).try_into().unwrap() }, original_length, suffix),
+        LengthExtensionAlgorithms::Sha256 => extend(Sha256Compression { state: words_be(known_digest).try_into().unwrap(), output_size: 32 }, original_length, suffix)
+    };
+
+    return Ok(Forgery {
+        message: [message, &glue_padding, suffix].concat(),
+        digest: forged_digest
+    });
+}
+
+
+
+
+#[derive(Debug, Args, Clone)]
+pub struct LengthExtensionAlg {
+    /// Hash function used as H(secret || message)
+    #[arg(short, long)]
+    pub algorithm: LengthExtensionAlgorithms,
+    /// The known digest H(secret || message) in hex
+    #[arg(short, long)]
+    pub digest: String,
+    /// The known message
+    #[arg(short, long)]
+    pub message: String,
+    /// Length in bytes of the unknown secret prefix
+    #[arg(short = 'l', long)]
+    pub secret_length: usize,
+    /// Data appended to the message
+    #[arg(short, long)]
+    pub suffix: String,
+    /// The secret itself, only used to show that the forged digest is right
+    #[arg(long)]
+    pub secret: Option<String>
+}
+
+impl LengthExtensionAlg {
+    pub fn execute(self) -> () {
+        let forgery: Forgery = match self.forge() {
+            Ok(forgery) => forgery,
+            Err(error) => {
+                println!("{}", error);
+                return ();
+            }
+        };
+
+        println!("forged message (hex): {}", bytes_to_hex(&forgery.message));
+        println!("forged digest: {}", bytes_to_hex(&forgery.digest));
+
+        if let Some(secret) = &self.secret {
+            let actual: Vec<u8> = digest(&self.algorithm.hash_algorithm(), &[secret.as_bytes(), &forgery.message].concat());
+            let status: &str = if actual == forgery.digest { "matches" } else { "does NOT match" };
+
+            println!("H(secret || forged message) = {} {} the forgery", bytes_to_hex(&actual), status);
+        }
+        println!("HMAC is not affected: its outer hash keeps the inner state out of reach");
+    }
+
+    fn forge(&self) -> Result<Forgery, String> {
+        return forge(&self.algorithm, &hex_to_bytes(&self.digest)?, self.message.as_bytes(), self.secret_length, self.suffix.as_bytes());
+    }
+}
+
+#[cfg(test)]
+mod length_extension_test {
+    use super::*;
+
+    fn check_forgery(algorithm: LengthExtensionAlgorithms, secret: &[u8]) -> () {
+        let message: &[u8] = b"user=guest&role=reader";
+        let known_digest: Vec<u8> = digest(&algorithm.hash_algorithm(), &[secret, message].concat());
+
+        let forgery: Forgery = forge(&algorithm, &known_digest, message, secret.len(), b"&role=admin").unwrap();
+
+        assert!(forgery.message.starts_with(message) && forgery.message.ends_with(b"&role=admin"));
+        assert_eq!(forgery.digest, digest(&algorithm.hash_algorithm(), &[secret, &forgery.message].concat()));
+    }
+
+    #[test]
+    fn forge_test() -> () {
+        check_forgery(LengthExtensionAlgorithms::Md5, b"secret");
+        check_forgery(LengthExtensionAlgorithms::Sha1, b"a much longer secret that spans into the next block of input");
+        check_forgery(LengthExtensionAlgorithms::Sha256, b"");
+        assert!(forge(&LengthExtensionAlgorithms::Sha256, &[0; 20], b"message", 6, b"suffix").is_err());
+    }
+
+    #[test]
+    fn execute_forge_test() -> () {
+        let instance_1: LengthExtensionAlg = LengthExtensionAlg {
+            algorithm: LengthExtensionAlgorithms::Sha1,
+            digest: bytes_to_hex(&digest(&HashAlgorithms::Sha1, b"keydata")),
+            message: "data".to_string(),
+            secret_length: 3,
+            suffix: "more".to_string(),
+            secret: None
+        };
+        assert_eq!(instance_1.forge().unwrap().digest, digest(&HashAlgorithms::Sha1, &[b"key".as_slice(), &instance_1.forge().unwrap().message].concat()));
+
+        let instance_2: LengthExtensionAlg = LengthExtensionAlg {
+            digest: "zz".to_string(),
+            ..instance_1.clone()
+        };
+        assert!(instance_2.forge().is_err());
+    }
+}
@@ -0,0 +1,787 @@
+use std::{cmp::Ordering, ops::{Add, Div, Mul, Rem, Shl, Shr, Sub}};
+use rand::{rngs::OsRng, RngCore};
+
+// Below this many limbs schoolbook multiplication beats the Karatsuba recursion
+const KARATSUBA_THRESHOLD: usize = 32;
+const SMALL_PRIMES: [u64; 54] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97, 101, 103, 107, 109, 113,
+                                 127, 131, 137, 139, 149, 151, 157, 163, 167, 173, 179, 181, 191, 193, 197, 199, 211, 223, 227, 229, 233, 239, 241, 251];
+
+// Unsigned integer of any size, as little endian 64 bit limbs without leading zero limbs
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigUint {
+    limbs: Vec<u64>
+}
+
+fn normalized(mut limbs: Vec<u64>) -> Vec<u64> {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+    return limbs;
+}
+
+
+
+
+fn compare_limbs(a: &[u64], b: &[u64]) -> Ordering {
+    let a: &[u64] = &a[..a.len() - a.iter().rev().take_while(|limb: &&u64| **limb == 0).count()];
+    let b: &[u64] = &b[..b.len() - b.iter().rev().take_while(|limb: &&u64| **limb == 0).count()];
+
+    return a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()));
+}
+
+
+
+
+fn add_limbs(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut sum: Vec<u64> = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry: bool = false;
+
+    for i in 0..a.len().max(b.len()) {
+        let (partial, overflow_1): (u64, bool) = a.get(i).unwrap_or(&0).overflowing_add(*b.get(i).unwrap_or(&0));
+        let (limb, overflow_2): (u64, bool) = partial.overflowing_add(carry as u64);
+        sum.push(limb);
+        carry = overflow_1 || overflow_2;
+    }
+    sum.push(carry as u64);
+
+    return normalized(sum);
+}
+
+
+
+
+// a - b, with a >= b
+fn sub_limbs(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut difference: Vec<u64> = Vec::with_capacity(a.len());
+    let mut borrow: bool = false;
+
+    for i in 0..a.len().max(b.len()) {
+        let (partial, overflow_1): (u64, bool) = a.get(i).unwrap_or(&0).overflowing_sub(*b.get(i).unwrap_or(&0));
+        let (limb, overflow_2): (u64, bool) = partial.overflowing_sub(borrow as u64);
+        difference.push(limb);
+        borrow = overflow_1 || overflow_2;
+    }
+    assert!(!borrow, "attempt to subtract with overflow");
+
+    return normalized(difference);
+}
+
+
+
+
+fn schoolbook_mul(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut product: Vec<u64> = vec![0; a.len() + b.len()];
+
+    for (i, a_limb) in a.iter().enumerate() {
+        let mut carry: u128 = 0;
+        for (j, b_limb) in b.iter().enumerate() {
+            let t: u128 = *a_limb as u128 * *b_limb as u128 + product[i + j] as u128 + carry;
+            product[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        product[i + b.len()] = carry as u64;
+    }
+    return normalized(product);
+}
+
+
+
+
+fn shifted_limbs(limbs: &[u64], offset: usize) -> Vec<u64> {
+    if limbs.is_empty() {
+        return Vec::new();
+    }
+    return [vec![0; offset], limbs.to_vec()].concat();
+}
+
+
+
+
+// a * b = z2 B^2m + z1 B^m + z0 with z1 = (a0 + a1)(b0 + b1) - z2 - z0, three half size products instead of four
+fn karatsuba_mul(a: &[u64], b: &[u64]) -> Vec<u64> {
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+        return schoolbook_mul(a, b);
+    }
+
+    let half: usize = a.len().max(b.len()) / 2;
+    let (a0, a1): (&[u64], &[u64]) = a.split_at(half.min(a.len()));
+    let (b0, b1): (&[u64], &[u64]) = b.split_at(half.min(b.len()));
+
+    let z0: Vec<u64> = karatsuba_mul(a0, b0);
+    let z2: Vec<u64> = karatsuba_mul(a1, b1);
+    let z1: Vec<u64> = sub_limbs(&sub_limbs(&karatsuba_mul(&add_limbs(a0, a1), &add_limbs(b0, b1)), &z0), &z2);
+
+    return add_limbs(&add_limbs(&z0, &shifted_limbs(&z1, half)), &shifted_limbs(&z2, 2 * half));
+}
+
+
+
+
+fn div_rem_small(u: &[u64], divisor: u64) -> (Vec<u64>, u64) {
+    let mut quotient: Vec<u64> = vec![0; u.len()];
+    let mut remainder: u128 = 0;
+
+    for i in (0..u.len()).rev() {
+        let current: u128 = (remainder << 64) | u[i] as u128;
+        quotient[i] = (current / divisor as u128) as u64;
+        remainder = current % divisor as u128;
+    }
+    return (normalized(quotient), remainder as u64);
+}
+
+
+
+
+// Knuth's algorithm D (TAOCP 4.3.1): the divisor is shifted so its top limb has its high bit set,
+// which keeps each estimated quotient limb at most two above the real one
+fn div_rem_limbs(u: &[u64], v: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    if compare_limbs(u, v) == Ordering::Less {
+        return (Vec::new(), normalized(u.to_vec()));
+    }
+    if v.len() == 1 {
+        let (quotient, remainder): (Vec<u64>, u64) = div_rem_small(u, v[0]);
+        return (quotient, normalized(vec![remainder]));
+    }
+
+    let n: usize = v.len();
+    let m: usize = u.len() - n;
+    let shift: u32 = v[n - 1].leading_zeros();
+    let vn: Vec<u64> = BigUint { limbs: v.to_vec() }.shl(shift as usize).limbs;
+    let mut un: Vec<u64> = BigUint { limbs: u.to_vec() }.shl(shift as usize).limbs;
+    un.resize(u.len() + 1, 0);
+    let mut quotient: Vec<u64> = vec![0; m + 1];
+
+    for j in (0..=m).rev() {
+        let numerator: u128 = ((un[j + n] as u128) << 64) | un[j + n - 1] as u128;
+        let mut q_hat: u128 = numerator / vn[n - 1] as u128;
+        let mut r_hat: u128 = numerator % vn[n - 1] as u128;
+
+        while q_hat > u64::MAX as u128 || q_hat * vn[n - 2] as u128 > ((r_hat << 64) | un[j + n - 2] as u128) {
+            q_hat -= 1;
+            r_hat += vn[n - 1] as u128;
+            if r_hat > u64::MAX as u128 {
+                break;
+            }
+        }
+
+        // un[j..=j + n] -= q_hat * vn
+        let mut borrow: i128 = 0;
+        let mut carry: u128 = 0;
+        for i in 0..n {
+            let product: u128 = q_hat * vn[i] as u128 + carry;
+            carry = product >> 64;
+            let t: i128 = un[i + j] as i128 - borrow - (product as u64) as i128;
+            un[i + j] = t as u64;
+            borrow = (t < 0) as i128;
+        }
+        let t: i128 = un[j + n] as i128 - borrow - carry as i128;
+        un[j + n] = t as u64;
+
+        // q_hat was one too large: add the divisor back
+        if t < 0 {
+            q_hat -= 1;
+            let mut carry: u128 = 0;
+            for i in 0..n {
+                let sum: u128 = un[i + j] as u128 + vn[i] as u128 + carry;
+                un[i + j] = sum as u64;
+                carry = sum >> 64;
+            }
+            un[j + n] = un[j + n].wrapping_add(carry as u64);
+        }
+        quotient[j] = q_hat as u64;
+    }
+
+    let remainder: BigUint = BigUint { limbs: normalized(un[..n].to_vec()) }.shr(shift as usize);
+    return (normalized(quotient), remainder.limbs);
+}
+
+
+
+
+impl BigUint {
+    pub fn zero() -> BigUint {
+        return BigUint { limbs: Vec::new() };
+    }
+
+    pub fn one() -> BigUint {
+        return BigUint::from_u64(1);
+    }
+
+    pub fn from_u64(value: u64) -> BigUint {
+        return BigUint { limbs: normalized(vec![value]) };
+    }
+
+    pub fn from_bytes_be(bytes: &[u8]) -> BigUint {
+        let limbs: Vec<u64> = bytes.rchunks(8).map(|chunk: &[u8]| chunk.iter().fold(0, |limb: u64, byte: &u8| (limb << 8) | *byte as u64)).collect();
+        return BigUint { limbs: normalized(limbs) };
+    }
+
+    // Minimal big endian encoding, empty for zero
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        let bytes: Vec<u8> = self.limbs.iter().rev().flat_map(|limb: &u64| limb.to_be_bytes()).collect();
+        let leading_zeros: usize = bytes.iter().take_while(|byte: &&u8| **byte == 0).count();
+
+        return bytes[leading_zeros..].to_vec();
+    }
+
+    // Big endian encoding left padded with zeros to length bytes
+    pub fn to_bytes_be_padded(&self, length: usize) -> Result<Vec<u8>, String> {
+        let bytes: Vec<u8> = self.to_bytes_be();
+        if bytes.len() > length {
+            return Err(format!("integer does not fit in {} bytes", length));
+        }
+        return Ok([vec![0; length - bytes.len()], bytes].concat());
+    }
+
+    pub fn from_str_radix(digits: &str, radix: u32) -> Result<BigUint, String> {
+        if digits.is_empty() || !(2..=36).contains(&radix) {
+            return Err(format!("invalid base {} number {:?}", radix, digits));
+        }
+
+        let mut value: BigUint = BigUint::zero();
+        let radix_big: BigUint = BigUint::from_u64(radix as u64);
+        for character in digits.chars().filter(|c: &char| *c != '_') {
+            let digit: u32 = character.to_digit(radix).ok_or(format!("invalid base {} digit {:?}", radix, character))?;
+            value = &(&value * &radix_big) + &BigUint::from_u64(digit as u64);
+        }
+        return Ok(value);
+    }
+
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        if self.is_zero() {
+            return "0".to_string();
+        }
+
+        let mut digits: Vec<char> = Vec::new();
+        let mut value: Vec<u64> = self.limbs.clone();
+        while !value.is_empty() {
+            let (quotient, digit): (Vec<u64>, u64) = div_rem_small(&value, radix as u64);
+            digits.push(std::char::from_digit(digit as u32, radix).unwrap());
+            value = quotient;
+        }
+        return digits.iter().rev().collect();
+    }
+
+    pub fn is_zero(&self) -> bool {
+        return self.limbs.is_empty();
+    }
+
+    pub fn is_odd(&self) -> bool {
+        return self.limbs.first().is_some_and(|limb: &u64| limb & 1 == 1);
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        return match self.limbs.len() {
+            0 => Some(0),
+            1 => Some(self.limbs[0]),
+            _ => None
+        };
+    }
+
+    // Number of significant bits
+    pub fn bits(&self) -> usize {
+        return match self.limbs.last() {
+            Some(top) => 64 * self.limbs.len() - top.leading_zeros() as usize,
+            None => 0
+        };
+    }
+
+    pub fn bit(&self, index: usize) -> bool {
+        return self.limbs.get(index / 64).is_some_and(|limb: &u64| (limb >> (index % 64)) & 1 == 1);
+    }
+
+    pub fn set_bit(&mut self, index: usize) -> () {
+        if self.limbs.len() <= index / 64 {
+            self.limbs.resize(index / 64 + 1, 0);
+        }
+        self.limbs[index / 64] |= 1 << (index % 64);
+    }
+
+    pub fn trailing_zeros(&self) -> usize {
+        return (0..self.bits()).take_while(|index: &usize| !self.bit(*index)).count();
+    }
+
+    pub fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+        if self < other {
+            return None;
+        }
+        return Some(BigUint { limbs: sub_limbs(&self.limbs, &other.limbs) });
+    }
+
+    pub fn div_rem(&self, divisor: &BigUint) -> (BigUint, BigUint) {
+        assert!(!divisor.is_zero(), "attempt to divide by zero");
+        let (quotient, remainder): (Vec<u64>, Vec<u64>) = div_rem_limbs(&self.limbs, &divisor.limbs);
+
+        return (BigUint { limbs: quotient }, BigUint { limbs: remainder });
+    }
+
+    pub fn gcd(&self, other: &BigUint) -> BigUint {
+        let (mut a, mut b): (BigUint, BigUint) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            (a, b) = (b.clone(), &a % &b);
+        }
+        return a;
+    }
+
+    // self^exponent mod modulus, in the Montgomery domain whenever the modulus is odd
+    pub fn mod_pow(&self, exponent: &BigUint, modulus: &BigUint) -> BigUint {
+        assert!(!modulus.is_zero(), "attempt to reduce modulo zero");
+        if modulus.is_odd() {
+            return Montgomery::new(modulus).pow(self, exponent);
+        }
+
+        let base: BigUint = self % modulus;
+        let mut result: BigUint = &BigUint::one() % modulus;
+        for index in (0..exponent.bits()).rev() {
+            result = &(&result * &result) % modulus;
+            if exponent.bit(index) {
+                result = &(&result * &base) % modulus;
+            }
+        }
+        return result;
+    }
+
+    // Extended Euclid, with the Bezout coefficient of self kept reduced modulo the modulus so it never goes negative
+    pub fn mod_inverse(&self, modulus: &BigUint) -> Option<BigUint> {
+        if modulus.is_zero() {
+            return None;
+        }
+
+        let (mut r_0, mut r_1): (BigUint, BigUint) = (modulus.clone(), self % modulus);
+        let (mut t_0, mut t_1): (BigUint, BigUint) = (BigUint::zero(), BigUint::one());
+
+        while !r_1.is_zero() {
+            let (quotient, remainder): (BigUint, BigUint) = r_0.div_rem(&r_1);
+            let product: BigUint = &(&quotient * &t_1) % modulus;
+            let t_2: BigUint = &(&t_0 + modulus).checked_sub(&product).unwrap() % modulus;
+
+            (r_0, r_1) = (r_1, remainder);
+            (t_0, t_1) = (t_1, t_2);
+        }
+
+        if r_0 != BigUint::one() {
+            return None;
+        }
+        return Some(&t_0 % modulus);
+    }
+
+    pub fn random_bits(bits: usize) -> BigUint {
+        let mut bytes: Vec<u8> = vec![0; bits.div_ceil(8)];
+        OsRng.fill_bytes(&mut bytes);
+        if !bits.is_multiple_of(8) {
+            bytes[0] &= (1 << (bits % 8)) - 1;
+        }
+        return BigUint::from_bytes_be(&bytes);
+    }
+
+    // Uniform in [0, bound), by rejection
+    pub fn random_below(bound: &BigUint) -> BigUint {
+        assert!(!bound.is_zero(), "random_below needs a positive bound");
+        loop {
+            let candidate: BigUint = BigUint::random_bits(bound.bits());
+            if &candidate < bound {
+                return candidate;
+            }
+        }
+    }
+
+    // Trial division by the primes below 256, then Miller-Rabin with random bases:
+    // a composite passes each round with probability at most 1/4
+    pub fn is_probable_prime(&self, rounds: usize) -> bool {
+        if let Some(small) = self.to_u64().filter(|value: &u64| *value <= 256) {
+            return SMALL_PRIMES.contains(&small);
+        }
+        if SMALL_PRIMES.iter().any(|prime: &u64| div_rem_small(&self.limbs, *prime).1 == 0) {
+            return false;
+        }
+
+        let n_minus_one: BigUint = self.checked_sub(&BigUint::one()).unwrap();
+        let s: usize = n_minus_one.trailing_zeros();
+        let d: BigUint = &n_minus_one >> s;
+        let montgomery: Montgomery = Montgomery::new(self);
+        let three: BigUint = BigUint::from_u64(3);
+
+        'witness: for _ in 0..rounds {
+            let base: BigUint = &BigUint::random_below(&self.checked_sub(&three).unwrap()) + &BigUint::from_u64(2);
+            let mut x: BigUint = montgomery.pow(&base, &d);
+            if x == BigUint::one() || x == n_minus_one {
+                continue;
+            }
+            for _ in 1..s {
+                x = &(&x * &x) % self;
+                if x == n_minus_one {
+                    continue 'witness;
+                }
+            }
+            return false;
+        }
+        return true;
+    }
+
+    // Whether self and 2 * self + 1 are both probable primes, 2 * self + 1 then being a safe prime.
+    // Trial division rules out a small factor of either one before any Miller-Rabin round:
+    // 2q + 1 is divisible by an odd prime r exactly when q = (r - 1) / 2 mod r
+    pub fn is_probable_sophie_germain_prime(&self, rounds: usize) -> bool {
+        let safe: BigUint = &(self << 1) + &BigUint::one();
+        if self.to_u64().is_some_and(|value: u64| value <= 256) {
+            return self.is_probable_prime(rounds) && safe.is_probable_prime(rounds);
+        }
+        if SMALL_PRIMES.iter().any(|prime: &u64| {
+            let remainder: u64 = div_rem_small(&self.limbs, *prime).1;
+            remainder == 0 || (*prime != 2 && remainder == (prime - 1) / 2)
+        }) {
+            return false;
+        }
+        return self.is_probable_prime(1) && safe.is_probable_prime(rounds) && self.is_probable_prime(rounds);
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        return compare_limbs(&self.limbs, &other.limbs);
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        return BigUint { limbs: add_limbs(&self.limbs, &other.limbs) };
+    }
+}
+
+impl Sub for &BigUint {
+    type Output = BigUint;
+
+    fn sub(self, other: &BigUint) -> BigUint {
+        return BigUint { limbs: sub_limbs(&self.limbs, &other.limbs) };
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        return BigUint { limbs: karatsuba_mul(&self.limbs, &other.limbs) };
+    }
+}
+
+impl Div for &BigUint {
+    type Output = BigUint;
+
+    fn div(self, other: &BigUint) -> BigUint {
+        return self.div_rem(other).0;
+    }
+}
+
+impl Rem for &BigUint {
+    type Output = BigUint;
+
+    fn rem(self, other: &BigUint) -> BigUint {
+        return self.div_rem(other).1;
+    }
+}
+
+impl Shl<usize> for &BigUint {
+    type Output = BigUint;
+
+    fn shl(self, bits: usize) -> BigUint {
+        let (limb_shift, bit_shift): (usize, usize) = (bits / 64, bits % 64);
+        let mut limbs: Vec<u64> = vec![0; limb_shift];
+        let mut carry: u64 = 0;
+
+        for limb in &self.limbs {
+            limbs.push(if bit_shift == 0 { *limb } else { (limb << bit_shift) | carry });
+            carry = if bit_shift == 0 { 0 } else { limb >> (64 - bit_shift) };
+        }
+        limbs.push(carry);
+
+        return BigUint { limbs: normalized(limbs) };
+    }
+}
+
+impl Shl<usize> for BigUint {
+    type Output = BigUint;
+
+    fn shl(self, bits: usize) -> BigUint {
+        return &self << bits;
+    }
+}
+
+impl Shr<usize> for &BigUint {
+    type Output = BigUint;
+
+    fn shr(self, bits: usize) -> BigUint {
+        let (limb_shift, bit_shift): (usize, usize) = (bits / 64, bits % 64);
+        if limb_shift >= self.limbs.len() {
+            return BigUint::zero();
+        }
+
+        let high: &[u64] = &self.limbs[limb_shift..];
+        let limbs: Vec<u64> = (0..high.len()).map(|i: usize| {
+            if bit_shift == 0 { high[i] } else { (high[i] >> bit_shift) | (high.get(i + 1).unwrap_or(&0) << (64 - bit_shift)) }
+        }).collect();
+
+        return BigUint { limbs: normalized(limbs) };
+    }
+}
+
+impl Shr<usize> for BigUint {
+    type Output = BigUint;
+
+    fn shr(self, bits: usize) -> BigUint {
+        return &self >> bits;
+    }
+}
+
+
+
+
+// Montgomery arithmetic modulo an odd n: numbers are kept as x R mod n with R = 2^(64 k),
+// so that reductions only need shifts instead of divisions
+pub struct Montgomery {
+    modulus: Vec<u64>,
+    // -n^-1 mod 2^64
+    n_prime: u64,
+    r_squared: BigUint,
+    size: usize
+}
+impl Montgomery {
+    pub fn new(modulus: &BigUint) -> Montgomery {
+        assert!(modulus.is_odd(), "Montgomery reduction needs an odd modulus");
+
+        // Newton iteration doubles the number of correct low bits of the inverse each step
+        let mut inverse: u64 = 1;
+        for _ in 0..6 {
+            inverse = inverse.wrapping_mul(2u64.wrapping_sub(modulus.limbs[0].wrapping_mul(inverse)));
+        }
+
+        let size: usize = modulus.limbs.len();
+        return Montgomery {
+            modulus: modulus.limbs.clone(),
+            n_prime: inverse.wrapping_neg(),
+            r_squared: &(&BigUint::one() << (128 * size)) % modulus,
+            size: size
+        };
+    }
+
+    fn padded(&self, value: &BigUint) -> Vec<u64> {
+        let mut limbs: Vec<u64> = value.limbs.clone();
+        limbs.resize(self.size, 0);
+        return limbs;
+    }
+
+    // a b R^-1 mod n, interleaving the multiplication with the word by word reduction (CIOS)
+    fn multiply(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        let size: usize = self.size;
+        let mut t: Vec<u64> = vec![0; size + 2];
+
+        for i in 0..size {
+            let mut carry: u128 = 0;
+            for j in 0..size {
+                let sum: u128 = t[j] as u128 + a[j] as u128 * b[i] as u128 + carry;
+                t[j] = sum as u64;
+                carry = sum >> 64;
+            }
+            let sum: u128 = t[size] as u128 + carry;
+            t[size] = sum as u64;
+            t[size + 1] = (sum >> 64) as u64;
+
+            let m: u64 = t[0].wrapping_mul(self.n_prime);
+            let mut carry: u128 = (t[0] as u128 + m as u128 * self.modulus[0] as u128) >> 64;
+            for j in 1..size {
+                let sum: u128 = t[j] as u128 + m as u128 * self.modulus[j] as u128 + carry;
+                t[j - 1] = sum as u64;
+                carry = sum >> 64;
+            }
+            let sum: u128 = t[size] as u128 + carry;
+            t[size - 1] = sum as u64;
+            t[size] = t[size + 1] + (sum >> 64) as u64;
+            t[size + 1] = 0;
+        }
+
+        if compare_limbs(&t, &self.modulus) != Ordering::Less {
+            t = sub_limbs(&t, &self.modulus);
+        }
+        t.resize(size, 0);
+        return t;
+    }
+
+    pub fn pow(&self, base: &BigUint, exponent: &BigUint) -> BigUint {
+        let modulus: BigUint = BigUint { limbs: self.modulus.clone() };
+        if modulus == BigUint::one() {
+            return BigUint::zero();
+        }
+
+        let base: Vec<u64> = self.multiply(&self.padded(&(base % &modulus)), &self.padded(&self.r_squared));
+        let mut result: Vec<u64> = self.multiply(&self.padded(&BigUint::one()), &self.padded(&self.r_squared));
+
+        for index in (0..exponent.bits()).rev() {
+            result = self.multiply(&result, &result);
+            if exponent.bit(index) {
+                result = self.multiply(&result, &base);
+            }
+        }
+
+        return BigUint { limbs: normalized(self.multiply(&result, &self.padded(&BigUint::one()))) };
+    }
+}
+
+#[cfg(test)]
+mod bignum_test {
+    use super::*;
+
+    fn hex(digits: &str) -> BigUint {
+        return BigUint::from_str_radix(digits, 16).unwrap();
+    }
+
+    fn dec(digits: &str) -> BigUint {
+        return BigUint::from_str_radix(digits, 10).unwrap();
+    }
+
+    #[test]
+    fn conversion_test() -> () {
+        assert_eq!(BigUint::from_bytes_be(&[0, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]).to_str_radix(16), "10203040506070809");
+        assert_eq!(hex("10203040506070809").to_bytes_be(), vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(hex("0102").to_bytes_be_padded(4).unwrap(), vec![0, 0, 1, 2]);
+        assert!(hex("010203").to_bytes_be_padded(2).is_err());
+        assert_eq!(dec("340282366920938463463374607431768211456"), &BigUint::one() << 128);
+        assert_eq!((&BigUint::one() << 128).to_str_radix(10), "340282366920938463463374607431768211456");
+        assert_eq!(BigUint::zero().to_str_radix(10), "0");
+        assert!(BigUint::zero().to_bytes_be().is_empty());
+        assert!(BigUint::from_str_radix("12z", 10).is_err());
+        assert_eq!(hex("8000000000000000000000000").bits(), 100);
+        assert_eq!(hex("8000000000000000000000000").trailing_zeros(), 99);
+
+        let mut value: BigUint = BigUint::one();
+        value.set_bit(130);
+        assert_eq!(value, &(&BigUint::one() << 130) + &BigUint::one());
+    }
+
+    #[test]
+    fn add_sub_test() -> () {
+        let max: BigUint = hex("ffffffffffffffffffffffffffffffff");
+        assert_eq!(&max + &BigUint::one(), &BigUint::one() << 128);
+        assert_eq!(&(&BigUint::one() << 128) - &BigUint::one(), max);
+        assert_eq!(max.checked_sub(&(&max + &BigUint::one())), None);
+        assert_eq!(&max - &max, BigUint::zero());
+        assert!(max < &max + &BigUint::one());
+    }
+
+    #[test]
+    fn shift_test() -> () {
+        let value: BigUint = hex("123456789abcdef0fedcba9876543210");
+        assert_eq!(&value << 4, hex("123456789abcdef0fedcba98765432100"));
+        assert_eq!(&value >> 68, hex("123456789abcdef"));
+        assert_eq!(&(&value << 131) >> 131, value);
+        assert_eq!(&value >> 128, BigUint::zero());
+    }
+
+    #[test]
+    fn mul_test() -> () {
+        assert_eq!(&hex("ffffffffffffffff") * &hex("ffffffffffffffff"), hex("fffffffffffffffe0000000000000001"));
+        assert_eq!(&dec("12345678901234567890") * &dec("98765432109876543210"), dec("1219326311370217952237463801111263526900"));
+        assert_eq!(&dec("12345678901234567890") * &BigUint::zero(), BigUint::zero());
+
+        // (2^4096 - 1)^2 = 2^8192 - 2^4097 + 1 goes through the Karatsuba recursion
+        let big: BigUint = &(&BigUint::one() << 4096) - &BigUint::one();
+        assert_eq!(&big * &big, &(&(&BigUint::one() << 8192) - &(&BigUint::one() << 4097)) + &BigUint::one());
+
+        let a: BigUint = BigUint::random_bits(5000);
+        let b: BigUint = BigUint::random_bits(3000);
+        assert_eq!(BigUint { limbs: karatsuba_mul(&a.limbs, &b.limbs) }, BigUint { limbs: schoolbook_mul(&a.limbs, &b.limbs) });
+    }
+
+    #[test]
+    fn div_rem_test() -> () {
+        assert_eq!(dec("1219326311370217952237463801111263526900").div_rem(&dec("12345678901234567890")), (dec("98765432109876543210"), BigUint::zero()));
+        assert_eq!(dec("1000000000000000000000000000007").div_rem(&dec("1000000000000")), (dec("1000000000000000000"), dec("7")));
+        assert_eq!(dec("5").div_rem(&dec("1000000000000000000000000")), (BigUint::zero(), dec("5")));
+
+        // Quotient limb estimates that need correcting
+        assert_eq!(hex("7fffffffffffffff800000000000000000000000000000000000000000000000").div_rem(&hex("800000000000000000000000000000010000000000000000")),
+                   (hex("fffffffffffffffe"), hex("7fffffffffffffff00000000000000020000000000000000")));
+        assert_eq!(hex("8000000000000000fffffffffffffffe0000000000000000").div_rem(&hex("8000000000000000ffffffffffffffff")),
+                   (hex("ffffffffffffffff"), hex("7fffffffffffffffffffffffffffffff")));
+        assert_eq!(hex("ffffffffffffffffffffffffffffffffffffffffffffffff").div_rem(&hex("ffffffffffffffff0000000000000001")),
+                   (hex("10000000000000000"), hex("fffffffffffffffeffffffffffffffff")));
+
+        for _ in 0..50 {
+            let dividend: BigUint = BigUint::random_bits(2048);
+            let divisor: BigUint = &BigUint::random_bits(1 + (OsRng.next_u32() % 1500) as usize) + &BigUint::one();
+            let (quotient, remainder): (BigUint, BigUint) = dividend.div_rem(&divisor);
+
+            assert!(remainder < divisor);
+            assert_eq!(&(&quotient * &divisor) + &remainder, dividend);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn div_by_zero_test() -> () {
+        let _ = &BigUint::one() / &BigUint::zero();
+    }
+
+    #[test]
+    fn mod_pow_test() -> () {
+        assert_eq!(dec("4").mod_pow(&dec("13"), &dec("497")), dec("445"));
+        assert_eq!(dec("2").mod_pow(&dec("100"), &dec("1000000")), dec("205376"));
+        assert_eq!(dec("7").mod_pow(&BigUint::zero(), &dec("13")), BigUint::one());
+        assert_eq!(dec("7").mod_pow(&dec("5"), &BigUint::one()), BigUint::zero());
+
+        // Fermat's little theorem with the Mersenne prime 2^521 - 1, odd modulus through Montgomery
+        let p: BigUint = &(&BigUint::one() << 521) - &BigUint::one();
+        let a: BigUint = dec("123456789012345678901234567890");
+        assert_eq!(a.mod_pow(&(&p - &BigUint::one()), &p), BigUint::one());
+
+        // Even modulus through plain square and multiply
+        assert_eq!(dec("3").mod_pow(&dec("1000"), &(&BigUint::one() << 100)), hex("6f7867dbe5616937bd3b85b21"));
+    }
+
+    #[test]
+    fn gcd_inverse_test() -> () {
+        assert_eq!(dec("1071").gcd(&dec("462")), dec("21"));
+        assert_eq!(dec("17").gcd(&BigUint::zero()), dec("17"));
+        assert_eq!(dec("3").mod_inverse(&dec("11")), Some(dec("4")));
+        assert_eq!(dec("6").mod_inverse(&dec("9")), None);
+        assert_eq!(dec("17").mod_inverse(&dec("3120")), Some(dec("2753")));
+
+        let modulus: BigUint = &(&BigUint::one() << 1279) - &BigUint::one();
+        for _ in 0..10 {
+            let value: BigUint = &BigUint::random_below(&modulus) + &BigUint::one();
+            let inverse: BigUint = value.mod_inverse(&modulus).unwrap();
+            assert_eq!(&(&value * &inverse) % &modulus, BigUint::one());
+        }
+    }
+
+    #[test]
+    fn primality_test() -> () {
+        let primes: [BigUint; 5] = [dec("2"), dec("251"), dec("65537"), &(&BigUint::one() << 127) - &BigUint::one(), &(&BigUint::one() << 521) - &BigUint::one()];
+        for prime in primes {
+            assert!(prime.is_probable_prime(20));
+        }
+
+        // Carmichael numbers, a strong pseudoprime to bases 2 and 3, and a product of two large primes
+        let composites: [BigUint; 5] = [dec("1"), dec("561"), dec("41041"), dec("1373653"), &(&(&BigUint::one() << 127) - &BigUint::one()) * &(&(&BigUint::one() << 89) - &BigUint::one())];
+        for composite in composites {
+            assert!(!composite.is_probable_prime(20));
+        }
+    }
+
+    #[test]
+    fn sophie_germain_test() -> () {
+        for q in [dec("2"), dec("3"), dec("83"), dec("1019"), dec("237316920141299476058754685203263177513")] {
+            assert!(q.is_probable_sophie_germain_prime(20));
+        }
+        // 2q + 1 = 15, 2q + 1 = 2^128 - 1 and a composite q
+        for q in [dec("7"), &(&BigUint::one() << 127) - &BigUint::one(), dec("237316920141299476058754685203263177515")] {
+            assert!(!q.is_probable_sophie_germain_prime(20));
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use crate::{hashes::hash_function::HashFunction, tables::{BLAKE2_SIGMA, SHA256_INITIAL_STATE, SHA512_INITIAL_STATE}};
+
+// Parameter block fields shared by both widths: digest length, key length, fanout 1 and depth 1
+fn parameter_word(output_size: usize, key_len: usize) -> u64 {
+    return 0x01010000 ^ ((key_len as u64) << 8) ^ output_size as u64;
+}
+
+
+
+
+fn check_parameters(name: &str, max_size: usize, output_size: usize, key: &[u8], salt: &[u8], personalization: &[u8]) -> Result<(), String> {
+    if output_size == 0 || output_size > max_size {
+        return Err(format!("{} output length must be between 1 and {} bytes", name, max_size));
+    }
+    if key.len() > max_size {
+        return Err(format!("{} key must be at most {} bytes", name, max_size));
+    }
+    if salt.len() > max_size / 4 || personalization.len() > max_size / 4 {
+        return Err(format!("{} salt and personalization must be at most {} bytes", name, max_size / 4));
+    }
+    return Ok(());
+}
+
+
+
+
+fn padded(data: &[u8], size: usize) -> Vec<u8> {
+    let mut padded: Vec<u8> = data.to_vec();
+    padded.resize(size, 0);
+
+    return padded;
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Blake2b {
+    pub state: [u64; 8],
+    buffer: Vec<u8>,
+    counter: u128,
+    output_size: usize
+}
+impl Blake2b {
+    pub fn new(output_size: usize, key: &[u8], salt: &[u8], personalization: &[u8]) -> Result<Blake2b, String> {
+        check_parameters("BLAKE2b", 64, output_size, key, salt, personalization)?;
+
+        let mut state: [u64; 8] = SHA512_INITIAL_STATE;
+        state[0] ^= parameter_word(output_size, key.len());
+
+        for (word, chunk) in state[4..].iter_mut().zip([padded(salt, 16), padded(personalization, 16)].concat().chunks(8)) {
+            *word ^= u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+
+        return Ok(Blake2b {
+            state: state,
+            buffer: if key.is_empty() { Vec::new() } else { padded(key, 128) },
+            counter: 0,
+            output_size: output_size
+        });
+    }
+
+    fn compress(&mut self, block: &[u8], last: bool) -> () {
+        let m: Vec<u64> = block.chunks(8).map(|chunk: &[u8]| u64::from_le_bytes(chunk.try_into().unwrap())).collect();
+        let mut v: [u64; 16] = [0; 16];
+
+        v[..8].copy_from_slice(&self.state);
+        v[8..].copy_from_slice(&SHA512_INITIAL_STATE);
+        v[12] ^= self.counter as u64;
+        v[13] ^= (self.counter >> 64) as u64;
+        if last {
+            v[14] = !v[14];
+        }
+
+        for round in 0..12 {
+            let s: [usize; 16] = BLAKE2_SIGMA[round % 10];
+
+            for (i, [a, b, c, d]) in [[0, 4, 8, 12], [1, 5, 9, 13], [2, 6, 10, 14], [3, 7, 11, 15], [0, 5, 10, 15], [1, 6, 11, 12], [2, 7, 8, 13], [3, 4, 9, 14]].into_iter().enumerate() {
+                v[a] = v[a].wrapping_add(v[b]).wrapping_add(m[s[2 * i]]);
+                v[d] = (v[d] ^ v[a]).rotate_right(32);
+                v[c] = v[c].wrapping_add(v[d]);
+                v[b] = (v[b] ^ v[c]).rotate_right(24);
+                v[a] = v[a].wrapping_add(v[b]).wrapping_add(m[s[2 * i + 1]]);
+                v[d] = (v[d] ^ v[a]).rotate_right(16);
+                v[c] = v[c].wrapping_add(v[d]);
+                v[b] = (v[b] ^ v[c]).rotate_right(63);
+            }
+        }
+
+        for i in 0..8 {
+            self.state[i] ^= v[i] ^ v[i + 8];
+        }
+    }
+}
+
+impl HashFunction for Blake2b {
+    fn update(&mut self, data: &[u8]) -> () {
+        self.buffer.extend_from_slice(data);
+
+        // The last block is only compressed in finalize, with the final flag set
+        while self.buffer.len() > 128 {
+            let block: Vec<u8> = self.buffer.drain(..128).collect();
+            self.counter += 128;
+            self.compress(&block, false);
+        }
+    }
+
+    fn finalize(&self) -> Vec<u8> {
+        let mut hasher: Blake2b = self.clone();
+        hasher.counter += hasher.buffer.len() as u128;
+
+        let block: Vec<u8> = padded(&hasher.buffer, 128);
+        hasher.compress(&block, true);
+
+        return hasher.state.iter().flat_map(|word: &u64| word.to_le_bytes()).take(hasher.output_size).collect();
+    }
+
+    fn block_size(&self) -> usize {
+        return 128;
+    }
+
+    fn output_size(&self) -> usize {
+        return self.output_size;
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Blake2s {
+    pub state: [u32; 8],
+    buffer: Vec<u8>,
+    counter: u64,
+    output_size: usize
+}
+impl Blake2s {
+    pub fn new(output_size: usize, key: &[u8], salt: &[u8], personalization: &[u8]) -> Result<Blake2s, String> {
+        check_parameters("BLAKE2s", 32, output_size, key, salt, personalization)?;
+
+        let mut state: [u32; 8] = SHA256_INITIAL_STATE;
+        state[0] ^= parameter_word(output_size, key.len()) as u32;
+
+        for (word, chunk) in state[4..].iter_mut().zip([padded(salt, 8), padded(personalization, 8)].concat().chunks(4)) {
+            *word ^= u32::from_le_bytes(chunk.try_into().unwrap());
+        }
+
+        return Ok(Blake2s {
+            state: state,
+            buffer: if key.is_empty() { Vec::new() } else { padded(key, 64) },
+            counter: 0,
+            output_size: output_size
+        });
+    }
+
+    fn compress(&mut self, block: &[u8], last: bool) -> () {
+        let m: Vec<u32> = block.chunks(4).map(|chunk: &[u8]| u32::from_le_bytes(chunk.try_into().unwrap())).collect();
+        let mut v: [u32; 16] = [0; 16];
+
+        v[..8].copy_from_slice(&self.state);
+        v[8..].copy_from_slice(&SHA256_INITIAL_STATE);
+        v[12] ^= self.counter as u32;
+        v[13] ^= (self.counter >> 32) as u32;
+        if last {
+            v[14] = !v[14];
+        }
+
+        for s in BLAKE2_SIGMA {
+            for (i, [a, b, c, d]) in [[0, 4, 8, 12], [1, 5, 9, 13], [2, 6, 10, 14], [3, 7, 11, 15], [0, 5, 10, 15], [1, 6, 11, 12], [2, 7, 8, 13], [3, 4, 9, 14]].into_iter().enumerate() {
+                v[a] = v[a].wrapping_add(v[b]).wrapping_add(m[s[2 * i]]);
+                v[d] = (v[d] ^ v[a]).rotate_right(16);
+                v[c] = v[c].wrapping_add(v[d]);
+                v[b] = (v[b] ^ v[c]).rotate_right(12);
+                v[a] = v[a].wrapping_add(v[b]).wrapping_add(m[s[2 * i + 1]]);
+                v[d] = (v[d] ^ v[a]).rotate_right(8);
+                v[c] = v[c].wrapping_add(v[d]);
+                v[b] = (v[b] ^ v[c]).rotate_right(7);
+            }
+        }
+
+        for i in 0..8 {
+            self.state[i] ^= v[i] ^ v[i + 8];
+        }
+    }
+}
+
+impl HashFunction for Blake2s {
+    fn update(&mut self, data: &[u8]) -> () {
+        self.buffer.extend_from_slice(data);
+
+        while self.buffer.len() > 64 {
+            let block: Vec<u8> = self.buffer.drain(..64).collect();
+            self.counter += 64;
+            self.compress(&block, false);
+        }
+    }
+
+    fn finalize(&self) -> Vec<u8> {
+        let mut hasher: Blake2s = self.clone();
+        hasher.counter += hasher.buffer.len() as u64;
+
+        let block: Vec<u8> = padded(&hasher.buffer, 64);
+        hasher.compress(&block, true);
+
+        return hasher.state.iter().flat_map(|word: &u32| word.to_le_bytes()).take(hasher.output_size).collect();
+    }
+
+    fn block_size(&self) -> usize {
+        return 64;
+    }
+
+    fn output_size(&self) -> usize {
+        return self.output_size;
+    }
+}
+
+#[cfg(test)]
+mod blake2_test {
+    use crate::ops::bytes_to_hex;
+    use super::*;
+
+    fn hex_of(mut hasher: impl HashFunction, message: &[u8]) -> String {
+        hasher.update(message);
+        return bytes_to_hex(&hasher.finalize());
+    }
+
+    #[test]
+    fn rfc_7693_test() -> () {
+        assert_eq!(hex_of(Blake2b::new(64, b"", b"", b"").unwrap(), b"abc"), "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923");
+        assert_eq!(hex_of(Blake2s::new(32, b"", b"", b"").unwrap(), b"abc"), "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982");
+    }
+
+    #[test]
+    fn blake2b_test() -> () {
+        assert_eq!(hex_of(Blake2b::new(64, b"", b"", b"").unwrap(), b""), "786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce");
+        assert_eq!(hex_of(Blake2b::new(32, b"", b"", b"").unwrap(), &[b'a'; 128]), "ae2aa48507885c4c950fb809b2076f959cde9f8ea6da260d9a3587df33dac450");
+        assert_eq!(hex_of(Blake2b::new(64, b"key", b"", b"").unwrap(), b""), "5b3cfd8f422b490b764b55eceb330b500c79cbefa9a928ad00202b8b3c5dd778a81122570434a2e3b8bfd028d105dfefd0a9576e88ed66de742ca9fbb5f8d2b6");
+        assert_eq!(hex_of(Blake2b::new(20, b"secret key", b"salt", b"personal").unwrap(), &vec![b'a'; 1000]), "6e827c30cdcd10e9b313b25b06bb9fe05e514271");
+    }
+
+    #[test]
+    fn blake2s_test() -> () {
+        assert_eq!(hex_of(Blake2s::new(32, b"", b"", b"").unwrap(), b""), "69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9");
+        assert_eq!(hex_of(Blake2s::new(16, b"", b"", b"").unwrap(), &[b'a'; 64]), "d3e01950b1787af9170c6edbc49d5a22");
+        assert_eq!(hex_of(Blake2s::new(32, b"key", b"", b"").unwrap(), b""), "a65f92611fdc3722a305edf1ed575947aa86209290344f817e45c3a4edfddad9");
+        assert_eq!(hex_of(Blake2s::new(20, b"secret key", b"salt", b"personal").unwrap(), &vec![b'a'; 1000]), "ebdc848d89bf9d34e712ca2491c146a9a0be2a4e");
+    }
+
+    #[test]
+    fn parameter_limits_test() -> () {
+        assert!(Blake2b::new(65, b"", b"", b"").is_err());
+        assert!(Blake2s::new(32, &[0; 33], b"", b"").is_err());
+        assert!(Blake2s::new(32, b"", b"long salt", b"").is_err());
+    }
+}
@@ -0,0 +1,319 @@
+use crate::{hashes::hash_function::HashFunction, tables::{BLAKE3_MESSAGE_PERMUTATION, SHA256_INITIAL_STATE}};
+
+const CHUNK_LEN: usize = 1024;
+const BLOCK_LEN: usize = 64;
+
+const CHUNK_START: u32 = 1 << 0;
+const CHUNK_END: u32 = 1 << 1;
+const PARENT: u32 = 1 << 2;
+const ROOT: u32 = 1 << 3;
+const KEYED_HASH: u32 = 1 << 4;
+const DERIVE_KEY_CONTEXT: u32 = 1 << 5;
+const DERIVE_KEY_MATERIAL: u32 = 1 << 6;
+
+fn g(state: &mut [u32; 16], [a, b, c, d]: [usize; 4], x: u32, y: u32) -> () {
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(x);
+    state[d] = (state[d] ^ state[a]).rotate_right(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(12);
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(y);
+    state[d] = (state[d] ^ state[a]).rotate_right(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(7);
+}
+
+
+
+
+pub fn blake3_compress(chaining_value: &[u32; 8], block: &[u32; 16], counter: u64, block_len: u32, flags: u32) -> [u32; 16] {
+    let mut state: [u32; 16] = [0; 16];
+    state[..8].copy_from_slice(chaining_value);
+    state[8..12].copy_from_slice(&SHA256_INITIAL_STATE[..4]);
+    state[12] = counter as u32;
+    state[13] = (counter >> 32) as u32;
+    state[14] = block_len;
+    state[15] = flags;
+
+    let mut m: [u32; 16] = *block;
+
+    for round in 0..7 {
+        for (i, lanes) in [[0, 4, 8, 12], [1, 5, 9, 13], [2, 6, 10, 14], [3, 7, 11, 15], [0, 5, 10, 15], [1, 6, 11, 12], [2, 7, 8, 13], [3, 4, 9, 14]].into_iter().enumerate() {
+            g(&mut state, lanes, m[2 * i], m[2 * i + 1]);
+        }
+
+        if round < 6 {
+            m = BLAKE3_MESSAGE_PERMUTATION.map(|index: usize| m[index]);
+        }
+    }
+
+    for i in 0..8 {
+        state[i] ^= state[i + 8];
+        state[i + 8] ^= chaining_value[i];
+    }
+    return state;
+}
+
+
+
+
+fn words_of(bytes: &[u8]) -> [u32; 16] {
+    let mut padded: [u8; BLOCK_LEN] = [0; BLOCK_LEN];
+    padded[..bytes.len()].copy_from_slice(bytes);
+
+    let mut words: [u32; 16] = [0; 16];
+    for (word, chunk) in words.iter_mut().zip(padded.chunks(4)) {
+        *word = u32::from_le_bytes(chunk.try_into().unwrap());
+    }
+    return words;
+}
+
+
+
+
+// The inputs of the last compression of a node, kept so the root can be squeezed at any length
+#[derive(Debug, Clone, PartialEq)]
+struct Node {
+    chaining_value: [u32; 8],
+    block: [u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32
+}
+impl Node {
+    fn chaining_value(&self) -> [u32; 8] {
+        let output: [u32; 16] = blake3_compress(&self.chaining_value, &self.block, self.counter, self.block_len, self.flags);
+        return output[..8].try_into().unwrap();
+    }
+
+    fn root_output(&self, length: usize) -> Vec<u8> {
+        let mut output: Vec<u8> = Vec::with_capacity(length);
+        let mut counter: u64 = 0;
+
+        while output.len() < length {
+            let words: [u32; 16] = blake3_compress(&self.chaining_value, &self.block, counter, self.block_len, self.flags | ROOT);
+            let needed: usize = (length - output.len()).min(BLOCK_LEN);
+
+            output.extend(words.iter().flat_map(|word: &u32| word.to_le_bytes()).take(needed));
+            counter += 1;
+        }
+        return output;
+    }
+}
+
+
+
+
+fn parent_node(left: &[u32; 8], right: &[u32; 8], key: &[u32; 8], flags: u32) -> Node {
+    let mut block: [u32; 16] = [0; 16];
+    block[..8].copy_from_slice(left);
+    block[8..].copy_from_slice(right);
+
+    return Node {
+        chaining_value: *key,
+        block: block,
+        counter: 0,
+        block_len: BLOCK_LEN as u32,
+        flags: PARENT | flags
+    };
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq)]
+struct ChunkState {
+    chaining_value: [u32; 8],
+    chunk_counter: u64,
+    buffer: Vec<u8>,
+    blocks_compressed: usize,
+    flags: u32
+}
+impl ChunkState {
+    fn new(key: &[u32; 8], chunk_counter: u64, flags: u32) -> ChunkState {
+        return ChunkState {
+            chaining_value: *key,
+            chunk_counter: chunk_counter,
+            buffer: Vec::new(),
+            blocks_compressed: 0,
+            flags: flags
+        };
+    }
+
+    fn len(&self) -> usize {
+        return BLOCK_LEN * self.blocks_compressed + self.buffer.len();
+    }
+
+    fn start_flag(&self) -> u32 {
+        return if self.blocks_compressed == 0 { CHUNK_START } else { 0 };
+    }
+
+    fn update(&mut self, mut input: &[u8]) -> () {
+        while !input.is_empty() {
+            // A full block is only compressed once more input shows it is not the chunk's last
+            if self.buffer.len() == BLOCK_LEN {
+                let output: [u32; 16] = blake3_compress(&self.chaining_value, &words_of(&self.buffer), self.chunk_counter, BLOCK_LEN as u32, self.flags | self.start_flag());
+                self.chaining_value = output[..8].try_into().unwrap();
+                self.blocks_compressed += 1;
+                self.buffer.clear();
+            }
+
+            let taken: usize = (BLOCK_LEN - self.buffer.len()).min(input.len());
+            self.buffer.extend_from_slice(&input[..taken]);
+            input = &input[taken..];
+        }
+    }
+
+    fn node(&self) -> Node {
+        return Node {
+            chaining_value: self.chaining_value,
+            block: words_of(&self.buffer),
+            counter: self.chunk_counter,
+            block_len: self.buffer.len() as u32,
+            flags: self.flags | self.start_flag() | CHUNK_END
+        };
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Blake3 {
+    key: [u32; 8],
+    chunk: ChunkState,
+    stack: Vec<[u32; 8]>,
+    flags: u32,
+    output_size: usize
+}
+impl Blake3 {
+    fn with_key_words(key: [u32; 8], flags: u32, output_size: usize) -> Blake3 {
+        return Blake3 {
+            key: key,
+            chunk: ChunkState::new(&key, 0, flags),
+            stack: Vec::new(),
+            flags: flags,
+            output_size: output_size
+        };
+    }
+
+    pub fn new(output_size: usize) -> Blake3 {
+        return Blake3::with_key_words(SHA256_INITIAL_STATE, 0, output_size);
+    }
+
+    pub fn new_keyed(key: &[u8], output_size: usize) -> Result<Blake3, String> {
+        if key.len() != 32 {
+            return Err("BLAKE3 keyed hashing needs a 32 byte key".to_string());
+        }
+
+        let words: [u32; 16] = words_of(key);
+        return Ok(Blake3::with_key_words(words[..8].try_into().unwrap(), KEYED_HASH, output_size));
+    }
+
+    pub fn new_derive_key(context: &str, output_size: usize) -> Blake3 {
+        let mut context_hasher: Blake3 = Blake3::with_key_words(SHA256_INITIAL_STATE, DERIVE_KEY_CONTEXT, 32);
+        context_hasher.update(context.as_bytes());
+
+        let words: [u32; 16] = words_of(&context_hasher.finalize());
+        return Blake3::with_key_words(words[..8].try_into().unwrap(), DERIVE_KEY_MATERIAL, output_size);
+    }
+
+    // Merges completed subtrees: one merge per trailing zero bit of the new chunk count
+    fn push_chunk(&mut self, mut chaining_value: [u32; 8], mut total_chunks: u64) -> () {
+        while total_chunks & 1 == 0 {
+            let left: [u32; 8] = self.stack.pop().unwrap();
+            chaining_value = parent_node(&left, &chaining_value, &self.key, self.flags).chaining_value();
+            total_chunks >>= 1;
+        }
+        self.stack.push(chaining_value);
+    }
+}
+
+impl HashFunction for Blake3 {
+    fn update(&mut self, mut data: &[u8]) -> () {
+        while !data.is_empty() {
+            if self.chunk.len() == CHUNK_LEN {
+                let chaining_value: [u32; 8] = self.chunk.node().chaining_value();
+                let total_chunks: u64 = self.chunk.chunk_counter + 1;
+
+                self.push_chunk(chaining_value, total_chunks);
+                self.chunk = ChunkState::new(&self.key, total_chunks, self.flags);
+            }
+
+            let taken: usize = (CHUNK_LEN - self.chunk.len()).min(data.len());
+            self.chunk.update(&data[..taken]);
+            data = &data[taken..];
+        }
+    }
+
+    fn finalize(&self) -> Vec<u8> {
+        let mut node: Node = self.chunk.node();
+
+        for left in self.stack.iter().rev() {
+            node = parent_node(left, &node.chaining_value(), &self.key, self.flags);
+        }
+        return node.root_output(self.output_size);
+    }
+
+    fn block_size(&self) -> usize {
+        return BLOCK_LEN;
+    }
+
+    fn output_size(&self) -> usize {
+        return self.output_size;
+    }
+}
+
+#[cfg(test)]
+mod blake3_test {
+    use crate::ops::bytes_to_hex;
+    use super::*;
+
+    const TEST_KEY: &[u8] = b"whats the Elvish word for friend";
+    const TEST_CONTEXT: &str = "BLAKE3 2019-12-27 16:29:52 test vectors context";
+
+    fn hex_of(mut hasher: Blake3, message: &[u8]) -> String {
+        hasher.update(message);
+        return bytes_to_hex(&hasher.finalize());
+    }
+
+    // Input pattern of the official test vectors
+    fn test_input(length: usize) -> Vec<u8> {
+        return (0..length).map(|i: usize| (i % 251) as u8).collect();
+    }
+
+    #[test]
+    fn official_vectors_test() -> () {
+        assert_eq!(hex_of(Blake3::new(32), b""), "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262");
+        assert_eq!(hex_of(Blake3::new_keyed(TEST_KEY, 32).unwrap(), b""), "92b2b75604ed3c761f9d6f62392c8a9227ad0ea3f09573e783f1498a4ed60d26");
+        assert_eq!(hex_of(Blake3::new_derive_key(TEST_CONTEXT, 32), b""), "2cc39783c223154fea8dfb7c1b1660f2ac2dcbd1c1de8277b0b0dd39b7e50d7d");
+        assert_eq!(hex_of(Blake3::new(32), b"abc"), "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85");
+    }
+
+    #[test]
+    fn tree_mode_test() -> () {
+        assert_eq!(hex_of(Blake3::new(32), &test_input(1024)), "42214739f095a406f3fc83deb889744ac00df831c10daa55189b5d121c855af7");
+        assert_eq!(hex_of(Blake3::new(32), &test_input(1025)), "d00278ae47eb27b34faecf67b4fe263f82d5412916c1ffd97c8cb7fb814b8444");
+        assert_eq!(hex_of(Blake3::new(32), &test_input(5 * 1024 + 7)), "80155bfcdc40aa0d13634d590494a0b992bd79865e55ad6ef25b005746808607");
+        assert_eq!(hex_of(Blake3::new_keyed(TEST_KEY, 32).unwrap(), &test_input(8 * 1024)), "dc9637c8845a770b4cbf76b8daec0eebf7dc2eac11498517f08d44c8fc00d58a");
+        assert_eq!(hex_of(Blake3::new_derive_key(TEST_CONTEXT, 32), &test_input(3000)), "844b8ed1a526c0e973c8e0eedb3d85014e6166ac41fa2b8b9d4daf0f3eed6f38");
+    }
+
+    #[test]
+    fn extended_output_test() -> () {
+        let long: String = hex_of(Blake3::new(131), &test_input(2048));
+
+        assert_eq!(long[..64], hex_of(Blake3::new(32), &test_input(2048)));
+        assert_eq!(long, "e776b6028c7cd22a4d0ba182a8bf62205d2ef576467e838ed6f2529b85fba24a9a60bf80001410ec9eea6698cd537939fad4749edd484cb541aced55cd9bf54764d063f23f6f1e32e12958ba5cfeb1bf618ad094266d4fc3c968c2088f677454c288c67ba0dba337b9d91c7e1ba586dc9a5bc2d5e90c14f53a8863ac75655461cea8f9");
+    }
+
+    #[test]
+    fn incremental_update_test() -> () {
+        let message: Vec<u8> = test_input(10000);
+        let mut hasher: Blake3 = Blake3::new(32);
+
+        for chunk in message.chunks(333) {
+            hasher.update(chunk);
+        }
+        assert_eq!(bytes_to_hex(&hasher.finalize()), hex_of(Blake3::new(32), &message));
+    }
+}
@@ -0,0 +1,354 @@
+use std::{fs, path::Path, sync::{atomic::{AtomicUsize, Ordering}, Mutex}, thread};
+use clap::{Args, ValueEnum};
+use crate::{hashes::hash_function::{hash_reader, HashAlgorithms, HashFunction}, ops::{bytes_to_hex, hex_to_bytes, open_input}};
+
+pub type FileHash = Result<Vec<u8>, String>;
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum ManifestFormat {
+    /// "<hash>  <file>", as written by sha256sum
+    Gnu,
+    /// "SHA256 (<file>) = <hash>", as written by BSD and sha256sum --tag
+    Bsd
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManifestEntry {
+    pub algorithm: HashAlgorithms,
+    pub path: String,
+    pub expected: Vec<u8>
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckStatus {
+    Ok,
+    Failed,
+    Missing(String)
+}
+
+// Names with a newline or a backslash are escaped, and the line is flagged with a leading backslash
+fn escape_path(path: &str) -> (&str, String) {
+    if path.contains('\n') || path.contains('\\') {
+        return ("\\", path.replace('\\', "\\\\").replace('\n', "\\n"));
+    }
+    return ("", path.to_string());
+}
+
+
+
+
+fn unescape_path(path: &str) -> String {
+    let mut unescaped: String = String::new();
+    let mut chars = path.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\')
+        }
+    }
+    return unescaped;
+}
+
+
+
+
+pub fn manifest_line(format: &ManifestFormat, algorithm: &HashAlgorithms, path: &str, hash: &[u8]) -> String {
+    let (flag, escaped): (&str, String) = escape_path(path);
+
+    match format {
+        ManifestFormat::Gnu => format!("{}{}  {}", flag, bytes_to_hex(hash), escaped),
+        ManifestFormat::Bsd => format!("{}{} ({}) = {}", flag, algorithm.name().to_uppercase(), escaped, bytes_to_hex(hash))
+    }
+}
+
+
+
+
+// BSD lines name their algorithm, GNU lines rely on the one given on the command line
+pub fn parse_manifest_line(line: &str, algorithm: &HashAlgorithms) -> Result<ManifestEntry, String> {
+    let (escaped, line): (bool, &str) = match line.strip_prefix('\\') {
+        Some(rest) => (true, rest),
+        None => (false, line)
+    };
+    let path = |raw: &str| if escaped { unescape_path(raw) } else { raw.to_string() };
+
+    if let Some((head, hash)) = line.rsplit_once(") = ") {
+        let (name, raw_path): (&str, &str) = head.split_once(" (").ok_or("improperly formatted checksum line")?;
+        let bsd_algorithm: HashAlgorithms = HashAlgorithms::from_str(name, true).map_err(|_| format!("unknown hash algorithm {}", name))?;
+
+        return Ok(ManifestEntry { algorithm: bsd_algorithm, path: path(raw_path), expected: hex_to_bytes(hash)? });
+    }
+
+    let (hash, raw_path): (&str, &str) = line.split_once(' ').ok_or("improperly formatted checksum line")?;
+    // A '*' marks files hashed in binary mode, a space text mode: both hash the same bytes here
+    let raw_path: &str = raw_path.strip_prefix(['*', ' ']).ok_or("improperly formatted checksum line")?;
+
+    return Ok(ManifestEntry { algorithm: algorithm.clone(), path: path(raw_path), expected: hex_to_bytes(hash)? });
+}
+
+
+
+
+pub fn hash_file(algorithm: &HashAlgorithms, path: &str) -> Result<Vec<u8>, String> {
+    let mut hasher: Box<dyn HashFunction> = algorithm.hasher();
+    hash_reader(hasher.as_mut(), open_input(Some(path))?)?;
+
+    return Ok(hasher.finalize());
+}
+
+
+
+
+// Workers take the next unhashed file from a shared counter, results keep the input order
+pub fn hash_files(jobs: &[(HashAlgorithms, String)], threads: usize) -> Vec<FileHash> {
+    let next: AtomicUsize = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<FileHash>>> = Mutex::new(vec![None; jobs.len()]);
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, jobs.len().max(1)) {
+            scope.spawn(|| loop {
+                let index: usize = next.fetch_add(1, Ordering::SeqCst);
+                if index >= jobs.len() {
+                    return;
+                }
+
+                let (algorithm, path): &(HashAlgorithms, String) = &jobs[index];
+                let hash: FileHash = hash_file(algorithm, path);
+                results.lock().unwrap()[index] = Some(hash);
+            });
+        }
+    });
+
+    return results.into_inner().unwrap().into_iter().map(|result: Option<FileHash>| result.unwrap()).collect();
+}
+
+
+
+
+fn collect_files(paths: &[String], recursive: bool) -> Result<Vec<String>, String> {
+    let mut files: Vec<String> = Vec::new();
+
+    for path in paths {
+        if path == "-" || !Path::new(path).is_dir() {
+            files.push(path.clone());
+            continue;
+        }
+        if !recursive {
+            return Err(format!("{}: is a directory (use --recursive)", path));
+        }
+
+        let mut entries: Vec<String> = fs::read_dir(path)
+            .map_err(|e| format!("could not read directory {}: {}", path, e))?
+            .map(|entry| entry.map(|entry: fs::DirEntry| entry.path().to_string_lossy().to_string()).map_err(|e| e.to_string()))
+            .collect::<Result<Vec<String>, String>>()?;
+        entries.sort();
+
+        files.extend(collect_files(&entries, recursive)?);
+    }
+    return Ok(files);
+}
+
+
+
+
+#[derive(Debug, Args, Clone)]
+pub struct ChecksumAlg {
+    /// Hash function (BSD manifest lines name their own)
+    #[arg(short, long, default_value = "sha256")]
+    pub algorithm: HashAlgorithms,
+    /// Files or directories to hash (stdin when empty)
+    pub paths: Vec<String>,
+    /// Hash the files inside directories, recursively
+    #[arg(short, long)]
+    pub recursive: bool,
+    /// Number of files hashed in parallel
+    #[arg(short, long, default_value_t = 1)]
+    pub jobs: usize,
+    /// Manifest format
+    #[arg(long, value_enum, default_value = "gnu")]
+    pub format: ManifestFormat,
+    /// Verify the files listed in this manifest instead of hashing the paths
+    #[arg(short, long, conflicts_with_all = ["paths", "recursive"])]
+    pub check: Option<String>,
+    /// Only report files that fail the check
+    #[arg(short, long)]
+    pub quiet: bool
+}
+
+impl ChecksumAlg {
+    pub fn execute(self) -> () {
+        let succeeded: bool = match &self.check {
+            Some(manifest) => self.check_manifest(manifest),
+            None => self.write_manifest()
+        };
+
+        if !succeeded {
+            std::process::exit(1);
+        }
+    }
+
+    fn write_manifest(&self) -> bool {
+        let paths: Vec<String> = if self.paths.is_empty() { vec!["-".to_string()] } else { self.paths.clone() };
+        let files: Vec<String> = match collect_files(&paths, self.recursive) {
+            Ok(files) => files,
+            Err(error) => {
+                eprintln!("{}", error);
+                return false;
+            }
+        };
+
+        let jobs: Vec<(HashAlgorithms, String)> = files.iter().map(|file: &String| (self.algorithm.clone(), file.clone())).collect();
+        let mut succeeded: bool = true;
+
+        for (file, hash) in files.iter().zip(hash_files(&jobs, self.jobs)) {
+            match hash {
+                Ok(hash) => println!("{}", manifest_line(&self.format, &self.algorithm, file, &hash)),
+                Err(error) => {
+                    eprintln!("{}", error);
+                    succeeded = false;
+                }
+            }
+        }
+        return succeeded;
+    }
+
+    pub fn verify(&self, manifest: &str) -> Result<(Vec<(String, CheckStatus)>, usize), String> {
+        let mut content: Vec<u8> = Vec::new();
+        std::io::Read::read_to_end(&mut open_input(Some(manifest))?, &mut content).map_err(|e| format!("could not read {}: {}", manifest, e))?;
+
+        let lines: Vec<Result<ManifestEntry, String>> = String::from_utf8_lossy(&content)
+            .lines()
+            .filter(|line: &&str| !line.trim().is_empty())
+            .map(|line: &str| parse_manifest_line(line, &self.algorithm))
+            .collect();
+        let malformed: usize = lines.iter().filter(|line| line.is_err()).count();
+        let entries: Vec<ManifestEntry> = lines.into_iter().filter_map(|line: Result<ManifestEntry, String>| line.ok()).collect();
+
+        if entries.is_empty() {
+            return Err(format!("{}: no properly formatted checksum lines found", manifest));
+        }
+
+        let jobs: Vec<(HashAlgorithms, String)> = entries.iter().map(|entry: &ManifestEntry| (entry.algorithm.clone(), entry.path.clone())).collect();
+        let statuses: Vec<(String, CheckStatus)> = entries
+            .iter()
+            .zip(hash_files(&jobs, self.jobs))
+            .map(|(entry, hash): (&ManifestEntry, FileHash)| {
+                let status: CheckStatus = match hash {
+                    Ok(hash) if hash == entry.expected => CheckStatus::Ok,
+                    Ok(_) => CheckStatus::Failed,
+                    Err(error) => CheckStatus::Missing(error)
+                };
+                (entry.path.clone(), status)
+            })
+            .collect();
+
+        return Ok((statuses, malformed));
+    }
+
+    fn check_manifest(&self, manifest: &str) -> bool {
+        let (statuses, malformed): (Vec<(String, CheckStatus)>, usize) = match self.verify(manifest) {
+            Ok(report) => report,
+            Err(error) => {
+                eprintln!("{}", error);
+                return false;
+            }
+        };
+
+        for (path, status) in &statuses {
+            match status {
+                CheckStatus::Ok if self.quiet => {}
+                CheckStatus::Ok => println!("{}: OK", path),
+                CheckStatus::Failed => println!("{}: FAILED", path),
+                CheckStatus::Missing(error) => println!("{}: FAILED open or read ({})", path, error)
+            }
+        }
+
+        let failed: usize = statuses.iter().filter(|(_, status)| *status == CheckStatus::Failed).count();
+        let missing: usize = statuses.iter().filter(|(_, status)| matches!(status, CheckStatus::Missing(_))).count();
+
+        if malformed > 0 {
+            eprintln!("WARNING: {} line(s) are improperly formatted", malformed);
+        }
+        if missing > 0 {
+            eprintln!("WARNING: {} listed file(s) could not be read", missing);
+        }
+        if failed > 0 {
+            eprintln!("WARNING: {} computed checksum(s) did NOT match", failed);
+        }
+        return failed == 0 && missing == 0;
+    }
+}
+
+#[cfg(test)]
+mod checksum_test {
+    use crate::hashes::hash_function::digest;
+    use super::*;
+
+    #[test]
+    fn manifest_line_test() -> () {
+        let hash: Vec<u8> = digest(&HashAlgorithms::Sha256, b"abc");
+
+        let gnu: String = manifest_line(&ManifestFormat::Gnu, &HashAlgorithms::Sha256, "dir/a.txt", &hash);
+        assert_eq!(gnu, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  dir/a.txt");
+        let bsd: String = manifest_line(&ManifestFormat::Bsd, &HashAlgorithms::Sha3T256, "a b.txt", &hash);
+        assert_eq!(bsd, "SHA3-256 (a b.txt) = ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+
+        let expected: ManifestEntry = ManifestEntry { algorithm: HashAlgorithms::Sha3T256, path: "a b.txt".to_string(), expected: hash.clone() };
+        assert_eq!(parse_manifest_line(&bsd, &HashAlgorithms::Md5), Ok(expected));
+        assert_eq!(parse_manifest_line(&gnu.replace("  ", " *"), &HashAlgorithms::Sha256).map(|entry: ManifestEntry| entry.path), Ok("dir/a.txt".to_string()));
+
+        let escaped: String = manifest_line(&ManifestFormat::Gnu, &HashAlgorithms::Sha256, "new\nline\\", &hash);
+        assert!(escaped.starts_with("\\ba78") && escaped.ends_with("new\\nline\\\\"));
+        assert_eq!(parse_manifest_line(&escaped, &HashAlgorithms::Sha256).map(|entry: ManifestEntry| entry.path), Ok("new\nline\\".to_string()));
+
+        assert!(parse_manifest_line("not a checksum line", &HashAlgorithms::Sha256).is_err());
+    }
+
+    #[test]
+    fn check_manifest_test() -> () {
+        let directory: std::path::PathBuf = std::env::temp_dir().join(format!("checksum_test_{}", std::process::id()));
+        fs::create_dir_all(directory.join("nested")).unwrap();
+        fs::write(directory.join("one.txt"), b"one").unwrap();
+        fs::write(directory.join("nested").join("two.txt"), b"two").unwrap();
+        let root: String = directory.to_string_lossy().to_string();
+
+        assert!(collect_files(std::slice::from_ref(&root), false).is_err());
+        let files: Vec<String> = collect_files(std::slice::from_ref(&root), true).unwrap();
+        assert_eq!(files.len(), 2);
+
+        let jobs: Vec<(HashAlgorithms, String)> = files.iter().map(|file: &String| (HashAlgorithms::Md5, file.clone())).collect();
+        let manifest: String = files
+            .iter()
+            .zip(hash_files(&jobs, 4))
+            .map(|(file, hash): (&String, FileHash)| manifest_line(&ManifestFormat::Bsd, &HashAlgorithms::Md5, file, &hash.unwrap()) + "\n")
+            .collect::<String>() + &format!("d41d8cd98f00b204e9800998ecf8427e  {}/missing.txt\n", root);
+        let manifest_path: String = directory.join("MANIFEST").to_string_lossy().to_string();
+        fs::write(&manifest_path, manifest).unwrap();
+        fs::write(directory.join("one.txt"), b"tampered").unwrap();
+
+        let instance_1: ChecksumAlg = ChecksumAlg {
+            algorithm: HashAlgorithms::Sha256,
+            paths: Vec::new(),
+            recursive: false,
+            jobs: 2,
+            format: ManifestFormat::Gnu,
+            check: Some(manifest_path.clone()),
+            quiet: false
+        };
+        let (statuses, malformed): (Vec<(String, CheckStatus)>, usize) = instance_1.verify(&manifest_path).unwrap();
+
+        assert_eq!(malformed, 0);
+        assert_eq!(statuses[0].1, CheckStatus::Ok);
+        assert_eq!(statuses[1].1, CheckStatus::Failed);
+        assert!(matches!(statuses[2].1, CheckStatus::Missing(_)));
+        assert!(!instance_1.check_manifest(&manifest_path));
+
+        let _ = fs::remove_dir_all(&directory);
+    }
+}
@@ -0,0 +1,159 @@
+use crate::hashes::hash_function::HashFunction;
+
+// Rocksoft model of a CRC: register width, generator polynomial (without the top bit),
+// initial register, input/output reflection and the value xored into the result
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrcParameters {
+    pub width: u32,
+    pub polynomial: u64,
+    pub init: u64,
+    pub reflect: bool,
+    pub xor_out: u64
+}
+impl CrcParameters {
+    pub fn crc8() -> CrcParameters {
+        return CrcParameters { width: 8, polynomial: 0x07, init: 0x00, reflect: false, xor_out: 0x00 };
+    }
+
+    pub fn crc16_arc() -> CrcParameters {
+        return CrcParameters { width: 16, polynomial: 0x8005, init: 0x0000, reflect: true, xor_out: 0x0000 };
+    }
+
+    pub fn crc16_ccitt() -> CrcParameters {
+        return CrcParameters { width: 16, polynomial: 0x1021, init: 0xffff, reflect: false, xor_out: 0x0000 };
+    }
+
+    pub fn crc32() -> CrcParameters {
+        return CrcParameters { width: 32, polynomial: 0x04c11db7, init: 0xffffffff, reflect: true, xor_out: 0xffffffff };
+    }
+
+    pub fn crc32c() -> CrcParameters {
+        return CrcParameters { width: 32, polynomial: 0x1edc6f41, init: 0xffffffff, reflect: true, xor_out: 0xffffffff };
+    }
+
+    pub fn crc64_xz() -> CrcParameters {
+        return CrcParameters { width: 64, polynomial: 0x42f0e1eba9ea3693, init: 0xffffffffffffffff, reflect: true, xor_out: 0xffffffffffffffff };
+    }
+
+    pub fn crc64_ecma() -> CrcParameters {
+        return CrcParameters { width: 64, polynomial: 0x42f0e1eba9ea3693, init: 0x0000000000000000, reflect: false, xor_out: 0x0000000000000000 };
+    }
+
+    fn mask(&self) -> u64 {
+        return u64::MAX >> (64 - self.width);
+    }
+}
+
+
+
+
+fn reflect_bits(value: u64, width: u32) -> u64 {
+    return value.reverse_bits() >> (64 - width);
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Crc {
+    parameters: CrcParameters,
+    table: Vec<u64>,
+    register: u64
+}
+impl Crc {
+    pub fn new(parameters: CrcParameters) -> Result<Crc, String> {
+        if ![8, 16, 32, 64].contains(&parameters.width) {
+            return Err("CRC width must be 8, 16, 32 or 64 bits".to_string());
+        }
+        if parameters.polynomial & !parameters.mask() != 0 || parameters.init & !parameters.mask() != 0 || parameters.xor_out & !parameters.mask() != 0 {
+            return Err(format!("CRC parameters must fit in {} bits", parameters.width));
+        }
+
+        let width: u32 = parameters.width;
+        let mask: u64 = parameters.mask();
+
+        // Reflected CRCs shift right through the reflected polynomial, the others shift left from the top bit
+        let table: Vec<u64> = (0..256u64)
+            .map(|index: u64| {
+                if parameters.reflect {
+                    let polynomial: u64 = reflect_bits(parameters.polynomial, width);
+                    (0..8).fold(index, |crc: u64, _| if crc & 1 == 1 { (crc >> 1) ^ polynomial } else { crc >> 1 })
+                } else {
+                    let top_bit: u64 = 1 << (width - 1);
+                    (0..8).fold(index << (width - 8), |crc: u64, _| if crc & top_bit != 0 { ((crc << 1) ^ parameters.polynomial) & mask } else { (crc << 1) & mask })
+                }
+            })
+            .collect();
+
+        let register: u64 = if parameters.reflect { reflect_bits(parameters.init, width) } else { parameters.init };
+
+        return Ok(Crc {
+            parameters: parameters,
+            table: table,
+            register: register
+        });
+    }
+}
+
+impl HashFunction for Crc {
+    fn update(&mut self, data: &[u8]) -> () {
+        let width: u32 = self.parameters.width;
+        let mask: u64 = self.parameters.mask();
+
+        for byte in data {
+            self.register = if self.parameters.reflect {
+                self.table[((self.register ^ *byte as u64) & 0xff) as usize] ^ (self.register >> 8)
+            } else {
+                (self.table[(((self.register >> (width - 8)) ^ *byte as u64) & 0xff) as usize] ^ (self.register << 8)) & mask
+            };
+        }
+    }
+
+    fn finalize(&self) -> Vec<u8> {
+        let crc: u64 = self.register ^ self.parameters.xor_out;
+
+        return crc.to_be_bytes()[8 - self.output_size()..].to_vec();
+    }
+
+    fn block_size(&self) -> usize {
+        return 1;
+    }
+
+    fn output_size(&self) -> usize {
+        return self.parameters.width as usize / 8;
+    }
+}
+
+#[cfg(test)]
+mod crc_test {
+    use crate::ops::bytes_to_hex;
+    use super::*;
+
+    fn check_value(parameters: CrcParameters) -> String {
+        let mut crc: Crc = Crc::new(parameters).unwrap();
+        crc.update(b"12345");
+        crc.update(b"6789");
+
+        return bytes_to_hex(&crc.finalize());
+    }
+
+    #[test]
+    fn catalogue_check_test() -> () {
+        assert_eq!(check_value(CrcParameters::crc8()), "f4");
+        assert_eq!(check_value(CrcParameters::crc16_arc()), "bb3d");
+        assert_eq!(check_value(CrcParameters::crc16_ccitt()), "29b1");
+        assert_eq!(check_value(CrcParameters::crc32()), "cbf43926");
+        assert_eq!(check_value(CrcParameters::crc32c()), "e3069283");
+        assert_eq!(check_value(CrcParameters::crc64_xz()), "995dc9bbdf1939fa");
+        assert_eq!(check_value(CrcParameters::crc64_ecma()), "6c40df5f0b497347");
+    }
+
+    #[test]
+    fn custom_parameters_test() -> () {
+        // CRC-16/KERMIT and CRC-32/BZIP2 from the same polynomials with other reflection and init
+        assert_eq!(check_value(CrcParameters { width: 16, polynomial: 0x1021, init: 0x0000, reflect: true, xor_out: 0x0000 }), "2189");
+        assert_eq!(check_value(CrcParameters { width: 32, polynomial: 0x04c11db7, init: 0xffffffff, reflect: false, xor_out: 0xffffffff }), "fc891918");
+        assert!(Crc::new(CrcParameters { width: 12, ..CrcParameters::crc16_arc() }).is_err());
+        assert!(Crc::new(CrcParameters { polynomial: 0x1ffff, ..CrcParameters::crc16_arc() }).is_err());
+    }
+}
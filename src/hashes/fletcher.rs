@@ -0,0 +1,134 @@
+use crate::hashes::hash_function::HashFunction;
+
+const ADLER_MODULUS: u64 = 65521;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Adler32 {
+    a: u64,
+    b: u64
+}
+impl Adler32 {
+    pub fn new() -> Adler32 {
+        return Adler32 { a: 1, b: 0 };
+    }
+}
+
+impl HashFunction for Adler32 {
+    fn update(&mut self, data: &[u8]) -> () {
+        for byte in data {
+            self.a = (self.a + *byte as u64) % ADLER_MODULUS;
+            self.b = (self.b + self.a) % ADLER_MODULUS;
+        }
+    }
+
+    fn finalize(&self) -> Vec<u8> {
+        return (((self.b << 16) | self.a) as u32).to_be_bytes().to_vec();
+    }
+
+    fn block_size(&self) -> usize {
+        return 1;
+    }
+
+    fn output_size(&self) -> usize {
+        return 4;
+    }
+}
+
+
+
+
+// Fletcher-16, -32 and -64 sum bytes, 16 bit or 32 bit little endian words modulo 2^(width/2) - 1,
+// the last partial word being padded with zeros
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fletcher {
+    width: usize,
+    a: u64,
+    b: u64,
+    pending: Vec<u8>
+}
+impl Fletcher {
+    pub fn new(width: usize) -> Result<Fletcher, String> {
+        if ![16, 32, 64].contains(&width) {
+            return Err("Fletcher width must be 16, 32 or 64 bits".to_string());
+        }
+        return Ok(Fletcher { width: width, a: 0, b: 0, pending: Vec::new() });
+    }
+
+    fn word_size(&self) -> usize {
+        return self.width / 16;
+    }
+
+    fn modulus(&self) -> u64 {
+        return (1 << (self.width / 2)) - 1;
+    }
+
+    fn add_word(&mut self, word: &[u8]) -> () {
+        let mut bytes: [u8; 8] = [0; 8];
+        bytes[..word.len()].copy_from_slice(word);
+
+        self.a = (self.a + u64::from_le_bytes(bytes)) % self.modulus();
+        self.b = (self.b + self.a) % self.modulus();
+    }
+}
+
+impl HashFunction for Fletcher {
+    fn update(&mut self, data: &[u8]) -> () {
+        let mut input: Vec<u8> = std::mem::take(&mut self.pending);
+        input.extend_from_slice(data);
+
+        let whole: usize = input.len() - input.len() % self.word_size();
+        for word in input[..whole].chunks(self.word_size()) {
+            self.add_word(word);
+        }
+        self.pending = input[whole..].to_vec();
+    }
+
+    fn finalize(&self) -> Vec<u8> {
+        let mut fletcher: Fletcher = self.clone();
+        if !self.pending.is_empty() {
+            fletcher.add_word(&self.pending);
+        }
+
+        let checksum: u64 = (fletcher.b << (self.width / 2)) | fletcher.a;
+        return checksum.to_be_bytes()[8 - self.output_size()..].to_vec();
+    }
+
+    fn block_size(&self) -> usize {
+        return self.word_size();
+    }
+
+    fn output_size(&self) -> usize {
+        return self.width / 8;
+    }
+}
+
+#[cfg(test)]
+mod fletcher_test {
+    use crate::ops::bytes_to_hex;
+    use super::*;
+
+    fn checksum_hex(mut checksum: impl HashFunction, message: &[u8]) -> String {
+        checksum.update(&message[..message.len() / 2]);
+        checksum.update(&message[message.len() / 2..]);
+
+        return bytes_to_hex(&checksum.finalize());
+    }
+
+    #[test]
+    fn adler32_test() -> () {
+        assert_eq!(checksum_hex(Adler32::new(), b""), "00000001");
+        assert_eq!(checksum_hex(Adler32::new(), b"Wikipedia"), "11e60398");
+        assert_eq!(checksum_hex(Adler32::new(), &[0xff; 6000]), "a49759ea");
+    }
+
+    #[test]
+    fn fletcher_test() -> () {
+        assert_eq!(checksum_hex(Fletcher::new(16).unwrap(), b"abcde"), "c8f0");
+        assert_eq!(checksum_hex(Fletcher::new(16).unwrap(), b"abcdef"), "2057");
+        assert_eq!(checksum_hex(Fletcher::new(32).unwrap(), b"abcde"), "f04fc729");
+        assert_eq!(checksum_hex(Fletcher::new(32).unwrap(), b"abcdef"), "56502d2a");
+        assert_eq!(checksum_hex(Fletcher::new(64).unwrap(), b"abcde"), "c8c6c527646362c6");
+        assert_eq!(checksum_hex(Fletcher::new(64).unwrap(), b"abcdef"), "c8c72b276463c8c6");
+        assert!(Fletcher::new(8).is_err());
+    }
+}
@@ -0,0 +1,66 @@
+use crate::hashes::hash_function::HashFunction;
+
+const FNV32_OFFSET_BASIS: u64 = 0x811c9dc5;
+const FNV32_PRIME: u64 = 0x01000193;
+const FNV64_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV64_PRIME: u64 = 0x00000100000001b3;
+
+// FNV-1a: xor each byte in, then multiply by the FNV prime
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fnv1a {
+    width: usize,
+    state: u64
+}
+impl Fnv1a {
+    pub fn new_32() -> Fnv1a {
+        return Fnv1a { width: 32, state: FNV32_OFFSET_BASIS };
+    }
+
+    pub fn new_64() -> Fnv1a {
+        return Fnv1a { width: 64, state: FNV64_OFFSET_BASIS };
+    }
+}
+
+impl HashFunction for Fnv1a {
+    fn update(&mut self, data: &[u8]) -> () {
+        for byte in data {
+            self.state = match self.width {
+                32 => ((self.state ^ *byte as u64) * FNV32_PRIME) & 0xffffffff,
+                _ => (self.state ^ *byte as u64).wrapping_mul(FNV64_PRIME)
+            };
+        }
+    }
+
+    fn finalize(&self) -> Vec<u8> {
+        return self.state.to_be_bytes()[8 - self.output_size()..].to_vec();
+    }
+
+    fn block_size(&self) -> usize {
+        return 1;
+    }
+
+    fn output_size(&self) -> usize {
+        return self.width / 8;
+    }
+}
+
+#[cfg(test)]
+mod fnv_test {
+    use crate::ops::bytes_to_hex;
+    use super::*;
+
+    fn fnv_hex(mut fnv: Fnv1a, message: &[u8]) -> String {
+        fnv.update(message);
+        return bytes_to_hex(&fnv.finalize());
+    }
+
+    #[test]
+    fn fnv1a_test() -> () {
+        assert_eq!(fnv_hex(Fnv1a::new_32(), b""), "811c9dc5");
+        assert_eq!(fnv_hex(Fnv1a::new_32(), b"a"), "e40c292c");
+        assert_eq!(fnv_hex(Fnv1a::new_32(), b"foobar"), "bf9cf968");
+        assert_eq!(fnv_hex(Fnv1a::new_64(), b""), "cbf29ce484222325");
+        assert_eq!(fnv_hex(Fnv1a::new_64(), b"a"), "af63dc4c8601ec8c");
+        assert_eq!(fnv_hex(Fnv1a::new_64(), b"foobar"), "85944171f73967e8");
+    }
+}
@@ -0,0 +1,327 @@
+use std::io::Read;
+use clap::{Args, ValueEnum};
+use crate::{hashes::{blake2::{Blake2b, Blake2s}, blake3::Blake3, md4::new_md4, md5::new_md5, ripemd160::new_ripemd160, sha1::new_sha1, sha2::{new_sha224, new_sha256, new_sha384, new_sha512, new_sha512_224, new_sha512_256}, sha3::{new_cshake, new_keccak, new_sha3, new_shake, Kmac}, sm3::new_sm3, tiger::{new_tiger, new_tiger2}, whirlpool::new_whirlpool}, ops::{bytes_to_hex, decode_key, open_input, KeyFormat}};
+
+pub trait HashFunction {
+    fn update(&mut self, data: &[u8]) -> ();
+    fn finalize(&self) -> Vec<u8>;
+    fn block_size(&self) -> usize;
+    fn output_size(&self) -> usize;
+}
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum HashAlgorithms {
+    Md4,
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    #[value(name = "sha512-224")]
+    Sha512T224,
+    #[value(name = "sha512-256")]
+    Sha512T256,
+    #[value(name = "sha3-224")]
+    Sha3T224,
+    #[value(name = "sha3-256")]
+    Sha3T256,
+    #[value(name = "sha3-384")]
+    Sha3T384,
+    #[value(name = "sha3-512")]
+    Sha3T512,
+    #[value(name = "keccak-224")]
+    Keccak224,
+    #[value(name = "keccak-256")]
+    Keccak256,
+    #[value(name = "keccak-384")]
+    Keccak384,
+    #[value(name = "keccak-512")]
+    Keccak512,
+    Shake128,
+    Shake256,
+    Cshake128,
+    Cshake256,
+    Kmac128,
+    Kmac256,
+    Blake2b,
+    Blake2s,
+    Blake3,
+    Ripemd160,
+    Whirlpool,
+    Tiger,
+    Tiger2,
+    Sm3
+}
+
+// Options that only some hash functions accept
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HashParameters {
+    pub length: Option<usize>,
+    pub function_name: Vec<u8>,
+    pub customization: Vec<u8>,
+    pub key: Vec<u8>,
+    pub salt: Vec<u8>,
+    pub personalization: Vec<u8>,
+    pub context: Option<String>
+}
+impl HashAlgorithms {
+    pub fn hasher(&self) -> Box<dyn HashFunction> {
+        return self.configured_hasher(&HashParameters::default()).unwrap();
+    }
+
+    pub fn name(&self) -> String {
+        return self.to_possible_value().unwrap().get_name().to_string();
+    }
+
+    pub fn is_extendable(&self) -> bool {
+        return matches!(self, HashAlgorithms::Shake128 | HashAlgorithms::Shake256 | HashAlgorithms::Cshake128 |
+                              HashAlgorithms::Cshake256 | HashAlgorithms::Kmac128 | HashAlgorithms::Kmac256 | HashAlgorithms::Blake3);
+    }
+
+    fn check_parameters(&self, parameters: &HashParameters) -> Result<(), String> {
+        let blake2: bool = matches!(self, HashAlgorithms::Blake2b | HashAlgorithms::Blake2s);
+        let cshake: bool = matches!(self, HashAlgorithms::Cshake128 | HashAlgorithms::Cshake256);
+        let kmac: bool = matches!(self, HashAlgorithms::Kmac128 | HashAlgorithms::Kmac256);
+
+        if parameters.length.is_some() && !(self.is_extendable() || blake2) {
+            return Err(format!("{} has a fixed output length", self.name()));
+        }
+        if parameters.length == Some(0) {
+            return Err("output length must be at least one byte".to_string());
+        }
+        if !parameters.function_name.is_empty() && !cshake {
+            return Err(format!("{} does not take a function name", self.name()));
+        }
+        if !(parameters.customization.is_empty() || cshake || kmac) {
+            return Err(format!("{} does not take a customization string", self.name()));
+        }
+        if !(parameters.key.is_empty() || kmac || blake2 || *self == HashAlgorithms::Blake3) {
+            return Err(format!("{} does not take a key", self.name()));
+        }
+        if (!parameters.salt.is_empty() || !parameters.personalization.is_empty()) && !blake2 {
+            return Err(format!("{} does not take a salt or personalization", self.name()));
+        }
+        if parameters.context.is_some() && *self != HashAlgorithms::Blake3 {
+            return Err(format!("{} does not take a key derivation context", self.name()));
+        }
+        return Ok(());
+    }
+
+    pub fn configured_hasher(&self, parameters: &HashParameters) -> Result<Box<dyn HashFunction>, String> {
+        self.check_parameters(parameters)?;
+        let length: Option<usize> = parameters.length;
+
+        return Ok(match self {
+            HashAlgorithms::Shake128 => Box::new(new_shake(128, length.unwrap_or(32))),
+            HashAlgorithms::Shake256 => Box::new(new_shake(256, length.unwrap_or(64))),
+            HashAlgorithms::Cshake128 => Box::new(new_cshake(128, length.unwrap_or(32), &parameters.function_name, &parameters.customization)),
+            HashAlgorithms::Cshake256 => Box::new(new_cshake(256, length.unwrap_or(64), &parameters.function_name, &parameters.customization)),
+            HashAlgorithms::Kmac128 => Box::new(Kmac::new(128, &parameters.key, length.unwrap_or(32), &parameters.customization)),
+            HashAlgorithms::Kmac256 => Box::new(Kmac::new(256, &parameters.key, length.unwrap_or(64), &parameters.customization)),
+            HashAlgorithms::Blake2b => Box::new(Blake2b::new(length.unwrap_or(64), &parameters.key, &parameters.salt, &parameters.personalization)?),
+            HashAlgorithms::Blake2s => Box::new(Blake2s::new(length.unwrap_or(32), &parameters.key, &parameters.salt, &parameters.personalization)?),
+            HashAlgorithms::Blake3 => match (&parameters.context, parameters.key.is_empty()) {
+                (Some(_), false) => return Err("BLAKE3 takes either a key or a key derivation context".to_string()),
+                (Some(context), true) => Box::new(Blake3::new_derive_key(context, length.unwrap_or(32))),
+                (None, false) => Box::new(Blake3::new_keyed(&parameters.key, length.unwrap_or(32))?),
+                (None, true) => Box::new(Blake3::new(length.unwrap_or(32)))
+            },
+            _ => self.fixed_hasher()
+        });
+    }
+
+    fn fixed_hasher(&self) -> Box<dyn HashFunction> {
+        match self {
+            HashAlgorithms::Md4 => Box::new(new_md4()),
+            HashAlgorithms::Md5 => Box::new(new_md5()),
+            HashAlgorithms::Sha1 => Box::new(new_sha1()),
+            HashAlgorithms::Sha224 => Box::new(new_sha224()),
+            HashAlgorithms::Sha256 => Box::new(new_sha256()),
+            HashAlgorithms::Sha384 => Box::new(new_sha384()),
+            HashAlgorithms::Sha512 => Box::new(new_sha512()),
+            HashAlgorithms::Sha512T224 => Box::new(new_sha512_224()),
+            HashAlgorithms::Sha512T256 => Box::new(new_sha512_256()),
+            HashAlgorithms::Sha3T224 => Box::new(new_sha3(28)),
+            HashAlgorithms::Sha3T256 => Box::new(new_sha3(32)),
+            HashAlgorithms::Sha3T384 => Box::new(new_sha3(48)),
+            HashAlgorithms::Sha3T512 => Box::new(new_sha3(64)),
+            HashAlgorithms::Keccak224 => Box::new(new_keccak(28)),
+            HashAlgorithms::Keccak256 => Box::new(new_keccak(32)),
+            HashAlgorithms::Keccak384 => Box::new(new_keccak(48)),
+            HashAlgorithms::Keccak512 => Box::new(new_keccak(64)),
+            HashAlgorithms::Ripemd160 => Box::new(new_ripemd160()),
+            HashAlgorithms::Whirlpool => Box::new(new_whirlpool()),
+            HashAlgorithms::Tiger => Box::new(new_tiger()),
+            HashAlgorithms::Tiger2 => Box::new(new_tiger2()),
+            HashAlgorithms::Sm3 => Box::new(new_sm3()),
+            _ => unreachable!()
+        }
+    }
+}
+
+
+
+
+pub fn digest(algorithm: &HashAlgorithms, data: &[u8]) -> Vec<u8> {
+    let mut hasher: Box<dyn HashFunction> = algorithm.hasher();
+    hasher.update(data);
+
+    return hasher.finalize();
+}
+
+
+
+
+pub fn hash_reader(hasher: &mut dyn HashFunction, mut reader: impl Read) -> Result<(), String> {
+    let mut buffer: Vec<u8> = vec![0; 64 * 1024];
+
+    loop {
+        let read: usize = reader.read(&mut buffer).map_err(|e| format!("could not read input: {}", e))?;
+
+        if read == 0 {
+            return Ok(());
+        }
+        hasher.update(&buffer[..read]);
+    }
+}
+
+
+
+
+#[derive(Debug, Args, Clone)]
+pub struct HashAlg {
+    /// Hash function
+    #[arg(short, long)]
+    pub algorithm: HashAlgorithms,
+    /// The message to hash
+    #[arg(short, long, conflicts_with = "file")]
+    pub message: Option<String>,
+    /// File to hash ("-" or no message for stdin)
+    #[arg(short, long)]
+    pub file: Option<String>,
+    /// Output length in bytes (SHAKE, cSHAKE, KMAC, BLAKE2 and BLAKE3)
+    #[arg(short, long)]
+    pub length: Option<usize>,
+    /// Function name string (cSHAKE)
+    #[arg(short = 'n', long, default_value = "")]
+    pub function_name: String,
+    /// Customization string (cSHAKE and KMAC)
+    #[arg(short = 's', long, default_value = "")]
+    pub customization: String,
+    /// Key (KMAC, BLAKE2 and BLAKE3)
+    #[arg(short, long, default_value = "")]
+    pub key: String,
+    /// Key encoding
+    #[arg(long, value_enum, default_value = "text")]
+    pub key_format: KeyFormat,
+    /// Salt (BLAKE2)
+    #[arg(long, default_value = "")]
+    pub salt: String,
+    /// Personalization string (BLAKE2)
+    #[arg(short, long, default_value = "")]
+    pub personalization: String,
+    /// Key derivation context (BLAKE3)
+    #[arg(short, long)]
+    pub context: Option<String>
+}
+
+impl HashAlg {
+    pub fn execute(self) -> () {
+        match self.hash() {
+            Ok(hash) => {
+                match (&self.message, &self.file) {
+                    (Some(_), _) => println!("{}", bytes_to_hex(&hash)),
+                    (None, Some(file)) => println!("{}  {}", bytes_to_hex(&hash), file),
+                    (None, None) => println!("{}  -", bytes_to_hex(&hash))
+                }
+            }
+            Err(error) => println!("{}", error)
+        }
+    }
+
+    fn hash(&self) -> Result<Vec<u8>, String> {
+        let parameters: HashParameters = HashParameters {
+            length: self.length,
+            function_name: self.function_name.as_bytes().to_vec(),
+            customization: self.customization.as_bytes().to_vec(),
+            key: decode_key(&self.key, &self.key_format)?,
+            salt: self.salt.as_bytes().to_vec(),
+            personalization: self.personalization.as_bytes().to_vec(),
+            context: self.context.clone()
+        };
+        let mut hasher: Box<dyn HashFunction> = self.algorithm.configured_hasher(&parameters)?;
+
+        match &self.message {
+            Some(message) => hasher.update(message.as_bytes()),
+            None => hash_reader(hasher.as_mut(), open_input(self.file.as_deref())?)?
+        }
+        return Ok(hasher.finalize());
+    }
+}
+
+#[cfg(test)]
+mod hash_function_test {
+    use super::*;
+
+    #[test]
+    fn hash_file_test() -> () {
+        let path: String = std::env::temp_dir().join(format!("hash_test_{}", std::process::id())).to_string_lossy().to_string();
+        let content: Vec<u8> = (0..=255).cycle().take(200000).collect();
+        std::fs::write(&path, &content).unwrap();
+
+        let instance_1: HashAlg = HashAlg {
+            algorithm: HashAlgorithms::Md5,
+            message: None,
+            file: Some(path.clone()),
+            length: None,
+            function_name: String::new(),
+            customization: String::new(),
+            key: String::new(),
+            key_format: KeyFormat::Text,
+            salt: String::new(),
+            personalization: String::new(),
+            context: None
+        };
+        assert_eq!(instance_1.hash(), Ok(digest(&HashAlgorithms::Md5, &content)));
+
+        let instance_2: HashAlg = HashAlg {
+            algorithm: HashAlgorithms::Md4,
+            message: Some("abc".to_string()),
+            file: None,
+            ..instance_1.clone()
+        };
+        assert_eq!(instance_2.hash().map(|hash: Vec<u8>| bytes_to_hex(&hash)), Ok("a448017aaf21d8525fc10ae87aa6729d".to_string()));
+
+        let instance_3: HashAlg = HashAlg {
+            algorithm: HashAlgorithms::Shake128,
+            length: Some(8),
+            ..instance_2.clone()
+        };
+        assert_eq!(instance_3.hash().map(|hash: Vec<u8>| bytes_to_hex(&hash)), Ok("5881092dd818bf5c".to_string()));
+
+        let instance_4: HashAlg = HashAlg {
+            algorithm: HashAlgorithms::Sha3T256,
+            ..instance_3.clone()
+        };
+        assert!(instance_4.hash().is_err());
+
+        let instance_5: HashAlg = HashAlg {
+            algorithm: HashAlgorithms::Blake2s,
+            length: Some(16),
+            key: "6b6579".to_string(),
+            key_format: KeyFormat::Hex,
+            ..instance_2.clone()
+        };
+        assert_eq!(instance_5.hash().map(|hash: Vec<u8>| bytes_to_hex(&hash)), Ok("94fdf6f35b9999920dcdcaee361ad435".to_string()));
+
+        let instance_6: HashAlg = HashAlg {
+            algorithm: HashAlgorithms::Blake3,
+            context: Some("context".to_string()),
+            ..instance_5.clone()
+        };
+        assert!(instance_6.hash().is_err());
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
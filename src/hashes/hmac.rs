@@ -0,0 +1,270 @@
+use std::fs;
+use clap::Args;
+use crate::{hashes::hash_function::{digest, hash_reader, HashAlgorithms, HashFunction}, ops::{bytes_to_hex, constant_time_eq, decode_key, hex_to_bytes, open_input, KeyFormat}};
+
+const INNER_PAD: u8 = 0x36;
+const OUTER_PAD: u8 = 0x5c;
+const MIN_TRUNCATED_TAG: usize = 10;
+
+// The outer hash is only built at finalize, since hashers behind the trait object can not be cloned
+pub struct Hmac {
+    algorithm: HashAlgorithms,
+    inner: Box<dyn HashFunction>,
+    outer_key: Vec<u8>
+}
+impl Hmac {
+    pub fn new(algorithm: &HashAlgorithms, key: &[u8]) -> Hmac {
+        let mut inner: Box<dyn HashFunction> = algorithm.hasher();
+        let block_size: usize = inner.block_size();
+
+        let mut block_key: Vec<u8> = if key.len() > block_size { digest(algorithm, key) } else { key.to_vec() };
+        block_key.resize(block_size, 0);
+
+        inner.update(&block_key.iter().map(|byte: &u8| byte ^ INNER_PAD).collect::<Vec<u8>>());
+
+        return Hmac {
+            algorithm: algorithm.clone(),
+            inner: inner,
+            outer_key: block_key.iter().map(|byte: &u8| byte ^ OUTER_PAD).collect()
+        };
+    }
+}
+
+impl HashFunction for Hmac {
+    fn update(&mut self, data: &[u8]) -> () {
+        self.inner.update(data);
+    }
+
+    fn finalize(&self) -> Vec<u8> {
+        let mut outer: Box<dyn HashFunction> = self.algorithm.hasher();
+        outer.update(&self.outer_key);
+        outer.update(&self.inner.finalize());
+
+        return outer.finalize();
+    }
+
+    fn block_size(&self) -> usize {
+        return self.inner.block_size();
+    }
+
+    fn output_size(&self) -> usize {
+        return self.inner.output_size();
+    }
+}
+
+
+
+
+pub fn hmac(algorithm: &HashAlgorithms, key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac: Hmac = Hmac::new(algorithm, key);
+    mac.update(message);
+
+    return mac.finalize();
+}
+
+
+
+
+#[derive(Debug, Args, Clone)]
+pub struct HmacAlg {
+    /// Hash function
+    #[arg(short, long)]
+    pub algorithm: HashAlgorithms,
+    /// Secret key
+    #[arg(short, long, conflicts_with = "key_file", required_unless_present = "key_file")]
+    pub key: Option<String>,
+    /// File holding the raw secret key
+    #[arg(long)]
+    pub key_file: Option<String>,
+    /// Key encoding
+    #[arg(long, value_enum, default_value = "text")]
+    pub key_format: KeyFormat,
+    /// The message to authenticate
+    #[arg(short, long, conflicts_with = "file")]
+    pub message: Option<String>,
+    /// File to authenticate ("-" or no message for stdin)
+    #[arg(short, long)]
+    pub file: Option<String>,
+    /// Expected tag in hex, verified instead of printing the tag
+    #[arg(short, long)]
+    pub expected: Option<String>
+}
+
+impl HmacAlg {
+    pub fn execute(self) -> () {
+        let tag: Vec<u8> = match self.tag() {
+            Ok(tag) => tag,
+            Err(error) => {
+                println!("{}", error);
+                std::process::exit(1);
+            }
+        };
+
+        match &self.expected {
+            None => println!("{}", bytes_to_hex(&tag)),
+            Some(expected) => match self.verify(&tag, expected) {
+                Ok(true) => println!("OK"),
+                Ok(false) => {
+                    println!("FAILED");
+                    std::process::exit(1);
+                }
+                Err(error) => {
+                    println!("{}", error);
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+
+    fn key(&self) -> Result<Vec<u8>, String> {
+        match (&self.key, &self.key_file) {
+            (Some(key), _) => decode_key(key, &self.key_format),
+            (None, Some(path)) => fs::read(path).map_err(|e| format!("could not read key file {}: {}", path, e)),
+            (None, None) => Err("a key or key file is required".to_string())
+        }
+    }
+
+    fn tag(&self) -> Result<Vec<u8>, String> {
+        let mut mac: Hmac = Hmac::new(&self.algorithm, &self.key()?);
+
+        match &self.message {
+            Some(message) => mac.update(message.as_bytes()),
+            None => hash_reader(&mut mac, open_input(self.file.as_deref())?)?
+        }
+        return Ok(mac.finalize());
+    }
+
+    // A shorter expected tag is compared against the leading bytes. RFC 2104 section 5 allows truncation
+    // down to half of the hash output, and never below 80 bits
+    fn verify(&self, tag: &[u8], expected: &str) -> Result<bool, String> {
+        let expected: Vec<u8> = hex_to_bytes(expected)?;
+
+        let minimum_length: usize = MIN_TRUNCATED_TAG.max(tag.len() / 2).min(tag.len());
+        if expected.len() < minimum_length {
+            return Err(format!("expected tag must be at least {} bytes", minimum_length));
+        }
+        if expected.len() > tag.len() {
+            return Ok(false);
+        }
+        return Ok(constant_time_eq(&tag[..expected.len()], &expected));
+    }
+}
+
+#[cfg(test)]
+mod hmac_test {
+    use super::*;
+
+    fn hmac_hex(algorithm: HashAlgorithms, key: &[u8], message: &[u8]) -> String {
+        return bytes_to_hex(&hmac(&algorithm, key, message));
+    }
+
+    // Key and data of test cases 1 to 7; RFC 2202 uses 16 byte keys for MD5, 20 byte keys for SHA-1 and 80 byte long keys
+    fn test_cases(key_size: usize, long_key_size: usize) -> Vec<(Vec<u8>, Vec<u8>)> {
+        return vec![
+            (vec![0x0b; key_size], b"Hi There".to_vec()),
+            (b"Jefe".to_vec(), b"what do ya want for nothing?".to_vec()),
+            (vec![0xaa; key_size], vec![0xdd; 50]),
+            ((1..=25).collect(), vec![0xcd; 50]),
+            (vec![0x0c; key_size], b"Test With Truncation".to_vec()),
+            (vec![0xaa; long_key_size], b"Test Using Larger Than Block-Size Key - Hash Key First".to_vec()),
+            (vec![0xaa; long_key_size], if long_key_size == 80 {
+                b"Test Using Larger Than Block-Size Key and Larger Than One Block-Size Data".to_vec()
+            } else {
+                b"This is a test using a larger than block-size key and a larger than block-size data. The key needs to be hashed before being used by the HMAC algorithm.".to_vec()
+            })
+        ];
+    }
+
+    fn check_cases(algorithm: HashAlgorithms, cases: Vec<(Vec<u8>, Vec<u8>)>, expected: [&str; 7]) -> () {
+        for ((key, data), tag) in cases.iter().zip(expected) {
+            assert_eq!(hmac_hex(algorithm.clone(), key, data)[..tag.len()], *tag);
+        }
+    }
+
+    #[test]
+    fn rfc_2202_test() -> () {
+        check_cases(HashAlgorithms::Md5, test_cases(16, 80), ["9294727a3638bb1c13f48ef8158bfc9d",
+                                                              "750c783e6ab0b503eaa86e310a5db738",
+                                                              "56be34521d144c88dbb8c733f0e8b3f6",
+                                                              "697eaf0aca3a3aea3a75164746ffaa79",
+                                                              "56461ef2342edc00f9bab995690efd4c",
+                                                              "6b1ab7fe4bd7bf8f0b62e6ce61b9d0cd",
+                                                              "6f630fad67cda0ee1fb1f562db3aa53e"]);
+
+        check_cases(HashAlgorithms::Sha1, test_cases(20, 80), ["b617318655057264e28bc0b6fb378c8ef146be00",
+                                                               "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79",
+                                                               "125d7342b9ac11cd91a39af48aa17b4f63f175d3",
+                                                               "4c9007f4026250c6bc8414f9bf50c86c2d7235da",
+                                                               "4c1a03424b55e07fe7f27be1d58bb9324a9a5a04",
+                                                               "aa4ae5e15272d00e95705637ce8a3b55ed402112",
+                                                               "e8e99d0f45237d786d6bbaa7965c7808bbff1a91"]);
+    }
+
+    #[test]
+    fn rfc_4231_test() -> () {
+        check_cases(HashAlgorithms::Sha224, test_cases(20, 131), ["896fb1128abbdf196832107cd49df33f47b4b1169912ba4f53684b22",
+                                                                  "a30e01098bc6dbbf45690f3a7e9e6d0f8bbea2a39e6148008fd05e44",
+                                                                  "7fb3cb3588c6c1f6ffa9694d7d6ad2649365b0c1f65d69d1ec8333ea",
+                                                                  "6c11506874013cac6a2abc1bb382627cec6a90d86efc012de7afec5a",
+                                                                  "0e2aea68a90c8d37c988bcdb9fca6fa8",
+                                                                  "95e9a0db962095adaebe9b2d6f0dbce2d499f112f2d2b7273fa6870e",
+                                                                  "3a854166ac5d9f023f54d517d0b39dbd946770db9c2b95c9f6f565d1"]);
+
+        check_cases(HashAlgorithms::Sha256, test_cases(20, 131), ["b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+                                                                  "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+                                                                  "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+                                                                  "82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b",
+                                                                  "a3b6167473100ee06e0c796c2955552b",
+                                                                  "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+                                                                  "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2"]);
+
+        check_cases(HashAlgorithms::Sha384, test_cases(20, 131), ["afd03944d84895626b0825f4ab46907f15f9dadbe4101ec682aa034c7cebc59cfaea9ea9076ede7f4af152e8b2fa9cb6",
+                                                                  "af45d2e376484031617f78d2b58a6b1b9c7ef464f5a01b47e42ec3736322445e8e2240ca5e69e2c78b3239ecfab21649",
+                                                                  "88062608d3e6ad8a0aa2ace014c8a86f0aa635d947ac9febe83ef4e55966144b2a5ab39dc13814b94e3ab6e101a34f27",
+                                                                  "3e8a69b7783c25851933ab6290af6ca77a9981480850009cc5577c6e1f573b4e6801dd23c4a7d679ccf8a386c674cffb",
+                                                                  "3abf34c3503b2a23a46efc619baef897",
+                                                                  "4ece084485813e9088d2c63a041bc5b44f9ef1012a2b588f3cd11f05033ac4c60c2ef6ab4030fe8296248df163f44952",
+                                                                  "6617178e941f020d351e2f254e8fd32c602420feb0b8fb9adccebb82461e99c5a678cc31e799176d3860e6110c46523e"]);
+
+        check_cases(HashAlgorithms::Sha512, test_cases(20, 131), ["87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854",
+                                                                  "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+                                                                  "fa73b0089d56a284efb0f0756c890be9b1b5dbdd8ee81a3655f83e33b2279d39bf3e848279a722c806b485a47e67c807b946a337bee8942674278859e13292fb",
+                                                                  "b0ba465637458c6990e5a8c5f61d4af7e576d97ff94b872de76f8050361ee3dba91ca5c11aa25eb4d679275cc5788063a5f19741120c4f2de2adebeb10a298dd",
+                                                                  "415fad6271580a531d4179bc891d87a6",
+                                                                  "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598",
+                                                                  "e37b6a775dc87dbaa4dfa9f96e5e3ffddebd71f8867289865df5a32d20cdc944b6022cac3c4982b10d5eeb55c3e4de15134676fb6de0446065c97440fa8c6a58"]);
+    }
+
+    #[test]
+    fn other_hashes_test() -> () {
+        assert_eq!(hmac_hex(HashAlgorithms::Sha3T256, b"key", b"The quick brown fox jumps over the lazy dog"), "8c6e0683409427f8931711b10ca92a506eb1fafa48fadd66d76126f47ac2c333");
+        assert_eq!(hmac_hex(HashAlgorithms::Ripemd160, b"key", b"The quick brown fox jumps over the lazy dog"), "50278a77d4d7670561ab72e867383aef6ce50b3e");
+    }
+
+    #[test]
+    fn verify_test() -> () {
+        let instance_1: HmacAlg = HmacAlg {
+            algorithm: HashAlgorithms::Sha256,
+            key: Some("4a656665".to_string()),
+            key_file: None,
+            key_format: KeyFormat::Hex,
+            message: Some("what do ya want for nothing?".to_string()),
+            file: None,
+            expected: None
+        };
+        let tag: Vec<u8> = instance_1.tag().unwrap();
+
+        assert_eq!(instance_1.verify(&tag, "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"), Ok(true));
+        assert_eq!(instance_1.verify(&tag, "5bdcc146bf60754e6a042426089575c7"), Ok(true));
+        assert_eq!(instance_1.verify(&tag, "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3844"), Ok(false));
+        assert!(instance_1.verify(&tag, "5bdcc146bf60754e6a042426089575").is_err());
+        assert!(instance_1.verify(&tag, "5bdcc146").is_err());
+        assert!(instance_1.verify(&tag, "not hex").is_err());
+
+        let instance_2: HmacAlg = HmacAlg { algorithm: HashAlgorithms::Sha512, ..instance_1.clone() };
+        let tag: Vec<u8> = instance_2.tag().unwrap();
+        assert_eq!(instance_2.verify(&tag, &bytes_to_hex(&tag[..32])), Ok(true));
+        assert!(instance_2.verify(&tag, &bytes_to_hex(&tag[..31])).is_err());
+        assert!(instance_2.verify(&tag, &bytes_to_hex(&tag[..10])).is_err());
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{hashes::merkle_damgard::{CompressionFunction, MerkleDamgard}, tables::{MD4_SHIFT_TABLE, MD4_WORD_ORDER_TABLE}};
+
+const MD4_ROUND_CONSTANTS: [u32; 3] = [0x00000000, 0x5a827999, 0x6ed9eba1];
+
+pub type Md4 = MerkleDamgard<Md4Compression>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Md4Compression {
+    pub state: [u32; 4]
+}
+impl Md4Compression {
+    pub fn new() -> Md4Compression {
+        return Md4Compression { state: [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476] };
+    }
+}
+
+impl CompressionFunction for Md4Compression {
+    fn block_size(&self) -> usize {
+        return 64;
+    }
+
+    fn output_size(&self) -> usize {
+        return 16;
+    }
+
+    fn big_endian_length(&self) -> bool {
+        return false;
+    }
+
+    fn compress(&mut self, block: &[u8]) -> () {
+        let x: Vec<u32> = block.chunks(4).map(|chunk: &[u8]| u32::from_le_bytes(chunk.try_into().unwrap())).collect();
+        let [mut a, mut b, mut c, mut d]: [u32; 4] = self.state;
+
+        for round in 0..3 {
+            for step in 0..16 {
+                let f: u32 = match round {
+                    0 => (b & c) | (!b & d),
+                    1 => (b & c) | (b & d) | (c & d),
+                    _ => b ^ c ^ d
+                };
+
+                let rotated: u32 = a.wrapping_add(f)
+                                    .wrapping_add(x[MD4_WORD_ORDER_TABLE[round][step]])
+                                    .wrapping_add(MD4_ROUND_CONSTANTS[round])
+                                    .rotate_left(MD4_SHIFT_TABLE[round][step % 4]);
+                a = d;
+                d = c;
+                c = b;
+                b = rotated;
+            }
+        }
+
+        for (word, value) in self.state.iter_mut().zip([a, b, c, d]) {
+            *word = word.wrapping_add(value);
+        }
+    }
+
+    fn digest(&self) -> Vec<u8> {
+        return self.state.iter().flat_map(|word: &u32| word.to_le_bytes()).collect();
+    }
+}
+
+pub fn new_md4() -> Md4 {
+    return MerkleDamgard::new(Md4Compression::new());
+}
+
+#[cfg(test)]
+mod md4_test {
+    use crate::{hashes::hash_function::HashFunction, ops::bytes_to_hex};
+    use super::*;
+
+    fn md4_hex(message: &[u8]) -> String {
+        let mut hasher: Md4 = new_md4();
+        hasher.update(message);
+        return bytes_to_hex(&hasher.finalize());
+    }
+
+    #[test]
+    fn rfc_1320_test_suite() -> () {
+        assert_eq!(md4_hex(b""), "31d6cfe0d16ae931b73c59d7e0c089c0");
+        assert_eq!(md4_hex(b"a"), "bde52cb31de33e46245e05fbdbd6fb24");
+        assert_eq!(md4_hex(b"abc"), "a448017aaf21d8525fc10ae87aa6729d");
+        assert_eq!(md4_hex(b"message digest"), "d9130a8164549fe818874806e1c7014b");
+        assert_eq!(md4_hex(b"abcdefghijklmnopqrstuvwxyz"), "d79e1c308aa5bbcdeea8ed63df412da9");
+        assert_eq!(md4_hex(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789"), "043f8582f241db351ce627e153e7f0e4");
+        assert_eq!(md4_hex(b"12345678901234567890123456789012345678901234567890123456789012345678901234567890"), "e33b4ddc9c38f2199c3e7b164fcc0536");
+    }
+}
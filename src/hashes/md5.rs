@@ -0,0 +1,96 @@
+use crate::{hashes::merkle_damgard::{CompressionFunction, MerkleDamgard}, tables::{MD5_SHIFT_TABLE, MD5_SINE_TABLE}};
+
+pub type Md5 = MerkleDamgard<Md5Compression>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Md5Compression {
+    pub state: [u32; 4]
+}
+impl Md5Compression {
+    pub fn new() -> Md5Compression {
+        return Md5Compression { state: [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476] };
+    }
+}
+
+impl CompressionFunction for Md5Compression {
+    fn block_size(&self) -> usize {
+        return 64;
+    }
+
+    fn output_size(&self) -> usize {
+        return 16;
+    }
+
+    fn big_endian_length(&self) -> bool {
+        return false;
+    }
+
+    fn compress(&mut self, block: &[u8]) -> () {
+        let m: Vec<u32> = block.chunks(4).map(|chunk: &[u8]| u32::from_le_bytes(chunk.try_into().unwrap())).collect();
+        let [mut a, mut b, mut c, mut d]: [u32; 4] = self.state;
+
+        for i in 0..64 {
+            let (f, g): (u32, usize) = match i / 16 {
+                0 => ((b & c) | (!b & d), i),
+                1 => ((b & d) | (c & !d), (5 * i + 1) % 16),
+                2 => (b ^ c ^ d, (3 * i + 5) % 16),
+                _ => (c ^ (b | !d), (7 * i) % 16)
+            };
+
+            let rotated: u32 = f.wrapping_add(a)
+                                .wrapping_add(MD5_SINE_TABLE[i])
+                                .wrapping_add(m[g])
+                                .rotate_left(MD5_SHIFT_TABLE[i / 16][i % 4]);
+            a = d;
+            d = c;
+            c = b;
+            b = b.wrapping_add(rotated);
+        }
+
+        for (word, value) in self.state.iter_mut().zip([a, b, c, d]) {
+            *word = word.wrapping_add(value);
+        }
+    }
+
+    fn digest(&self) -> Vec<u8> {
+        return self.state.iter().flat_map(|word: &u32| word.to_le_bytes()).collect();
+    }
+}
+
+pub fn new_md5() -> Md5 {
+    return MerkleDamgard::new(Md5Compression::new());
+}
+
+#[cfg(test)]
+mod md5_test {
+    use crate::{hashes::hash_function::HashFunction, ops::bytes_to_hex};
+    use super::*;
+
+    fn md5_hex(message: &[u8]) -> String {
+        let mut hasher: Md5 = new_md5();
+        hasher.update(message);
+        return bytes_to_hex(&hasher.finalize());
+    }
+
+    #[test]
+    fn rfc_1321_test_suite() -> () {
+        assert_eq!(md5_hex(b""), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(md5_hex(b"a"), "0cc175b9c0f1b6a831c399e269772661");
+        assert_eq!(md5_hex(b"abc"), "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(md5_hex(b"message digest"), "f96b697d7cb7938d525a2f31aaf161d0");
+        assert_eq!(md5_hex(b"abcdefghijklmnopqrstuvwxyz"), "c3fcd3d76192e4007dfb496cca67e13b");
+        assert_eq!(md5_hex(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789"), "d174ab98d277d9f5a5611c2c9f419d9f");
+        assert_eq!(md5_hex(b"12345678901234567890123456789012345678901234567890123456789012345678901234567890"), "57edf4a22be3c955ac49da2e2107b67a");
+    }
+
+    #[test]
+    fn incremental_update_test() -> () {
+        let message: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let mut hasher: Md5 = new_md5();
+
+        for chunk in message.chunks(37) {
+            hasher.update(chunk);
+        }
+        assert_eq!(bytes_to_hex(&hasher.finalize()), md5_hex(&message));
+    }
+}
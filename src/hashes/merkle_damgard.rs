@@ -0,0 +1,109 @@
+use crate::hashes::hash_function::HashFunction;
+
+pub trait CompressionFunction: Clone {
+    fn block_size(&self) -> usize;
+    fn output_size(&self) -> usize;
+    fn big_endian_length(&self) -> bool;
+    fn compress(&mut self, block: &[u8]) -> ();
+    fn digest(&self) -> Vec<u8>;
+
+    fn length_size(&self) -> usize {
+        return 8;
+    }
+
+    fn padding_byte(&self) -> u8 {
+        return 0x80;
+    }
+}
+
+
+
+
+#[derive(Debug, Clone)]
+pub struct MerkleDamgard<C: CompressionFunction> {
+    pub core: C,
+    buffer: Vec<u8>,
+    length: u128
+}
+impl<C: CompressionFunction> MerkleDamgard<C> {
+    pub fn new(core: C) -> MerkleDamgard<C> {
+        return MerkleDamgard::from_state(core, 0);
+    }
+
+    // Resumes hashing from a chaining state that already absorbed `length` bytes (a whole number of blocks)
+    pub fn from_state(core: C, length: u128) -> MerkleDamgard<C> {
+        return MerkleDamgard {
+            core: core,
+            buffer: Vec::new(),
+            length: length
+        };
+    }
+
+    pub fn padding(&self, message_len: u128) -> Vec<u8> {
+        let block_size: usize = self.core.block_size();
+        let length_size: usize = self.core.length_size();
+        let used: usize = (message_len % block_size as u128) as usize;
+        let zeros: usize = (2 * block_size - used - 1 - length_size) % block_size;
+        let bit_length: u128 = message_len.wrapping_mul(8);
+
+        let mut padding: Vec<u8> = vec![self.core.padding_byte()];
+        padding.extend(vec![0; zeros]);
+
+        let mut length_field: Vec<u8> = vec![0; length_size];
+        let n: usize = length_size.min(16);
+
+        if self.core.big_endian_length() {
+            length_field[length_size - n..].copy_from_slice(&bit_length.to_be_bytes()[16 - n..]);
+        } else {
+            length_field[..n].copy_from_slice(&bit_length.to_le_bytes()[..n]);
+        }
+
+        padding.extend(length_field);
+        return padding;
+    }
+}
+
+impl<C: CompressionFunction> HashFunction for MerkleDamgard<C> {
+    fn update(&mut self, data: &[u8]) -> () {
+        let block_size: usize = self.core.block_size();
+        let mut input: &[u8] = data;
+
+        self.length = self.length.wrapping_add(data.len() as u128);
+
+        if !self.buffer.is_empty() {
+            let missing: usize = (block_size - self.buffer.len()).min(input.len());
+            self.buffer.extend_from_slice(&input[..missing]);
+            input = &input[missing..];
+
+            if self.buffer.len() < block_size {
+                return ();
+            }
+
+            let block: Vec<u8> = std::mem::take(&mut self.buffer);
+            self.core.compress(&block);
+        }
+
+        let mut blocks = input.chunks_exact(block_size);
+
+        for block in &mut blocks {
+            self.core.compress(block);
+        }
+        self.buffer.extend_from_slice(blocks.remainder());
+    }
+
+    fn finalize(&self) -> Vec<u8> {
+        let mut final_state: MerkleDamgard<C> = self.clone();
+        let padding: Vec<u8> = self.padding(self.length);
+
+        final_state.update(&padding);
+        return final_state.core.digest();
+    }
+
+    fn block_size(&self) -> usize {
+        return self.core.block_size();
+    }
+
+    fn output_size(&self) -> usize {
+        return self.core.output_size();
+    }
+}
@@ -0,0 +1,207 @@
+use clap::{Args, ValueEnum};
+use crate::{hashes::{crc::{Crc, CrcParameters}, fletcher::{Adler32, Fletcher}, fnv::Fnv1a, hash_function::{hash_reader, HashFunction}, siphash::SipHash}, ops::{bytes_to_hex, decode_key, open_input, KeyFormat}};
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum NonCryptoHashAlgorithms {
+    /// CRC-8/SMBUS (non-cryptographic)
+    Crc8,
+    /// CRC-16/ARC (non-cryptographic)
+    Crc16,
+    /// CRC-16/CCITT-FALSE (non-cryptographic)
+    Crc16Ccitt,
+    /// CRC-32 as in zip and Ethernet (non-cryptographic)
+    Crc32,
+    /// CRC-32C Castagnoli (non-cryptographic)
+    Crc32c,
+    /// CRC-64/XZ (non-cryptographic)
+    Crc64,
+    /// CRC-64/ECMA-182 (non-cryptographic)
+    Crc64Ecma,
+    /// Adler-32 (non-cryptographic)
+    Adler32,
+    /// Fletcher-16 (non-cryptographic)
+    Fletcher16,
+    /// Fletcher-32 (non-cryptographic)
+    Fletcher32,
+    /// Fletcher-64 (non-cryptographic)
+    Fletcher64,
+    /// FNV-1a 32 bits (non-cryptographic)
+    Fnv1a32,
+    /// FNV-1a 64 bits (non-cryptographic)
+    Fnv1a64,
+    /// SipHash-2-4, a keyed PRF
+    Siphash
+}
+impl NonCryptoHashAlgorithms {
+    pub fn is_cryptographic(&self) -> bool {
+        return *self == NonCryptoHashAlgorithms::Siphash;
+    }
+
+    fn crc_parameters(&self) -> Option<CrcParameters> {
+        match self {
+            NonCryptoHashAlgorithms::Crc8 => Some(CrcParameters::crc8()),
+            NonCryptoHashAlgorithms::Crc16 => Some(CrcParameters::crc16_arc()),
+            NonCryptoHashAlgorithms::Crc16Ccitt => Some(CrcParameters::crc16_ccitt()),
+            NonCryptoHashAlgorithms::Crc32 => Some(CrcParameters::crc32()),
+            NonCryptoHashAlgorithms::Crc32c => Some(CrcParameters::crc32c()),
+            NonCryptoHashAlgorithms::Crc64 => Some(CrcParameters::crc64_xz()),
+            NonCryptoHashAlgorithms::Crc64Ecma => Some(CrcParameters::crc64_ecma()),
+            _ => None
+        }
+    }
+}
+
+
+
+
+fn parse_hex_u64(value: &str) -> Result<u64, String> {
+    return u64::from_str_radix(value.trim_start_matches("0x"), 16).map_err(|_| format!("invalid hex number: {}", value));
+}
+
+
+
+
+#[derive(Debug, Args, Clone)]
+pub struct NonCryptoHashAlg {
+    /// Checksum or hash function
+    #[arg(short, long)]
+    pub algorithm: NonCryptoHashAlgorithms,
+    /// The message to hash
+    #[arg(short, long, conflicts_with = "file")]
+    pub message: Option<String>,
+    /// File to hash ("-" or no message for stdin)
+    #[arg(short, long)]
+    pub file: Option<String>,
+    /// Key (SipHash, 16 bytes)
+    #[arg(short, long)]
+    pub key: Option<String>,
+    /// Key encoding
+    #[arg(long, value_enum, default_value = "text")]
+    pub key_format: KeyFormat,
+    /// CRC generator polynomial in hex, replacing the preset one (same width)
+    #[arg(long)]
+    pub polynomial: Option<String>,
+    /// CRC initial register in hex
+    #[arg(long)]
+    pub init: Option<String>,
+    /// CRC final xor value in hex
+    #[arg(long)]
+    pub xor_out: Option<String>,
+    /// Whether the CRC reflects input bytes and the result
+    #[arg(long)]
+    pub reflect: Option<bool>
+}
+
+impl NonCryptoHashAlg {
+    pub fn execute(self) -> () {
+        match self.hash() {
+            Ok(hash) => {
+                match (&self.message, &self.file) {
+                    (Some(_), _) => println!("{}", bytes_to_hex(&hash)),
+                    (None, Some(file)) => println!("{}  {}", bytes_to_hex(&hash), file),
+                    (None, None) => println!("{}  -", bytes_to_hex(&hash))
+                }
+            }
+            Err(error) => println!("{}", error)
+        }
+    }
+
+    fn crc_parameters(&self, preset: CrcParameters) -> Result<CrcParameters, String> {
+        return Ok(CrcParameters {
+            width: preset.width,
+            polynomial: self.polynomial.as_deref().map(parse_hex_u64).transpose()?.unwrap_or(preset.polynomial),
+            init: self.init.as_deref().map(parse_hex_u64).transpose()?.unwrap_or(preset.init),
+            reflect: self.reflect.unwrap_or(preset.reflect),
+            xor_out: self.xor_out.as_deref().map(parse_hex_u64).transpose()?.unwrap_or(preset.xor_out)
+        });
+    }
+
+    fn hasher(&self) -> Result<Box<dyn HashFunction>, String> {
+        let crc_options: bool = self.polynomial.is_some() || self.init.is_some() || self.xor_out.is_some() || self.reflect.is_some();
+
+        if self.key.is_some() && !self.algorithm.is_cryptographic() {
+            return Err("only SipHash takes a key".to_string());
+        }
+        if let Some(preset) = self.algorithm.crc_parameters() {
+            return Ok(Box::new(Crc::new(self.crc_parameters(preset)?)?));
+        }
+        if crc_options {
+            return Err("polynomial, init, xor-out and reflect only apply to CRCs".to_string());
+        }
+
+        return Ok(match self.algorithm {
+            NonCryptoHashAlgorithms::Adler32 => Box::new(Adler32::new()),
+            NonCryptoHashAlgorithms::Fletcher16 => Box::new(Fletcher::new(16)?),
+            NonCryptoHashAlgorithms::Fletcher32 => Box::new(Fletcher::new(32)?),
+            NonCryptoHashAlgorithms::Fletcher64 => Box::new(Fletcher::new(64)?),
+            NonCryptoHashAlgorithms::Fnv1a32 => Box::new(Fnv1a::new_32()),
+            NonCryptoHashAlgorithms::Fnv1a64 => Box::new(Fnv1a::new_64()),
+            NonCryptoHashAlgorithms::Siphash => Box::new(SipHash::new(&decode_key(self.key.as_ref().ok_or("SipHash requires a 16 byte key")?, &self.key_format)?)?),
+            _ => unreachable!()
+        });
+    }
+
+    fn hash(&self) -> Result<Vec<u8>, String> {
+        let mut hasher: Box<dyn HashFunction> = self.hasher()?;
+
+        match &self.message {
+            Some(message) => hasher.update(message.as_bytes()),
+            None => hash_reader(hasher.as_mut(), open_input(self.file.as_deref())?)?
+        }
+        return Ok(hasher.finalize());
+    }
+}
+
+#[cfg(test)]
+mod non_crypto_hash_test {
+    use super::*;
+
+    #[test]
+    fn non_crypto_hash_test() -> () {
+        let instance_1: NonCryptoHashAlg = NonCryptoHashAlg {
+            algorithm: NonCryptoHashAlgorithms::Crc32,
+            message: Some("123456789".to_string()),
+            file: None,
+            key: None,
+            key_format: KeyFormat::Text,
+            polynomial: None,
+            init: None,
+            xor_out: None,
+            reflect: None
+        };
+        assert_eq!(instance_1.hash().map(|hash: Vec<u8>| bytes_to_hex(&hash)), Ok("cbf43926".to_string()));
+
+        let instance_2: NonCryptoHashAlg = NonCryptoHashAlg {
+            polynomial: Some("0x1edc6f41".to_string()),
+            ..instance_1.clone()
+        };
+        assert_eq!(instance_2.hash().map(|hash: Vec<u8>| bytes_to_hex(&hash)), Ok("e3069283".to_string()));
+
+        let instance_3: NonCryptoHashAlg = NonCryptoHashAlg {
+            algorithm: NonCryptoHashAlgorithms::Adler32,
+            ..instance_2.clone()
+        };
+        assert!(instance_3.hash().is_err());
+
+        let instance_4: NonCryptoHashAlg = NonCryptoHashAlg {
+            algorithm: NonCryptoHashAlgorithms::Siphash,
+            message: Some("".to_string()),
+            key: Some("000102030405060708090a0b0c0d0e0f".to_string()),
+            key_format: KeyFormat::Hex,
+            ..instance_1.clone()
+        };
+        assert_eq!(instance_4.hash().map(|hash: Vec<u8>| bytes_to_hex(&hash)), Ok("310e0edd47db6f72".to_string()));
+
+        let instance_5: NonCryptoHashAlg = NonCryptoHashAlg {
+            key: None,
+            ..instance_4.clone()
+        };
+        assert!(instance_5.hash().is_err());
+
+        let instance_6: NonCryptoHashAlg = NonCryptoHashAlg {
+            key: Some("secret".to_string()),
+            ..instance_1.clone()
+        };
+        assert!(instance_6.hash().is_err());
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{hashes::merkle_damgard::{CompressionFunction, MerkleDamgard}, tables::{RIPEMD160_SHIFT_TABLE, RIPEMD160_WORD_ORDER_TABLE}};
+
+const RIPEMD160_ROUND_CONSTANTS: [[u32; 5]; 2] = [ [0x00000000, 0x5a827999, 0x6ed9eba1, 0x8f1bbcdc, 0xa953fd4e],
+                                                   [0x50a28be6, 0x5c4dd124, 0x6d703ef3, 0x7a6d76e9, 0x00000000] ];
+
+pub type Ripemd160 = MerkleDamgard<Ripemd160Compression>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ripemd160Compression {
+    pub state: [u32; 5]
+}
+impl Ripemd160Compression {
+    pub fn new() -> Ripemd160Compression {
+        return Ripemd160Compression { state: [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0] };
+    }
+}
+
+fn boolean_function(round: usize, x: u32, y: u32, z: u32) -> u32 {
+    match round {
+        0 => x ^ y ^ z,
+        1 => (x & y) | (!x & z),
+        2 => (x | !y) ^ z,
+        3 => (x & z) | (y & !z),
+        _ => x ^ (y | !z)
+    }
+}
+
+impl CompressionFunction for Ripemd160Compression {
+    fn block_size(&self) -> usize {
+        return 64;
+    }
+
+    fn output_size(&self) -> usize {
+        return 20;
+    }
+
+    fn big_endian_length(&self) -> bool {
+        return false;
+    }
+
+    fn compress(&mut self, block: &[u8]) -> () {
+        let x: Vec<u32> = block.chunks(4).map(|chunk: &[u8]| u32::from_le_bytes(chunk.try_into().unwrap())).collect();
+        let mut lines: [[u32; 5]; 2] = [self.state; 2];
+
+        // The left and right lines run the same rounds with the boolean functions in opposite order
+        for line in 0..2 {
+            let [mut a, mut b, mut c, mut d, mut e]: [u32; 5] = lines[line];
+
+            for round in 0..5 {
+                let function: usize = if line == 0 { round } else { 4 - round };
+
+                for step in 0..16 {
+                    let rotated: u32 = a.wrapping_add(boolean_function(function, b, c, d))
+                                        .wrapping_add(x[RIPEMD160_WORD_ORDER_TABLE[line][round][step]])
+                                        .wrapping_add(RIPEMD160_ROUND_CONSTANTS[line][round])
+                                        .rotate_left(RIPEMD160_SHIFT_TABLE[line][round][step])
+                                        .wrapping_add(e);
+                    a = e;
+                    e = d;
+                    d = c.rotate_left(10);
+                    c = b;
+                    b = rotated;
+                }
+            }
+            lines[line] = [a, b, c, d, e];
+        }
+
+        let [left, right]: [[u32; 5]; 2] = lines;
+        let h: [u32; 5] = self.state;
+
+        self.state = [h[1].wrapping_add(left[2]).wrapping_add(right[3]),
+                      h[2].wrapping_add(left[3]).wrapping_add(right[4]),
+                      h[3].wrapping_add(left[4]).wrapping_add(right[0]),
+                      h[4].wrapping_add(left[0]).wrapping_add(right[1]),
+                      h[0].wrapping_add(left[1]).wrapping_add(right[2])];
+    }
+
+    fn digest(&self) -> Vec<u8> {
+        return self.state.iter().flat_map(|word: &u32| word.to_le_bytes()).collect();
+    }
+}
+
+pub fn new_ripemd160() -> Ripemd160 {
+    return MerkleDamgard::new(Ripemd160Compression::new());
+}
+
+#[cfg(test)]
+mod ripemd160_test {
+    use crate::{hashes::hash_function::{digest, HashAlgorithms}, ops::bytes_to_hex};
+
+    fn ripemd160_hex(message: &[u8]) -> String {
+        return bytes_to_hex(&digest(&HashAlgorithms::Ripemd160, message));
+    }
+
+    #[test]
+    fn reference_vectors_test() -> () {
+        assert_eq!(ripemd160_hex(b""), "9c1185a5c5e9fc54612808977ee8f548b2258d31");
+        assert_eq!(ripemd160_hex(b"a"), "0bdc9d2d256b3ee9daae347be6f4dc835a467ffe");
+        assert_eq!(ripemd160_hex(b"abc"), "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc");
+        assert_eq!(ripemd160_hex(b"message digest"), "5d0689ef49d2fae572b881b123a85ffa21595f36");
+        assert_eq!(ripemd160_hex(b"abcdefghijklmnopqrstuvwxyz"), "f71c27109c692c1b56bbdceb5b9d2865b3708dbc");
+        assert_eq!(ripemd160_hex(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"), "12a053384a9c0c88e405a06c27dcf49ada62eb2b");
+        assert_eq!(ripemd160_hex(&vec![b'a'; 1000000]), "52783243c1697bdbe16d37f97f68f08325dc1528");
+    }
+}
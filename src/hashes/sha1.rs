@@ -0,0 +1,106 @@
+use crate::{hashes::merkle_damgard::{CompressionFunction, MerkleDamgard}, tables::{SHA1_INITIAL_STATE, SHA1_ROUND_CONSTANTS}};
+
+pub type Sha1 = MerkleDamgard<Sha1Compression>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sha1Compression {
+    pub state: [u32; 5]
+}
+impl Sha1Compression {
+    pub fn new() -> Sha1Compression {
+        return Sha1Compression { state: SHA1_INITIAL_STATE };
+    }
+}
+
+impl CompressionFunction for Sha1Compression {
+    fn block_size(&self) -> usize {
+        return 64;
+    }
+
+    fn output_size(&self) -> usize {
+        return 20;
+    }
+
+    fn big_endian_length(&self) -> bool {
+        return true;
+    }
+
+    fn compress(&mut self, block: &[u8]) -> () {
+        let mut w: [u32; 80] = [0; 80];
+
+        for (i, chunk) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes(chunk.try_into().unwrap());
+        }
+
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e]: [u32; 5] = self.state;
+
+        for i in 0..80 {
+            let f: u32 = match i / 20 {
+                0 => (b & c) | (!b & d),
+                2 => (b & c) | (b & d) | (c & d),
+                _ => b ^ c ^ d
+            };
+
+            let temp: u32 = a.rotate_left(5)
+                             .wrapping_add(f)
+                             .wrapping_add(e)
+                             .wrapping_add(SHA1_ROUND_CONSTANTS[i / 20])
+                             .wrapping_add(w[i]);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+
+        for (word, value) in self.state.iter_mut().zip([a, b, c, d, e]) {
+            *word = word.wrapping_add(value);
+        }
+    }
+
+    fn digest(&self) -> Vec<u8> {
+        return self.state.iter().flat_map(|word: &u32| word.to_be_bytes()).collect();
+    }
+}
+
+pub fn new_sha1() -> Sha1 {
+    return MerkleDamgard::new(Sha1Compression::new());
+}
+
+#[cfg(test)]
+mod sha1_test {
+    use crate::{hashes::hash_function::{digest, HashAlgorithms}, ops::{bytes_to_hex, hex_to_bytes}};
+
+    fn sha1_hex(message: &[u8]) -> String {
+        return bytes_to_hex(&digest(&HashAlgorithms::Sha1, message));
+    }
+
+    #[test]
+    fn fips_180_examples_test() -> () {
+        assert_eq!(sha1_hex(b""), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+        assert_eq!(sha1_hex(b"abc"), "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_eq!(sha1_hex(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"), "84983e441c3bd26ebaae4aa1f95129e5e54670f1");
+        assert_eq!(sha1_hex(&vec![b'a'; 1000000]), "34aa973cd4c4daa4f61eeb2bdbad27316534016f");
+    }
+
+    #[test]
+    fn cavp_short_msg_test() -> () {
+        assert_eq!(sha1_hex(&hex_to_bytes("36").unwrap()), "c1dfd96eea8cc2b62785275bca38ac261256e278");
+        assert_eq!(sha1_hex(&hex_to_bytes("195a").unwrap()), "0a1c2d555bbe431ad6288af5a54f93e0449c9232");
+        assert_eq!(sha1_hex(&hex_to_bytes("df4bd2").unwrap()), "bf36ed5d74727dfd5d7854ec6b1d49468d8ee8aa");
+        assert_eq!(sha1_hex(&hex_to_bytes("549e959e").unwrap()), "b78bae6d14338ffccfd5d5b5674a275f6ef9c717");
+        assert_eq!(sha1_hex(&hex_to_bytes("f7fb1be205").unwrap()), "60b7d5bb560a1acf6fa45721bd0abb419a841a89");
+        assert_eq!(sha1_hex(&hex_to_bytes("c0e5abeaea63").unwrap()), "a6d338459780c08363090fd8fc7d28dc80e8e01f");
+    }
+
+    // First vector of SHA1LongMsg.rsp, 1304 bits spanning three blocks
+    #[test]
+    fn cavp_long_msg_test() -> () {
+        let message: Vec<u8> = hex_to_bytes("7c9c67323a1df1adbfe5ceb415eaef0155ece2820f4d50c1ec22cba4928ac656c83fe585db6a78ce40bc42757aba7e5a3f582428d6ca68d0c3978336a6efb729613e8d9979016204bfd921322fdd5222183554447de5e6e9bbe6edf76d7b71e18dc2e8d6dc89b7398364f652fafc734329aafa3dcd45d4f31e388e4fafd7fc6495f37ca5cbab7f54d586463da4bfeaa3bae09f7b8e9239d832b4f0a733aa609cc1f8d4").unwrap();
+        assert_eq!(sha1_hex(&message), "d8fd6a91ef3b6ced05b98358a99107c1fac8c807");
+    }
+}
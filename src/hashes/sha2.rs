@@ -0,0 +1,266 @@
+use crate::{hashes::merkle_damgard::{CompressionFunction, MerkleDamgard}, tables::{SHA224_INITIAL_STATE, SHA256_INITIAL_STATE, SHA256_ROUND_CONSTANTS, SHA384_INITIAL_STATE, SHA512_224_INITIAL_STATE, SHA512_256_INITIAL_STATE, SHA512_INITIAL_STATE, SHA512_ROUND_CONSTANTS}};
+
+pub type Sha256 = MerkleDamgard<Sha256Compression>;
+pub type Sha512 = MerkleDamgard<Sha512Compression>;
+
+// SHA-224 and SHA-256 share the 32-bit word compression
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sha256Compression {
+    pub state: [u32; 8],
+    pub output_size: usize
+}
+
+impl CompressionFunction for Sha256Compression {
+    fn block_size(&self) -> usize {
+        return 64;
+    }
+
+    fn output_size(&self) -> usize {
+        return self.output_size;
+    }
+
+    fn big_endian_length(&self) -> bool {
+        return true;
+    }
+
+    fn compress(&mut self, block: &[u8]) -> () {
+        let mut w: [u32; 64] = [0; 64];
+
+        for (i, chunk) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes(chunk.try_into().unwrap());
+        }
+
+        for i in 16..64 {
+            let s0: u32 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1: u32 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h]: [u32; 8] = self.state;
+
+        for i in 0..64 {
+            let sum_1: u32 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let choice: u32 = (e & f) ^ (!e & g);
+            let temp_1: u32 = h.wrapping_add(sum_1).wrapping_add(choice).wrapping_add(SHA256_ROUND_CONSTANTS[i]).wrapping_add(w[i]);
+            let sum_0: u32 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let majority: u32 = (a & b) ^ (a & c) ^ (b & c);
+            let temp_2: u32 = sum_0.wrapping_add(majority);
+
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(temp_1);
+            d = c;
+            c = b;
+            b = a;
+            a = temp_1.wrapping_add(temp_2);
+        }
+
+        for (word, value) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *word = word.wrapping_add(value);
+        }
+    }
+
+    fn digest(&self) -> Vec<u8> {
+        return self.state.iter().flat_map(|word: &u32| word.to_be_bytes()).take(self.output_size).collect();
+    }
+}
+
+
+
+
+// SHA-384, SHA-512, SHA-512/224 and SHA-512/256 share the 64-bit word compression
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sha512Compression {
+    pub state: [u64; 8],
+    pub output_size: usize
+}
+
+impl CompressionFunction for Sha512Compression {
+    fn block_size(&self) -> usize {
+        return 128;
+    }
+
+    fn output_size(&self) -> usize {
+        return self.output_size;
+    }
+
+    fn big_endian_length(&self) -> bool {
+        return true;
+    }
+
+    fn length_size(&self) -> usize {
+        return 16;
+    }
+
+    fn compress(&mut self, block: &[u8]) -> () {
+        let mut w: [u64; 80] = [0; 80];
+
+        for (i, chunk) in block.chunks(8).enumerate() {
+            w[i] = u64::from_be_bytes(chunk.try_into().unwrap());
+        }
+
+        for i in 16..80 {
+            let s0: u64 = w[i - 15].rotate_right(1) ^ w[i - 15].rotate_right(8) ^ (w[i - 15] >> 7);
+            let s1: u64 = w[i - 2].rotate_right(19) ^ w[i - 2].rotate_right(61) ^ (w[i - 2] >> 6);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h]: [u64; 8] = self.state;
+
+        for i in 0..80 {
+            let sum_1: u64 = e.rotate_right(14) ^ e.rotate_right(18) ^ e.rotate_right(41);
+            let choice: u64 = (e & f) ^ (!e & g);
+            let temp_1: u64 = h.wrapping_add(sum_1).wrapping_add(choice).wrapping_add(SHA512_ROUND_CONSTANTS[i]).wrapping_add(w[i]);
+            let sum_0: u64 = a.rotate_right(28) ^ a.rotate_right(34) ^ a.rotate_right(39);
+            let majority: u64 = (a & b) ^ (a & c) ^ (b & c);
+            let temp_2: u64 = sum_0.wrapping_add(majority);
+
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(temp_1);
+            d = c;
+            c = b;
+            b = a;
+            a = temp_1.wrapping_add(temp_2);
+        }
+
+        for (word, value) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *word = word.wrapping_add(value);
+        }
+    }
+
+    fn digest(&self) -> Vec<u8> {
+        return self.state.iter().flat_map(|word: &u64| word.to_be_bytes()).take(self.output_size).collect();
+    }
+}
+
+
+
+
+pub fn new_sha224() -> Sha256 {
+    return MerkleDamgard::new(Sha256Compression { state: SHA224_INITIAL_STATE, output_size: 28 });
+}
+
+pub fn new_sha256() -> Sha256 {
+    return MerkleDamgard::new(Sha256Compression { state: SHA256_INITIAL_STATE, output_size: 32 });
+}
+
+pub fn new_sha384() -> Sha512 {
+    return MerkleDamgard::new(Sha512Compression { state: SHA384_INITIAL_STATE, output_size: 48 });
+}
+
+pub fn new_sha512() -> Sha512 {
+    return MerkleDamgard::new(Sha512Compression { state: SHA512_INITIAL_STATE, output_size: 64 });
+}
+
+pub fn new_sha512_224() -> Sha512 {
+    return MerkleDamgard::new(Sha512Compression { state: SHA512_224_INITIAL_STATE, output_size: 28 });
+}
+
+pub fn new_sha512_256() -> Sha512 {
+    return MerkleDamgard::new(Sha512Compression { state: SHA512_256_INITIAL_STATE, output_size: 32 });
+}
+
+#[cfg(test)]
+mod sha2_test {
+    use crate::{hashes::hash_function::{digest, HashAlgorithms}, ops::{bytes_to_hex, hex_to_bytes}};
+
+    const MESSAGE_448_BITS: &[u8] = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+    const MESSAGE_896_BITS: &[u8] = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu";
+    // First vectors of the CAVP SHA224, SHA256, SHA384 and SHA512 LongMsg.rsp files
+    const LONG_MSG_224: &str = "f149e41d848f59276cfddd743bafa9a90e1ee4a263a118142b33e3702176ef0a59f8237a1cb51b42f3ded6b202d9af0997898fdd03cf60bda951c514547a0850cec25444ae2f24cb711bfbafcc3956c941d3de69f155e3f8b10f06db5f37359b772ddd43e1035a0a0d3db33242d5843033833b0dd43b870c6bf60e8deab55f317cc3273f5e3ba747f0cb65050cb7228796210d9254873643008d45f29cfd6c5b060c9a";
+    const LONG_MSG_256: &str = "451101250ec6f26652249d59dc974b7361d571a8101cdfd36aba3b5854d3ae086b5fdd4597721b66e3c0dc5d8c606d9657d0e323283a5217d1f53f2f284f57b85c8a61ac8924711f895c5ed90ef17745ed2d728abd22a5f7a13479a462d71b56c19a74a40b655c58edfe0a188ad2cf46cbf30524f65d423c837dd1ff2bf462ac4198007345bb44dbb7b1c861298cdf61982a833afc728fae1eda2f87aa2c9480858bec";
+    const LONG_MSG_384: &str = "62c6a169b9be02b3d7b471a964fc0bcc72b480d26aecb2ed460b7f50016ddaf04c51218783f3aadfdff5a04ded030d7b3fb7376b61ba30b90e2da921a4470740d63fb99fa16cc8ed81abaf8ce4016e50df81da832070372c24a80890aa3a26fa675710b8fb718266249d496f313c55d0bada101f8f56eeccee4345a8f98f60a36662cfda794900d12f9414fcbdfdeb85388a814996b47e24d5c8086e7a8edcc53d299d0d033e6bb60c58b83d6e8b57f6c258d6081dd10eb942fdf8ec157ec3e75371235a8196eb9d22b1de3a2d30c2abbe0db7650cf6c7159bacbe29b3a93c92100508";
+    const LONG_MSG_512: &str = "4f05600950664d5190a2ebc29c9edb89c20079a4d3e6bc3b27d75e34e2fa3d02768502bd69790078598d5fcf3d6779bfed1284bbe5ad72fb456015181d9587d6e864c940564eaafb4f2fead4346ea09b6877d9340f6b82eb1515880872213da3ad88feba9f4f13817a71d6f90a1a17c43a15c038d988b5b29edffe2d6a062813cedbe852cde302b3e33b696846d2a8e36bd680efcc6cd3f9e9a4c1ae8cac10cc5244d131677140399176ed46700019a004a163806f7fa467fc4e17b4617bbd7641aaff7ff56396ba8c08a8be100b33a20b5daf134a2aefa5e1c3496770dcf6baa4f7bb";
+
+    fn sha_hex(algorithm: HashAlgorithms, message: &[u8]) -> String {
+        return bytes_to_hex(&digest(&algorithm, message));
+    }
+
+    #[test]
+    fn sha224_test() -> () {
+        assert_eq!(sha_hex(HashAlgorithms::Sha224, b""), "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f");
+        assert_eq!(sha_hex(HashAlgorithms::Sha224, b"abc"), "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7");
+        assert_eq!(sha_hex(HashAlgorithms::Sha224, MESSAGE_448_BITS), "75388b16512776cc5dba5da1fd890150b0c6455cb4f58b1952522525");
+        assert_eq!(sha_hex(HashAlgorithms::Sha224, &hex_to_bytes("84").unwrap()), "3cd36921df5d6963e73739cf4d20211e2d8877c19cff087ade9d0e3a");
+        assert_eq!(sha_hex(HashAlgorithms::Sha224, &vec![b'a'; 1000000]), "20794655980c91d8bbb4c1ea97618a4bf03f42581948b2ee4ee7ad67");
+    }
+
+    #[test]
+    fn sha256_test() -> () {
+        assert_eq!(sha_hex(HashAlgorithms::Sha256, b""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(sha_hex(HashAlgorithms::Sha256, b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(sha_hex(HashAlgorithms::Sha256, MESSAGE_448_BITS), "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1");
+        assert_eq!(sha_hex(HashAlgorithms::Sha256, &hex_to_bytes("d3").unwrap()), "28969cdfa74a12c82f3bad960b0b000aca2ac329deea5c2328ebc6f2ba9802c1");
+        assert_eq!(sha_hex(HashAlgorithms::Sha256, &hex_to_bytes("11af").unwrap()), "5ca7133fa735326081558ac312c620eeca9970d1e70a4b95533d956f072d1f98");
+        assert_eq!(sha_hex(HashAlgorithms::Sha256, &vec![b'a'; 1000000]), "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0");
+    }
+
+    #[test]
+    fn sha384_test() -> () {
+        assert_eq!(sha_hex(HashAlgorithms::Sha384, b""), "38b060a751ac96384cd9327eb1b1e36a21fdb71114be07434c0cc7bf63f6e1da274edebfe76f65fbd51ad2f14898b95b");
+        assert_eq!(sha_hex(HashAlgorithms::Sha384, b"abc"), "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed8086072ba1e7cc2358baeca134c825a7");
+        assert_eq!(sha_hex(HashAlgorithms::Sha384, MESSAGE_896_BITS), "09330c33f71147e83d192fc782cd1b4753111b173b3b05d22fa08086e3b0f712fcc7c71a557e2db966c3e9fa91746039");
+        assert_eq!(sha_hex(HashAlgorithms::Sha384, &hex_to_bytes("c5").unwrap()), "b52b72da75d0666379e20f9b4a79c33a329a01f06a2fb7865c9062a28c1de860ba432edfd86b4cb1cb8a75b46076e3b1");
+        assert_eq!(sha_hex(HashAlgorithms::Sha384, &vec![b'a'; 1000000]), "9d0e1809716474cb086e834e310a4a1ced149e9c00f248527972cec5704c2a5b07b8b3dc38ecc4ebae97ddd87f3d8985");
+    }
+
+    #[test]
+    fn sha512_test() -> () {
+        assert_eq!(sha_hex(HashAlgorithms::Sha512, b""), "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e");
+        assert_eq!(sha_hex(HashAlgorithms::Sha512, b"abc"), "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f");
+        assert_eq!(sha_hex(HashAlgorithms::Sha512, MESSAGE_896_BITS), "8e959b75dae313da8cf4f72814fc143f8f7779c6eb9f7fa17299aeadb6889018501d289e4900f7e4331b99dec4b5433ac7d329eeb6dd26545e96e55b874be909");
+        assert_eq!(sha_hex(HashAlgorithms::Sha512, &hex_to_bytes("21").unwrap()), "3831a6a6155e509dee59a7f451eb35324d8f8f2df6e3708894740f98fdee23889f4de5adb0c5010dfb555cda77c8ab5dc902094c52de3278f35a75ebc25f093a");
+        assert_eq!(sha_hex(HashAlgorithms::Sha512, &vec![b'a'; 1000000]), "e718483d0ce769644e2e42c7bc15b4638e1f98b13b2044285632a803afa973ebde0ff244877ea60a4cb0432ce577c31beb009c5c2c49aa2e4eadb217ad8cc09b");
+    }
+
+    #[test]
+    fn sha512_t_test() -> () {
+        assert_eq!(sha_hex(HashAlgorithms::Sha512T224, b"abc"), "4634270f707b6a54daae7530460842e20e37ed265ceee9a43e8924aa");
+        assert_eq!(sha_hex(HashAlgorithms::Sha512T224, MESSAGE_896_BITS), "23fec5bb94d60b23308192640b0c453335d664734fe40e7268674af9");
+        assert_eq!(sha_hex(HashAlgorithms::Sha512T224, &hex_to_bytes("cf").unwrap()), "4199239e87d47b6feda016802bf367fb6e8b5655eff6225cb2668f4a");
+        assert_eq!(sha_hex(HashAlgorithms::Sha512T256, b"abc"), "53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23");
+        assert_eq!(sha_hex(HashAlgorithms::Sha512T256, MESSAGE_896_BITS), "3928e184fb8690f840da3988121d31be65cb9d3ef83ee6146feac861e19b563a");
+        assert_eq!(sha_hex(HashAlgorithms::Sha512T256, &hex_to_bytes("fa").unwrap()), "c4ef36923c64e51e875720e550298a5ab8a3f2f875b1e1a4c9b95babf7344fef");
+    }
+
+    #[test]
+    fn cavp_short_msg_test() -> () {
+        assert_eq!(sha_hex(HashAlgorithms::Sha224, &hex_to_bytes("5c7b").unwrap()), "daff9bce685eb831f97fc1225b03c275a6c112e2d6e76f5faf7a36e6");
+        assert_eq!(sha_hex(HashAlgorithms::Sha224, &hex_to_bytes("51ca3d").unwrap()), "2c8959023515476e38388abb43599a29876b4b33d56adc06032de3a2");
+        assert_eq!(sha_hex(HashAlgorithms::Sha256, &hex_to_bytes("b4190e").unwrap()), "dff2e73091f6c05e528896c4c831b9448653dc2ff043528f6769437bc7b975c2");
+        assert_eq!(sha_hex(HashAlgorithms::Sha256, &hex_to_bytes("74ba2521").unwrap()), "b16aa56be3880d18cd41e68384cf1ec8c17680c45a02b1575dc1518923ae8b0e");
+        assert_eq!(sha_hex(HashAlgorithms::Sha256, &hex_to_bytes("c299209682").unwrap()), "f0887fe961c9cd3beab957e8222494abb969b1ce4c6557976df8b0f6d20e9166");
+        assert_eq!(sha_hex(HashAlgorithms::Sha384, &hex_to_bytes("6ece").unwrap()), "53d4773da50d8be4145d8f3a7098ff3691a554a29ae6f652cc7121eb8bc96fd2210e06ae2fa2a36c4b3b3497341e70f0");
+        assert_eq!(sha_hex(HashAlgorithms::Sha384, &hex_to_bytes("1fa4d5").unwrap()), "e4ca4663dff189541cd026dcc056626419028774666f5b379b99f4887c7237bdbd3bea46d5388be0efc2d4b7989ab2c4");
+        assert_eq!(sha_hex(HashAlgorithms::Sha512, &hex_to_bytes("9083").unwrap()), "55586ebba48768aeb323655ab6f4298fc9f670964fc2e5f2731e34dfa4b0c09e6e1e12e3d7286b3145c61c2047fb1a2a1297f36da64160b31fa4c8c2cddd2fb4");
+        assert_eq!(sha_hex(HashAlgorithms::Sha512, &hex_to_bytes("0a55db").unwrap()), "7952585e5330cb247d72bae696fc8a6b0f7d0804577e347d99bc1b11e52f384985a428449382306a89261ae143c2f3fb613804ab20b42dc097e5bf4a96ef919b");
+        assert_eq!(sha_hex(HashAlgorithms::Sha512T224, &hex_to_bytes("ca2d").unwrap()), "392b99b593b85e147f031986c2a9edfdb4ffd9f24c77c452d339c9fc");
+        assert_eq!(sha_hex(HashAlgorithms::Sha512T256, &hex_to_bytes("74e4").unwrap()), "0c994228b8d3bd5ea5b5259157a9bba7a193118ad22817e6fbed2df1a32a4148");
+    }
+
+    #[test]
+    fn cavp_long_msg_test() -> () {
+        assert_eq!(sha_hex(HashAlgorithms::Sha224, &hex_to_bytes(LONG_MSG_224).unwrap()), "9db6dc3a23abd7b6c3d72c38f4843c7de48a71d0ba91a86b18393e5f");
+        assert_eq!(sha_hex(HashAlgorithms::Sha256, &hex_to_bytes(LONG_MSG_256).unwrap()), "3c593aa539fdcdae516cdf2f15000f6634185c88f505b39775fb9ab137a10aa2");
+        assert_eq!(sha_hex(HashAlgorithms::Sha384, &hex_to_bytes(LONG_MSG_384).unwrap()), "0730e184e7795575569f87030260bb8e54498e0e5d096b18285e988d245b6f3486d1f2447d5f85bcbe59d5689fc49425");
+        assert_eq!(sha_hex(HashAlgorithms::Sha512, &hex_to_bytes(LONG_MSG_512).unwrap()), "a9db490c708cc72548d78635aa7da79bb253f945d710e5cb677a474efc7c65a2aab45bc7ca1113c8ce0f3c32e1399de9c459535e8816521ab714b2a6cd200525");
+        // The truncated variants share the SHA-512 compression, checked on the same two block message against OpenSSL
+        assert_eq!(sha_hex(HashAlgorithms::Sha512T224, &hex_to_bytes(LONG_MSG_512).unwrap()), "e1552e2fb577aad369b8ac333cbc7f60ae36514c409328aa2be19d77");
+        assert_eq!(sha_hex(HashAlgorithms::Sha512T256, &hex_to_bytes(LONG_MSG_512).unwrap()), "e772f8a9591cf1285da5c7138b63276d033dfd6e0a580df8e12a897cdab9fde2");
+    }
+
+    #[test]
+    fn padding_boundaries_test() -> () {
+        let expected_sha256: [&str; 4] = ["9f4390f8d30c2dd92ec9f095b65e2b9ae9b0a925a5258e241c9f1e910f734318",
+                                          "b35439a4ac6f0948b6d6f9e3c6af0f5f590ce20f1bde7090ef7970686ec6738a",
+                                          "7d3e74a05d7db15bce4ad9ec0658ea98e3f06eeecf16b4c6fff2da457ddc2f34",
+                                          "ffe054fe7ae0cb6dc65c3af9b61d5209f439851db43d0ba5997337df154668eb"];
+
+        for (len, expected) in [55, 56, 63, 64].iter().zip(expected_sha256.iter()) {
+            assert_eq!(sha_hex(HashAlgorithms::Sha256, &vec![b'a'; *len]), *expected);
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use crate::{hashes::hash_function::HashFunction, tables::{KECCAK_ROTATION_OFFSETS, KECCAK_ROUND_CONSTANTS}};
+
+const KECCAK_PADDING: u8 = 0x01;
+const SHA3_PADDING: u8 = 0x06;
+const SHAKE_PADDING: u8 = 0x1f;
+const CSHAKE_PADDING: u8 = 0x04;
+
+pub fn keccak_f1600(state: &mut [u64; 25]) -> () {
+    for round_constant in KECCAK_ROUND_CONSTANTS {
+        // theta
+        let mut columns: [u64; 5] = [0; 5];
+        for x in 0..5 {
+            columns[x] = state[x] ^ state[x + 5] ^ state[x + 10] ^ state[x + 15] ^ state[x + 20];
+        }
+        for x in 0..5 {
+            let d: u64 = columns[(x + 4) % 5] ^ columns[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                state[x + 5 * y] ^= d;
+            }
+        }
+
+        // rho and pi
+        let mut lanes: [u64; 25] = [0; 25];
+        for x in 0..5 {
+            for y in 0..5 {
+                lanes[y + 5 * ((2 * x + 3 * y) % 5)] = state[x + 5 * y].rotate_left(KECCAK_ROTATION_OFFSETS[y][x]);
+            }
+        }
+
+        // chi
+        for y in 0..5 {
+            for x in 0..5 {
+                state[x + 5 * y] = lanes[x + 5 * y] ^ (!lanes[(x + 1) % 5 + 5 * y] & lanes[(x + 2) % 5 + 5 * y]);
+            }
+        }
+
+        // iota
+        state[0] ^= round_constant;
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sponge {
+    pub state: [u64; 25],
+    rate: usize,
+    buffer: Vec<u8>,
+    padding: u8,
+    output_size: usize
+}
+impl Sponge {
+    pub fn new(rate: usize, padding: u8, output_size: usize) -> Sponge {
+        return Sponge {
+            state: [0; 25],
+            rate: rate,
+            buffer: Vec::new(),
+            padding: padding,
+            output_size: output_size
+        };
+    }
+
+    fn absorb_block(&mut self, block: &[u8]) -> () {
+        for (lane, chunk) in self.state.iter_mut().zip(block.chunks(8)) {
+            *lane ^= u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+        keccak_f1600(&mut self.state);
+    }
+
+    pub fn squeeze(&self, length: usize) -> Vec<u8> {
+        let mut sponge: Sponge = self.clone();
+        let mut last_block: Vec<u8> = sponge.buffer.clone();
+
+        last_block.push(sponge.padding);
+        last_block.resize(sponge.rate, 0);
+        last_block[sponge.rate - 1] |= 0x80;
+        sponge.absorb_block(&last_block);
+
+        let mut output: Vec<u8> = Vec::with_capacity(length);
+        loop {
+            let block: Vec<u8> = sponge.state.iter().flat_map(|lane: &u64| lane.to_le_bytes()).take(sponge.rate).collect();
+            let needed: usize = (length - output.len()).min(sponge.rate);
+            output.extend_from_slice(&block[..needed]);
+
+            if output.len() == length {
+                return output;
+            }
+            keccak_f1600(&mut sponge.state);
+        }
+    }
+}
+
+impl HashFunction for Sponge {
+    fn update(&mut self, data: &[u8]) -> () {
+        self.buffer.extend_from_slice(data);
+
+        let full: usize = self.buffer.len() - self.buffer.len() % self.rate;
+        let blocks: Vec<u8> = self.buffer.drain(..full).collect();
+
+        for block in blocks.chunks(self.rate) {
+            self.absorb_block(block);
+        }
+    }
+
+    fn finalize(&self) -> Vec<u8> {
+        return self.squeeze(self.output_size);
+    }
+
+    fn block_size(&self) -> usize {
+        return self.rate;
+    }
+
+    fn output_size(&self) -> usize {
+        return self.output_size;
+    }
+}
+
+
+
+
+// SP 800-185 encodings
+pub fn left_encode(value: u64) -> Vec<u8> {
+    let bytes: Vec<u8> = value.to_be_bytes().into_iter().skip_while(|byte: &u8| *byte == 0).collect();
+    let bytes: Vec<u8> = if bytes.is_empty() { vec![0] } else { bytes };
+
+    return [vec![bytes.len() as u8], bytes].concat();
+}
+
+
+
+
+pub fn right_encode(value: u64) -> Vec<u8> {
+    let mut encoded: Vec<u8> = left_encode(value);
+    let length: u8 = encoded.remove(0);
+    encoded.push(length);
+
+    return encoded;
+}
+
+
+
+
+pub fn encode_string(data: &[u8]) -> Vec<u8> {
+    return [left_encode(8 * data.len() as u64), data.to_vec()].concat();
+}
+
+
+
+
+pub fn bytepad(data: &[u8], width: usize) -> Vec<u8> {
+    let mut padded: Vec<u8> = [left_encode(width as u64), data.to_vec()].concat();
+    padded.resize(padded.len().div_ceil(width) * width, 0);
+
+    return padded;
+}
+
+
+
+
+fn rate_for(security_bits: usize) -> usize {
+    return 200 - security_bits / 4;
+}
+
+pub fn new_sha3(output_size: usize) -> Sponge {
+    return Sponge::new(rate_for(8 * output_size), SHA3_PADDING, output_size);
+}
+
+pub fn new_keccak(output_size: usize) -> Sponge {
+    return Sponge::new(rate_for(8 * output_size), KECCAK_PADDING, output_size);
+}
+
+pub fn new_shake(security: usize, output_size: usize) -> Sponge {
+    return Sponge::new(rate_for(security), SHAKE_PADDING, output_size);
+}
+
+pub fn new_cshake(security: usize, output_size: usize, function_name: &[u8], customization: &[u8]) -> Sponge {
+    if function_name.is_empty() && customization.is_empty() {
+        return new_shake(security, output_size);
+    }
+
+    let mut sponge: Sponge = Sponge::new(rate_for(security), CSHAKE_PADDING, output_size);
+    let rate: usize = sponge.rate;
+    sponge.update(&bytepad(&[encode_string(function_name), encode_string(customization)].concat(), rate));
+
+    return sponge;
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Kmac {
+    sponge: Sponge
+}
+impl Kmac {
+    pub fn new(security: usize, key: &[u8], output_size: usize, customization: &[u8]) -> Kmac {
+        let mut sponge: Sponge = new_cshake(security, output_size, b"KMAC", customization);
+        let rate: usize = sponge.rate;
+        sponge.update(&bytepad(&encode_string(key), rate));
+
+        return Kmac { sponge: sponge };
+    }
+}
+
+impl HashFunction for Kmac {
+    fn update(&mut self, data: &[u8]) -> () {
+        self.sponge.update(data);
+    }
+
+    fn finalize(&self) -> Vec<u8> {
+        let mut sponge: Sponge = self.sponge.clone();
+        sponge.update(&right_encode(8 * sponge.output_size as u64));
+
+        return sponge.finalize();
+    }
+
+    fn block_size(&self) -> usize {
+        return self.sponge.rate;
+    }
+
+    fn output_size(&self) -> usize {
+        return self.sponge.output_size;
+    }
+}
+
+#[cfg(test)]
+mod sha3_test {
+    use crate::ops::{bytes_to_hex, hex_to_bytes};
+    use super::*;
+
+    fn hex_of(mut hasher: impl HashFunction, message: &[u8]) -> String {
+        hasher.update(message);
+        return bytes_to_hex(&hasher.finalize());
+    }
+
+    #[test]
+    fn sha3_test() -> () {
+        assert_eq!(hex_of(new_sha3(28), b""), "6b4e03423667dbb73b6e15454f0eb1abd4597f9a1b078e3f5b5a6bc7");
+        assert_eq!(hex_of(new_sha3(32), b""), "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a");
+        assert_eq!(hex_of(new_sha3(32), b"abc"), "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532");
+        assert_eq!(hex_of(new_sha3(48), b"abc"), "ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b298d88cea927ac7f539f1edf228376d25");
+        assert_eq!(hex_of(new_sha3(64), b"abc"), "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0");
+        assert_eq!(hex_of(new_sha3(32), &vec![b'a'; 1000000]), "5c8875ae474a3634ba4fd55ec85bffd661f32aca75c6d699d0cdcb6c115891c1");
+    }
+
+    #[test]
+    fn rate_boundary_test() -> () {
+        assert_eq!(hex_of(new_sha3(32), &[b'a'; 135]), "8094bb53c44cfb1e67b7c30447f9a1c33696d2463ecc1d9c92538913392843c9");
+        assert_eq!(hex_of(new_sha3(32), &[b'a'; 136]), "3fc5559f14db8e453a0a3091edbd2bc25e11528d81c66fa570a4efdcc2695ee1");
+    }
+
+    #[test]
+    fn keccak_test() -> () {
+        assert_eq!(hex_of(new_keccak(32), b""), "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470");
+        assert_eq!(hex_of(new_keccak(32), b"abc"), "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45");
+    }
+
+    #[test]
+    fn shake_test() -> () {
+        assert_eq!(hex_of(new_shake(128, 32), b""), "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26");
+        assert_eq!(hex_of(new_shake(256, 64), b""), "46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762fd75dc4ddd8c0f200cb05019d67b592f6fc821c49479ab48640292eacb3b7c4be");
+        assert_eq!(hex_of(new_shake(128, 200), b"abc"), hex_of(new_shake(128, 300), b"abc")[..400]);
+    }
+
+    #[test]
+    fn sp_800_185_samples_test() -> () {
+        let data: Vec<u8> = hex_to_bytes("00010203").unwrap();
+        let key: Vec<u8> = (0x40..0x60).collect();
+
+        assert_eq!(hex_of(new_cshake(128, 32, b"", b"Email Signature"), &data), "c1c36925b6409a04f1b504fcbca9d82b4017277cb5ed2b2065fc1d3814d5aaf5");
+        assert_eq!(hex_of(Kmac::new(128, &key, 32, b""), &data), "e5780b0d3ea6f7d3a429c5706aa43a00fadbd7d49628839e3187243f456ee14e");
+        assert_eq!(hex_of(Kmac::new(128, &key, 32, b"My Tagged Application"), &data), "3b1fba963cd8b0b59e8c1a6d71888b7143651af8ba0a7070c0979e2811324aa5");
+    }
+
+    #[test]
+    fn encoding_test() -> () {
+        assert_eq!(left_encode(0), vec![1, 0]);
+        assert_eq!(left_encode(256), vec![2, 1, 0]);
+        assert_eq!(right_encode(256), vec![1, 0, 2]);
+        assert_eq!(bytepad(b"", 168).len(), 168);
+    }
+}
@@ -0,0 +1,116 @@
+use crate::hashes::hash_function::HashFunction;
+
+const SIPHASH_COMPRESSION_ROUNDS: usize = 2;
+const SIPHASH_FINALIZATION_ROUNDS: usize = 4;
+
+fn sip_round(v: &mut [u64; 4]) -> () {
+    v[0] = v[0].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(13) ^ v[0];
+    v[0] = v[0].rotate_left(32);
+    v[2] = v[2].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(16) ^ v[2];
+    v[0] = v[0].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(21) ^ v[0];
+    v[2] = v[2].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(17) ^ v[2];
+    v[2] = v[2].rotate_left(32);
+}
+
+
+
+
+// SipHash-2-4 keyed with 128 bits: a short input PRF meant for hash tables, not a general purpose MAC
+#[derive(Debug, Clone, PartialEq)]
+pub struct SipHash {
+    state: [u64; 4],
+    pending: Vec<u8>,
+    length: u64
+}
+impl SipHash {
+    pub fn new(key: &[u8]) -> Result<SipHash, String> {
+        if key.len() != 16 {
+            return Err("SipHash key must be 16 bytes".to_string());
+        }
+
+        let k0: u64 = u64::from_le_bytes(key[0..8].try_into().unwrap());
+        let k1: u64 = u64::from_le_bytes(key[8..16].try_into().unwrap());
+
+        return Ok(SipHash {
+            state: [k0 ^ 0x736f6d6570736575, k1 ^ 0x646f72616e646f6d, k0 ^ 0x6c7967656e657261, k1 ^ 0x7465646279746573],
+            pending: Vec::new(),
+            length: 0
+        });
+    }
+
+    fn compress(&mut self, word: u64) -> () {
+        self.state[3] ^= word;
+        for _ in 0..SIPHASH_COMPRESSION_ROUNDS {
+            sip_round(&mut self.state);
+        }
+        self.state[0] ^= word;
+    }
+}
+
+impl HashFunction for SipHash {
+    fn update(&mut self, data: &[u8]) -> () {
+        self.length += data.len() as u64;
+        self.pending.extend_from_slice(data);
+
+        let whole: usize = self.pending.len() - self.pending.len() % 8;
+        let words: Vec<u64> = self.pending[..whole].chunks(8).map(|chunk: &[u8]| u64::from_le_bytes(chunk.try_into().unwrap())).collect();
+        for word in words {
+            self.compress(word);
+        }
+        self.pending.drain(..whole);
+    }
+
+    // The last word carries the remaining bytes and the message length modulo 256 in its top byte
+    fn finalize(&self) -> Vec<u8> {
+        let mut siphash: SipHash = self.clone();
+
+        let mut last: [u8; 8] = [0; 8];
+        last[..self.pending.len()].copy_from_slice(&self.pending);
+        last[7] = self.length as u8;
+        siphash.compress(u64::from_le_bytes(last));
+
+        siphash.state[2] ^= 0xff;
+        for _ in 0..SIPHASH_FINALIZATION_ROUNDS {
+            sip_round(&mut siphash.state);
+        }
+
+        let [v0, v1, v2, v3]: [u64; 4] = siphash.state;
+        return (v0 ^ v1 ^ v2 ^ v3).to_le_bytes().to_vec();
+    }
+
+    fn block_size(&self) -> usize {
+        return 8;
+    }
+
+    fn output_size(&self) -> usize {
+        return 8;
+    }
+}
+
+#[cfg(test)]
+mod siphash_test {
+    use crate::ops::bytes_to_hex;
+    use super::*;
+
+    fn siphash_hex(length: usize) -> String {
+        let message: Vec<u8> = (0..length as u8).collect();
+        let mut siphash: SipHash = SipHash::new(&(0..16).collect::<Vec<u8>>()).unwrap();
+        siphash.update(&message[..length / 3]);
+        siphash.update(&message[length / 3..]);
+
+        return bytes_to_hex(&siphash.finalize());
+    }
+
+    #[test]
+    fn reference_vectors_test() -> () {
+        assert_eq!(siphash_hex(0), "310e0edd47db6f72");
+        assert_eq!(siphash_hex(8), "6224939a79f5f593");
+        assert_eq!(siphash_hex(15), "e545be4961ca29a1");
+        assert_eq!(siphash_hex(63), "724506eb4c328a95");
+        assert!(SipHash::new(b"short").is_err());
+    }
+}
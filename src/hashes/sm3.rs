@@ -0,0 +1,110 @@
+use crate::{hashes::merkle_damgard::{CompressionFunction, MerkleDamgard}, tables::SM3_INITIAL_STATE};
+
+const SM3_ROUND_CONSTANTS: [u32; 2] = [0x79cc4519, 0x7a879d8a];
+
+pub type Sm3 = MerkleDamgard<Sm3Compression>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sm3Compression {
+    pub state: [u32; 8]
+}
+impl Sm3Compression {
+    pub fn new() -> Sm3Compression {
+        return Sm3Compression { state: SM3_INITIAL_STATE };
+    }
+}
+
+fn p0(x: u32) -> u32 {
+    return x ^ x.rotate_left(9) ^ x.rotate_left(17);
+}
+
+fn p1(x: u32) -> u32 {
+    return x ^ x.rotate_left(15) ^ x.rotate_left(23);
+}
+
+impl CompressionFunction for Sm3Compression {
+    fn block_size(&self) -> usize {
+        return 64;
+    }
+
+    fn output_size(&self) -> usize {
+        return 32;
+    }
+
+    fn big_endian_length(&self) -> bool {
+        return true;
+    }
+
+    fn compress(&mut self, block: &[u8]) -> () {
+        let mut w: [u32; 68] = [0; 68];
+
+        for (i, chunk) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes(chunk.try_into().unwrap());
+        }
+
+        for j in 16..68 {
+            w[j] = p1(w[j - 16] ^ w[j - 9] ^ w[j - 3].rotate_left(15)) ^ w[j - 13].rotate_left(7) ^ w[j - 6];
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h]: [u32; 8] = self.state;
+
+        for j in 0..64 {
+            let (ff, gg, t): (u32, u32, u32) = if j < 16 {
+                (a ^ b ^ c, e ^ f ^ g, SM3_ROUND_CONSTANTS[0])
+            } else {
+                ((a & b) | (a & c) | (b & c), (e & f) | (!e & g), SM3_ROUND_CONSTANTS[1])
+            };
+
+            let ss1: u32 = a.rotate_left(12)
+                            .wrapping_add(e)
+                            .wrapping_add(t.rotate_left(j as u32 % 32))
+                            .rotate_left(7);
+            let ss2: u32 = ss1 ^ a.rotate_left(12);
+            let tt1: u32 = ff.wrapping_add(d).wrapping_add(ss2).wrapping_add(w[j] ^ w[j + 4]);
+            let tt2: u32 = gg.wrapping_add(h).wrapping_add(ss1).wrapping_add(w[j]);
+
+            d = c;
+            c = b.rotate_left(9);
+            b = a;
+            a = tt1;
+            h = g;
+            g = f.rotate_left(19);
+            f = e;
+            e = p0(tt2);
+        }
+
+        for (word, value) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *word ^= value;
+        }
+    }
+
+    fn digest(&self) -> Vec<u8> {
+        return self.state.iter().flat_map(|word: &u32| word.to_be_bytes()).collect();
+    }
+}
+
+pub fn new_sm3() -> Sm3 {
+    return MerkleDamgard::new(Sm3Compression::new());
+}
+
+#[cfg(test)]
+mod sm3_test {
+    use crate::{hashes::hash_function::{digest, HashAlgorithms}, ops::bytes_to_hex};
+
+    fn sm3_hex(message: &[u8]) -> String {
+        return bytes_to_hex(&digest(&HashAlgorithms::Sm3, message));
+    }
+
+    #[test]
+    fn standard_examples_test() -> () {
+        assert_eq!(sm3_hex(b"abc"), "66c7f0f462eeedd9d1f2d46bdc10e4e24167c4875cf2f7a2297da02b8f4ba8e0");
+        assert_eq!(sm3_hex(&b"abcd".repeat(16)), "debe9ff92275b8a138604889c18e5a4d6fdb70e5387e5765293dcba39c0c5732");
+    }
+
+    #[test]
+    fn padding_boundaries_test() -> () {
+        assert_eq!(sm3_hex(b""), "1ab21d8355cfa17f8e61194831e81a8f22bec8c728fefb747ed035eb5082aa2b");
+        assert_eq!(sm3_hex(&[b'a'; 55]), "288337eef51eec62e7544d7270424c8dbe656254c99852870a73b2453a6a7fb1");
+        assert_eq!(sm3_hex(&[b'a'; 56]), "ba00ebedaab54065a5fd4f9f56326016203166bcee3eed44ea868d59d67aa3c8");
+    }
+}
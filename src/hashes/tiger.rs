@@ -0,0 +1,134 @@
+use crate::{hashes::merkle_damgard::{CompressionFunction, MerkleDamgard}, tables::TIGER_SBOXES};
+
+pub type Tiger = MerkleDamgard<TigerCompression>;
+
+// Tiger and Tiger2 only differ in the first padding byte
+#[derive(Debug, Clone, PartialEq)]
+pub struct TigerCompression {
+    pub state: [u64; 3],
+    pub padding_byte: u8
+}
+impl TigerCompression {
+    pub fn new(padding_byte: u8) -> TigerCompression {
+        return TigerCompression {
+            state: [0x0123456789abcdef, 0xfedcba9876543210, 0xf096a5b4c3b2e187],
+            padding_byte: padding_byte
+        };
+    }
+}
+
+fn tiger_round(a: &mut u64, b: &mut u64, c: &mut u64, word: u64, multiplier: u64) -> () {
+    *c ^= word;
+    let bytes: [u8; 8] = c.to_le_bytes();
+
+    *a = a.wrapping_sub(TIGER_SBOXES[0][bytes[0] as usize] ^ TIGER_SBOXES[1][bytes[2] as usize] ^ TIGER_SBOXES[2][bytes[4] as usize] ^ TIGER_SBOXES[3][bytes[6] as usize]);
+    *b = b.wrapping_add(TIGER_SBOXES[3][bytes[1] as usize] ^ TIGER_SBOXES[2][bytes[3] as usize] ^ TIGER_SBOXES[1][bytes[5] as usize] ^ TIGER_SBOXES[0][bytes[7] as usize]);
+    *b = b.wrapping_mul(multiplier);
+}
+
+
+
+
+fn tiger_pass(a: &mut u64, b: &mut u64, c: &mut u64, x: &[u64; 8], multiplier: u64) -> () {
+    tiger_round(a, b, c, x[0], multiplier);
+    tiger_round(b, c, a, x[1], multiplier);
+    tiger_round(c, a, b, x[2], multiplier);
+    tiger_round(a, b, c, x[3], multiplier);
+    tiger_round(b, c, a, x[4], multiplier);
+    tiger_round(c, a, b, x[5], multiplier);
+    tiger_round(a, b, c, x[6], multiplier);
+    tiger_round(b, c, a, x[7], multiplier);
+}
+
+
+
+
+fn key_schedule(x: &mut [u64; 8]) -> () {
+    x[0] = x[0].wrapping_sub(x[7] ^ 0xa5a5a5a5a5a5a5a5);
+    x[1] ^= x[0];
+    x[2] = x[2].wrapping_add(x[1]);
+    x[3] = x[3].wrapping_sub(x[2] ^ (!x[1] << 19));
+    x[4] ^= x[3];
+    x[5] = x[5].wrapping_add(x[4]);
+    x[6] = x[6].wrapping_sub(x[5] ^ (!x[4] >> 23));
+    x[7] ^= x[6];
+    x[0] = x[0].wrapping_add(x[7]);
+    x[1] = x[1].wrapping_sub(x[0] ^ (!x[7] << 19));
+    x[2] ^= x[1];
+    x[3] = x[3].wrapping_add(x[2]);
+    x[4] = x[4].wrapping_sub(x[3] ^ (!x[2] >> 23));
+    x[5] ^= x[4];
+    x[6] = x[6].wrapping_add(x[5]);
+    x[7] = x[7].wrapping_sub(x[6] ^ 0x0123456789abcdef);
+}
+
+impl CompressionFunction for TigerCompression {
+    fn block_size(&self) -> usize {
+        return 64;
+    }
+
+    fn output_size(&self) -> usize {
+        return 24;
+    }
+
+    fn big_endian_length(&self) -> bool {
+        return false;
+    }
+
+    fn padding_byte(&self) -> u8 {
+        return self.padding_byte;
+    }
+
+    fn compress(&mut self, block: &[u8]) -> () {
+        let mut x: [u64; 8] = [0; 8];
+
+        for (i, chunk) in block.chunks(8).enumerate() {
+            x[i] = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+
+        let [mut a, mut b, mut c]: [u64; 3] = self.state;
+
+        tiger_pass(&mut a, &mut b, &mut c, &x, 5);
+        key_schedule(&mut x);
+        tiger_pass(&mut c, &mut a, &mut b, &x, 7);
+        key_schedule(&mut x);
+        tiger_pass(&mut b, &mut c, &mut a, &x, 9);
+
+        self.state = [a ^ self.state[0], b.wrapping_sub(self.state[1]), c.wrapping_add(self.state[2])];
+    }
+
+    fn digest(&self) -> Vec<u8> {
+        return self.state.iter().flat_map(|word: &u64| word.to_le_bytes()).collect();
+    }
+}
+
+pub fn new_tiger() -> Tiger {
+    return MerkleDamgard::new(TigerCompression::new(0x01));
+}
+
+pub fn new_tiger2() -> Tiger {
+    return MerkleDamgard::new(TigerCompression::new(0x80));
+}
+
+#[cfg(test)]
+mod tiger_test {
+    use crate::{hashes::hash_function::{digest, HashAlgorithms}, ops::bytes_to_hex};
+
+    fn tiger_hex(algorithm: HashAlgorithms, message: &[u8]) -> String {
+        return bytes_to_hex(&digest(&algorithm, message));
+    }
+
+    #[test]
+    fn tiger_test() -> () {
+        assert_eq!(tiger_hex(HashAlgorithms::Tiger, b""), "3293ac630c13f0245f92bbb1766e16167a4e58492dde73f3");
+        assert_eq!(tiger_hex(HashAlgorithms::Tiger, b"abc"), "2aab1484e8c158f2bfb8c5ff41b57a525129131c957b5f93");
+        assert_eq!(tiger_hex(HashAlgorithms::Tiger, b"Tiger"), "dd00230799f5009fec6debc838bb6a27df2b9d6f110c7937");
+        assert_eq!(tiger_hex(HashAlgorithms::Tiger, b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789"), "8dcea680a17583ee502ba38a3c368651890ffbccdc49a8cc");
+    }
+
+    #[test]
+    fn tiger2_test() -> () {
+        assert_eq!(tiger_hex(HashAlgorithms::Tiger2, b""), "4441be75f6018773c206c22745374b924aa8313fef919f41");
+        assert_eq!(tiger_hex(HashAlgorithms::Tiger2, b"abc"), "f68d7bc5af4b43a06e048d7829560d4a9415658bb0b1f3bf");
+    }
+}
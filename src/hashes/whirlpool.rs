@@ -0,0 +1,112 @@
+use crate::{hashes::merkle_damgard::{CompressionFunction, MerkleDamgard}, tables::{WHIRLPOOL_SBOX, WHIRLPOOL_TABLES}};
+
+const WHIRLPOOL_ROUNDS: usize = 10;
+
+pub type Whirlpool = MerkleDamgard<WhirlpoolCompression>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WhirlpoolCompression {
+    pub state: [u8; 64]
+}
+impl WhirlpoolCompression {
+    pub fn new() -> WhirlpoolCompression {
+        return WhirlpoolCompression { state: [0; 64] };
+    }
+}
+
+// One round on an 8x8 byte matrix with one row per word: substitution, column shift and row diffusion
+// are a single table lookup per byte, followed by the key addition
+fn whirlpool_round(state: &[u64; 8], key: &[u64; 8]) -> [u64; 8] {
+    let mut output: [u64; 8] = *key;
+
+    for row in 0..8 {
+        for column in 0..8 {
+            let byte: u8 = (state[(row + 8 - column) % 8] >> (56 - 8 * column)) as u8;
+            output[row] ^= WHIRLPOOL_TABLES[column][byte as usize];
+        }
+    }
+    return output;
+}
+
+fn to_rows(bytes: &[u8]) -> [u64; 8] {
+    let mut rows: [u64; 8] = [0; 8];
+
+    for (row, chunk) in rows.iter_mut().zip(bytes.chunks(8)) {
+        *row = u64::from_be_bytes(chunk.try_into().unwrap());
+    }
+    return rows;
+}
+
+impl CompressionFunction for WhirlpoolCompression {
+    fn block_size(&self) -> usize {
+        return 64;
+    }
+
+    fn output_size(&self) -> usize {
+        return 64;
+    }
+
+    fn big_endian_length(&self) -> bool {
+        return true;
+    }
+
+    fn length_size(&self) -> usize {
+        return 32;
+    }
+
+    fn compress(&mut self, block: &[u8]) -> () {
+        let message: [u64; 8] = to_rows(block);
+        let mut key: [u64; 8] = to_rows(&self.state);
+        let mut state: [u64; 8] = [0; 8];
+
+        for i in 0..8 {
+            state[i] = message[i] ^ key[i];
+        }
+
+        for round in 1..=WHIRLPOOL_ROUNDS {
+            let mut round_constant: [u64; 8] = [0; 8];
+            round_constant[0] = u64::from_be_bytes(WHIRLPOOL_SBOX[8 * (round - 1)..8 * round].try_into().unwrap());
+
+            key = whirlpool_round(&key, &round_constant);
+            state = whirlpool_round(&state, &key);
+        }
+
+        let chaining: [u64; 8] = to_rows(&self.state);
+        for i in 0..8 {
+            self.state[8 * i..8 * i + 8].copy_from_slice(&(chaining[i] ^ state[i] ^ message[i]).to_be_bytes());
+        }
+    }
+
+    fn digest(&self) -> Vec<u8> {
+        return self.state.to_vec();
+    }
+}
+
+pub fn new_whirlpool() -> Whirlpool {
+    return MerkleDamgard::new(WhirlpoolCompression::new());
+}
+
+#[cfg(test)]
+mod whirlpool_test {
+    use crate::{hashes::hash_function::{digest, HashAlgorithms}, ops::bytes_to_hex};
+
+    fn whirlpool_hex(message: &[u8]) -> String {
+        return bytes_to_hex(&digest(&HashAlgorithms::Whirlpool, message));
+    }
+
+    #[test]
+    fn iso_10118_3_test() -> () {
+        assert_eq!(whirlpool_hex(b""), "19fa61d75522a4669b44e39c1d2e1726c530232130d407f89afee0964997f7a73e83be698b288febcf88e3e03c4f0757ea8964e59b63d93708b138cc42a66eb3");
+        assert_eq!(whirlpool_hex(b"a"), "8aca2602792aec6f11a67206531fb7d7f0dff59413145e6973c45001d0087b42d11bc645413aeff63a42391a39145a591a92200d560195e53b478584fdae231a");
+        assert_eq!(whirlpool_hex(b"abc"), "4e2448a4c6f486bb16b6562c73b4020bf3043e3a731bce721ae1b303d97e6d4c7181eebdb6c57e277d0e34957114cbd6c797fc9d95d8b582d225292076d4eef5");
+        assert_eq!(whirlpool_hex(b"message digest"), "378c84a4126e2dc6e56dcc7458377aac838d00032230f53ce1f5700c0ffb4d3b8421557659ef55c106b4b52ac5a4aaa692ed920052838f3362e86dbd37a8903e");
+        assert_eq!(whirlpool_hex(b"abcdefghijklmnopqrstuvwxyz"), "f1d754662636ffe92c82ebb9212a484a8d38631ead4238f5442ee13b8054e41b08bf2a9251c30b6a0b8aae86177ab4a6f68f673e7207865d5d9819a3dba4eb3b");
+        assert_eq!(whirlpool_hex(b"12345678901234567890123456789012345678901234567890123456789012345678901234567890"), "466ef18babb0154d25b9d38a6414f5c08784372bccb204d6549c4afadb6014294d5bd8df2a6c44e538cd047b2681a51a2c60481e88c5a20b2c2a80cf3a9a083b");
+    }
+
+    #[test]
+    fn padding_boundaries_test() -> () {
+        assert_eq!(whirlpool_hex(&[b'a'; 31]), "698d25826e50bfd1f4e67a1ddbe0d40fac00c4b8f49bd17f706e2f4c5c813249a8a2b771acec2a7425c20406acbc672a2bc83a62150af78f0d804d382658af05");
+        assert_eq!(whirlpool_hex(&[b'a'; 32]), "661fe85e302a100bc85048438a734d219e0c006c8464f10eb2281194db21d3b236fabb497818f63511a63be7e1c5ea4009a0f937040f4bc080a68a2fff589dab");
+    }
+}
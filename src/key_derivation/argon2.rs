@@ -0,0 +1,504 @@
+use clap::{Args, ValueEnum};
+use crate::{hashes::{blake2::Blake2b, hash_function::HashFunction}, key_derivation::{pbkdf2::{random_salt, PASSWORD_SALT_LENGTH}, phc::PhcString}, ops::{bytes_to_hex, constant_time_eq, decode_key, KeyFormat}};
+
+const ARGON2_VERSION: u32 = 0x13;
+const BLOCK_WORDS: usize = 128;
+const SYNC_POINTS: usize = 4;
+// OWASP recommendation for Argon2id: 19 MiB, 2 passes, 1 lane
+pub const DEFAULT_MEMORY: u32 = 19456;
+pub const DEFAULT_TIME: u32 = 2;
+// Memory read from a PHC string or a password header is bounded so crafted input can't allocate more than 1 GiB
+pub const MAX_MEMORY: u32 = 1 << 20;
+
+type Block = [u64; BLOCK_WORDS];
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum Argon2Operations {
+    Hash,
+    Verify
+}
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum Argon2Variant {
+    Argon2d,
+    Argon2i,
+    Argon2id
+}
+impl Argon2Variant {
+    fn type_code(&self) -> u64 {
+        match self {
+            Argon2Variant::Argon2d => 0,
+            Argon2Variant::Argon2i => 1,
+            Argon2Variant::Argon2id => 2
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Argon2Variant::Argon2d => "argon2d",
+            Argon2Variant::Argon2i => "argon2i",
+            Argon2Variant::Argon2id => "argon2id"
+        }
+    }
+
+    fn from_name(name: &str) -> Result<Argon2Variant, String> {
+        match name {
+            "argon2d" => Ok(Argon2Variant::Argon2d),
+            "argon2i" => Ok(Argon2Variant::Argon2i),
+            "argon2id" => Ok(Argon2Variant::Argon2id),
+            _ => Err(format!("expected an $argon2d$, $argon2i$ or $argon2id$ PHC string, got ${}$", name))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Argon2Parameters {
+    pub variant: Argon2Variant,
+    // memory size in KiB, i.e. in 1024 byte blocks
+    pub memory: u32,
+    pub time: u32,
+    pub parallelism: u32,
+    pub length: usize
+}
+impl Argon2Parameters {
+    fn check(&self, salt: &[u8]) -> Result<(), String> {
+        if self.parallelism == 0 || self.parallelism >= 1 << 24 {
+            return Err("Argon2 parallelism must be between 1 and 2^24 - 1".to_string());
+        }
+        if self.memory < 8 * self.parallelism {
+            return Err("Argon2 memory must be at least 8 KiB per lane".to_string());
+        }
+        if self.time == 0 {
+            return Err("Argon2 needs at least one pass".to_string());
+        }
+        if self.length < 4 {
+            return Err("Argon2 tag length must be at least 4 bytes".to_string());
+        }
+        if salt.len() < 8 {
+            return Err("Argon2 salt must be at least 8 bytes".to_string());
+        }
+        return Ok(());
+    }
+}
+
+fn blake2b(output_size: usize, inputs: &[&[u8]]) -> Vec<u8> {
+    let mut hasher: Blake2b = Blake2b::new(output_size, &[], &[], &[]).unwrap();
+    for input in inputs {
+        hasher.update(input);
+    }
+    return hasher.finalize();
+}
+
+
+
+
+// H': BLAKE2b stretched to any length by chaining 64 byte outputs and keeping half of each
+fn variable_hash(output_size: usize, input: &[u8]) -> Vec<u8> {
+    let size: [u8; 4] = (output_size as u32).to_le_bytes();
+    if output_size <= 64 {
+        return blake2b(output_size, &[&size, input]);
+    }
+
+    let mut v: Vec<u8> = blake2b(64, &[&size, input]);
+    let mut output: Vec<u8> = Vec::with_capacity(output_size);
+
+    while output_size - output.len() > 64 {
+        output.extend_from_slice(&v[..32]);
+        v = blake2b(if output_size - output.len() > 64 { 64 } else { output_size - output.len() }, &[&v]);
+    }
+    output.extend(v);
+
+    return output;
+}
+
+
+
+
+// GB with the BlaMka multiplication a + b + 2 * lo(a) * lo(b)
+fn gb(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize) -> () {
+    let blamka = |x: u64, y: u64| -> u64 { x.wrapping_add(y).wrapping_add(2u64.wrapping_mul(x & 0xffffffff).wrapping_mul(y & 0xffffffff)) };
+
+    v[a] = blamka(v[a], v[b]);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = blamka(v[c], v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = blamka(v[a], v[b]);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = blamka(v[c], v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+
+
+
+fn permutation(v: &mut [u64; 16]) -> () {
+    gb(v, 0, 4, 8, 12);
+    gb(v, 1, 5, 9, 13);
+    gb(v, 2, 6, 10, 14);
+    gb(v, 3, 7, 11, 15);
+    gb(v, 0, 5, 10, 15);
+    gb(v, 1, 6, 11, 12);
+    gb(v, 2, 7, 8, 13);
+    gb(v, 3, 4, 9, 14);
+}
+
+
+
+
+// G: the 1 KiB block as an 8x8 matrix of 16 byte registers, permuted by rows then by columns
+fn compress(x: &Block, y: &Block) -> Block {
+    let mut r: Block = [0; BLOCK_WORDS];
+    for i in 0..BLOCK_WORDS {
+        r[i] = x[i] ^ y[i];
+    }
+    let mut z: Block = r;
+
+    for row in 0..8 {
+        let mut v: [u64; 16] = z[16 * row..16 * (row + 1)].try_into().unwrap();
+        permutation(&mut v);
+        z[16 * row..16 * (row + 1)].copy_from_slice(&v);
+    }
+    for column in 0..8 {
+        let indexes: Vec<usize> = (0..8).flat_map(|row: usize| [16 * row + 2 * column, 16 * row + 2 * column + 1]).collect();
+        let mut v: [u64; 16] = [0; 16];
+        for (word, index) in v.iter_mut().zip(&indexes) {
+            *word = z[*index];
+        }
+        permutation(&mut v);
+        for (word, index) in v.iter().zip(&indexes) {
+            z[*index] = *word;
+        }
+    }
+
+    for i in 0..BLOCK_WORDS {
+        z[i] ^= r[i];
+    }
+    return z;
+}
+
+
+
+
+fn bytes_to_block(bytes: &[u8]) -> Block {
+    let mut block: Block = [0; BLOCK_WORDS];
+    for (word, chunk) in block.iter_mut().zip(bytes.chunks(8)) {
+        *word = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    return block;
+}
+
+
+
+
+struct Position {
+    pass: usize,
+    lane: usize,
+    slice: usize
+}
+
+struct Memory {
+    blocks: Vec<Block>,
+    lanes: usize,
+    lane_length: usize,
+    segment_length: usize
+}
+impl Memory {
+    // Which block of which lane the block at index of the segment refers to, from the 64 bit pseudo-random value
+    fn reference(&self, position: &Position, index: usize, pseudo_random: u64) -> usize {
+        let reference_lane: usize = if position.pass == 0 && position.slice == 0 { position.lane } else { (pseudo_random >> 32) as usize % self.lanes };
+        let same_lane: bool = reference_lane == position.lane;
+
+        let finished_segments: usize = if position.pass == 0 { position.slice * self.segment_length } else { self.lane_length - self.segment_length };
+        let reference_area: usize = if same_lane {
+            finished_segments + index - 1
+        } else if index == 0 {
+            finished_segments - 1
+        } else {
+            finished_segments
+        };
+
+        let x: u64 = ((pseudo_random & 0xffffffff) * (pseudo_random & 0xffffffff)) >> 32;
+        let relative_position: usize = reference_area - 1 - ((reference_area as u64 * x) >> 32) as usize;
+        let start: usize = if position.pass == 0 || position.slice == SYNC_POINTS - 1 { 0 } else { (position.slice + 1) * self.segment_length };
+
+        return reference_lane * self.lane_length + (start + relative_position) % self.lane_length;
+    }
+
+    fn fill_segment(&mut self, parameters: &Argon2Parameters, position: &Position) -> () {
+        let data_independent: bool = match parameters.variant {
+            Argon2Variant::Argon2d => false,
+            Argon2Variant::Argon2i => true,
+            Argon2Variant::Argon2id => position.pass == 0 && position.slice < SYNC_POINTS / 2
+        };
+
+        let mut input: Block = [0; BLOCK_WORDS];
+        input[..6].copy_from_slice(&[position.pass as u64, position.lane as u64, position.slice as u64, self.blocks.len() as u64, parameters.time as u64, parameters.variant.type_code()]);
+        let zero: Block = [0; BLOCK_WORDS];
+        let mut addresses: Block = [0; BLOCK_WORDS];
+
+        let first_index: usize = if position.pass == 0 && position.slice == 0 { 2 } else { 0 };
+        if data_independent && first_index != 0 {
+            input[6] += 1;
+            addresses = compress(&zero, &compress(&zero, &input));
+        }
+
+        for index in first_index..self.segment_length {
+            let current: usize = position.lane * self.lane_length + position.slice * self.segment_length + index;
+            let previous: usize = if current.is_multiple_of(self.lane_length) { current + self.lane_length - 1 } else { current - 1 };
+
+            let pseudo_random: u64 = if data_independent {
+                if index % BLOCK_WORDS == 0 {
+                    input[6] += 1;
+                    addresses = compress(&zero, &compress(&zero, &input));
+                }
+                addresses[index % BLOCK_WORDS]
+            } else {
+                self.blocks[previous][0]
+            };
+
+            let mut block: Block = compress(&self.blocks[previous], &self.blocks[self.reference(position, index, pseudo_random)]);
+            if position.pass > 0 {
+                for (word, old) in block.iter_mut().zip(self.blocks[current].iter()) {
+                    *word ^= old;
+                }
+            }
+            self.blocks[current] = block;
+        }
+    }
+}
+
+
+
+
+// RFC 9106, version 0x13
+pub fn argon2(parameters: &Argon2Parameters, password: &[u8], salt: &[u8], secret: &[u8], associated_data: &[u8]) -> Result<Vec<u8>, String> {
+    parameters.check(salt)?;
+
+    let mut h0_input: Vec<u8> = Vec::new();
+    for word in [parameters.parallelism, parameters.length as u32, parameters.memory, parameters.time, ARGON2_VERSION, parameters.variant.type_code() as u32] {
+        h0_input.extend(word.to_le_bytes());
+    }
+    for field in [password, salt, secret, associated_data] {
+        h0_input.extend((field.len() as u32).to_le_bytes());
+        h0_input.extend_from_slice(field);
+    }
+    let h0: Vec<u8> = blake2b(64, &[&h0_input]);
+
+    let lanes: usize = parameters.parallelism as usize;
+    let segment_length: usize = parameters.memory as usize / (SYNC_POINTS * lanes);
+    let mut memory: Memory = Memory {
+        blocks: vec![[0; BLOCK_WORDS]; segment_length * SYNC_POINTS * lanes],
+        lanes: lanes,
+        lane_length: segment_length * SYNC_POINTS,
+        segment_length: segment_length
+    };
+
+    for lane in 0..lanes {
+        for column in 0..2 {
+            let block: Vec<u8> = variable_hash(1024, &[h0.as_slice(), &(column as u32).to_le_bytes(), &(lane as u32).to_le_bytes()].concat());
+            memory.blocks[lane * memory.lane_length + column] = bytes_to_block(&block);
+        }
+    }
+
+    // Lanes only read the segments of other lanes that are already finished, so filling them one after the other is enough
+    for pass in 0..parameters.time as usize {
+        for slice in 0..SYNC_POINTS {
+            for lane in 0..lanes {
+                memory.fill_segment(parameters, &Position { pass: pass, lane: lane, slice: slice });
+            }
+        }
+    }
+
+    let mut last_column: Block = [0; BLOCK_WORDS];
+    for lane in 0..lanes {
+        for (word, last) in last_column.iter_mut().zip(memory.blocks[(lane + 1) * memory.lane_length - 1].iter()) {
+            *word ^= last;
+        }
+    }
+    let last_column_bytes: Vec<u8> = last_column.iter().flat_map(|word: &u64| word.to_le_bytes()).collect();
+
+    return Ok(variable_hash(parameters.length, &last_column_bytes));
+}
+
+
+
+
+// $argon2id$v=19$m=<memory>,t=<time>,p=<parallelism>$<salt>$<hash>
+pub fn encode_phc(parameters: &Argon2Parameters, salt: &[u8], hash: &[u8]) -> String {
+    return PhcString {
+        algorithm: parameters.variant.name().to_string(),
+        version: Some(ARGON2_VERSION),
+        parameters: vec![("m".to_string(), parameters.memory.to_string()), ("t".to_string(), parameters.time.to_string()), ("p".to_string(), parameters.parallelism.to_string())],
+        salt: salt.to_vec(),
+        hash: hash.to_vec()
+    }.encode();
+}
+
+
+
+
+pub fn verify_phc(password: &[u8], phc: &str) -> Result<bool, String> {
+    let phc: PhcString = PhcString::parse(phc)?;
+    if phc.version.is_some_and(|version: u32| version != ARGON2_VERSION) {
+        return Err(format!("only Argon2 version {} is supported", ARGON2_VERSION));
+    }
+
+    let parameters: Argon2Parameters = Argon2Parameters {
+        variant: Argon2Variant::from_name(&phc.algorithm)?,
+        memory: phc.parameter("m")?,
+        time: phc.parameter("t")?,
+        parallelism: phc.parameter("p")?,
+        length: phc.hash.len()
+    };
+    if parameters.memory > MAX_MEMORY {
+        return Err(format!("Argon2 memory above {} KiB is refused", MAX_MEMORY));
+    }
+    return Ok(constant_time_eq(&argon2(&parameters, password, &phc.salt, &[], &[])?, &phc.hash));
+}
+
+
+
+
+#[derive(Debug, Args, Clone)]
+pub struct Argon2Alg {
+    /// Derive a key and PHC string from the password, or verify the password against a PHC string
+    #[arg(short, long, value_enum, default_value = "hash")]
+    pub operation: Argon2Operations,
+    /// Argon2 variant
+    #[arg(short, long, value_enum, default_value = "argon2id")]
+    pub variant: Argon2Variant,
+    /// Password to derive the key from
+    #[arg(short, long)]
+    pub password: String,
+    /// Salt, at least 8 bytes (a random 16 byte salt is generated when omitted)
+    #[arg(short, long)]
+    pub salt: Option<String>,
+    /// Encoding of the given salt
+    #[arg(long, value_enum, default_value = "text")]
+    pub salt_format: KeyFormat,
+    /// Memory size in KiB
+    #[arg(short, long, default_value_t = DEFAULT_MEMORY)]
+    pub memory: u32,
+    /// Number of passes over the memory
+    #[arg(short, long, default_value_t = DEFAULT_TIME)]
+    pub time: u32,
+    /// Number of lanes
+    #[arg(long, default_value_t = 1)]
+    pub parallelism: u32,
+    /// Derived key length in bytes
+    #[arg(short, long, default_value_t = 32)]
+    pub length: usize,
+    /// $argon2id$ (or $argon2i$, $argon2d$) PHC string to verify the password against
+    #[arg(long, required_if_eq("operation", "verify"))]
+    pub hash: Option<String>
+}
+
+impl Argon2Alg {
+    pub fn execute(self) -> () {
+        match self.operation {
+            Argon2Operations::Hash => match self.derive() {
+                Ok((salt, key)) => {
+                    println!("salt: {}", bytes_to_hex(&salt));
+                    println!("key: {}", bytes_to_hex(&key));
+                    println!("phc: {}", encode_phc(&self.parameters(), &salt, &key));
+                }
+                Err(error) => println!("{}", error)
+            },
+            Argon2Operations::Verify => match verify_phc(self.password.as_bytes(), self.hash.as_deref().unwrap_or_default()) {
+                Ok(true) => println!("OK"),
+                Ok(false) => {
+                    println!("FAILED");
+                    std::process::exit(1);
+                }
+                Err(error) => {
+                    println!("{}", error);
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+
+    fn parameters(&self) -> Argon2Parameters {
+        return Argon2Parameters { variant: self.variant.clone(), memory: self.memory, time: self.time, parallelism: self.parallelism, length: self.length };
+    }
+
+    fn derive(&self) -> Result<(Vec<u8>, Vec<u8>), String> {
+        let salt: Vec<u8> = match &self.salt {
+            Some(salt) => decode_key(salt, &self.salt_format)?,
+            None => random_salt(PASSWORD_SALT_LENGTH)
+        };
+        let key: Vec<u8> = argon2(&self.parameters(), self.password.as_bytes(), &salt, &[], &[])?;
+
+        return Ok((salt, key));
+    }
+}
+
+#[cfg(test)]
+mod argon2_test {
+    use super::*;
+
+    fn rfc_9106_tag(variant: Argon2Variant) -> String {
+        let parameters: Argon2Parameters = Argon2Parameters { variant: variant, memory: 32, time: 3, parallelism: 4, length: 32 };
+
+        return bytes_to_hex(&argon2(&parameters, &[1; 32], &[2; 16], &[3; 8], &[4; 12]).unwrap());
+    }
+
+    #[test]
+    fn rfc_9106_test() -> () {
+        assert_eq!(rfc_9106_tag(Argon2Variant::Argon2d), "512b391b6f1162975371d30919734294f868e3be3984f3c1a13a4db9fabe4acb");
+        assert_eq!(rfc_9106_tag(Argon2Variant::Argon2i), "c814d9d1dc7f37aa13f0d77f2494bda1c8de6b016dd388d29952a4c4672b6ce8");
+        assert_eq!(rfc_9106_tag(Argon2Variant::Argon2id), "0d640df58d78766c08c037a34a8b53c9d01ef0452d75b65eb52520e96b01e659");
+    }
+
+    #[test]
+    fn long_tag_and_passes_test() -> () {
+        let parameters: Argon2Parameters = Argon2Parameters { variant: Argon2Variant::Argon2id, memory: 64, time: 2, parallelism: 2, length: 100 };
+
+        assert_eq!(bytes_to_hex(&argon2(&parameters, b"password", b"somesalt", &[], &[]).unwrap()), "9e1aabbad29c004e3b0d760b8b6cfeb6ea4f061bf94781c4dcd2b7a981b129e4af08296207efb3c70b9c3f31fada12f649d690aa4f232e94cb1c718f1c99b076d11262baeca847b91bfb385e06cd339955de1a11dd1a62016d9996a12eba40a1649f1fcc");
+    }
+
+    #[test]
+    fn parameters_test() -> () {
+        let parameters: Argon2Parameters = Argon2Parameters { variant: Argon2Variant::Argon2id, memory: 32, time: 1, parallelism: 4, length: 32 };
+
+        assert!(argon2(&parameters, b"password", b"short", &[], &[]).is_err());
+        assert!(argon2(&Argon2Parameters { memory: 31, ..parameters.clone() }, b"password", b"somesalt", &[], &[]).is_err());
+        assert!(argon2(&Argon2Parameters { time: 0, ..parameters.clone() }, b"password", b"somesalt", &[], &[]).is_err());
+        assert!(argon2(&Argon2Parameters { length: 3, ..parameters.clone() }, b"password", b"somesalt", &[], &[]).is_err());
+    }
+
+    #[test]
+    fn phc_test() -> () {
+        assert!(verify_phc(b"password", "$argon2i$v=19$m=256,t=3,p=1$c29tZXNhbHQ$EMX23XHVA/naOJ1pHBjHLljgYcSkmStx").unwrap());
+        assert!(!verify_phc(b"Password", "$argon2i$v=19$m=256,t=3,p=1$c29tZXNhbHQ$EMX23XHVA/naOJ1pHBjHLljgYcSkmStx").unwrap());
+        assert!(verify_phc(b"password", "$argon2id$v=16$m=32,t=1,p=1$c29tZXNhbHQ$aGFzaGhhc2g").is_err());
+        assert!(verify_phc(b"password", "$scrypt$ln=4,r=8,p=1$c29tZXNhbHQ$aGFzaGhhc2g").is_err());
+        assert!(verify_phc(b"password", "$argon2id$v=19$m=4194304000,t=1,p=1$c29tZXNhbHQ$aGFzaGhhc2g").is_err());
+    }
+
+    #[test]
+    fn execute_derive_test() -> () {
+        let instance_1: Argon2Alg = Argon2Alg {
+            operation: Argon2Operations::Hash,
+            variant: Argon2Variant::Argon2id,
+            password: "password".to_string(),
+            salt: Some("somesalt".to_string()),
+            salt_format: KeyFormat::Text,
+            memory: 64,
+            time: 2,
+            parallelism: 2,
+            length: 100,
+            hash: None
+        };
+        let (salt, key): (Vec<u8>, Vec<u8>) = instance_1.derive().unwrap();
+        assert!(verify_phc(b"password", &encode_phc(&instance_1.parameters(), &salt, &key)).unwrap());
+
+        let instance_2: Argon2Alg = Argon2Alg {
+            salt: None,
+            memory: 8,
+            parallelism: 1,
+            ..instance_1.clone()
+        };
+        assert_eq!(instance_2.derive().unwrap().0.len(), PASSWORD_SALT_LENGTH);
+    }
+}
@@ -0,0 +1,333 @@
+use clap::{Args, ValueEnum};
+use crate::{algorithms::blowfish::Blowfish, key_derivation::pbkdf2::random_salt, ops::constant_time_eq};
+
+const BCRYPT_RADIX64_ALPHABET: &[u8; 64] = b"./ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+const BCRYPT_MAGIC: &[u8; 24] = b"OrpheanBeholderScryDoubt";
+const BCRYPT_SALT_LENGTH: usize = 16;
+const BCRYPT_HASH_LENGTH: usize = 23;
+// The password plus its terminating NUL is cut to the 72 bytes that fill the P array
+const BCRYPT_MAX_KEY_LENGTH: usize = 72;
+pub const BCRYPT_MIN_COST: u32 = 4;
+pub const BCRYPT_MAX_COST: u32 = 31;
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum BcryptOperations {
+    Hash,
+    Verify
+}
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum BcryptVariant {
+    #[value(name = "2a")]
+    V2a,
+    #[value(name = "2b")]
+    V2b,
+    #[value(name = "2y")]
+    V2y
+}
+impl BcryptVariant {
+    pub fn prefix(&self) -> &str {
+        match self {
+            BcryptVariant::V2a => "2a",
+            BcryptVariant::V2b => "2b",
+            BcryptVariant::V2y => "2y"
+        }
+    }
+
+    fn from_prefix(prefix: &str) -> Result<BcryptVariant, String> {
+        match prefix {
+            "2a" => Ok(BcryptVariant::V2a),
+            "2b" => Ok(BcryptVariant::V2b),
+            "2y" => Ok(BcryptVariant::V2y),
+            _ => Err(format!("unsupported bcrypt variant ${}$", prefix))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BcryptHash {
+    pub variant: BcryptVariant,
+    pub cost: u32,
+    pub salt: Vec<u8>,
+    pub hash: Vec<u8>
+}
+impl BcryptHash {
+    // $2b$12$ followed by the 22 character salt and the 31 character hash
+    pub fn parse(hash_string: &str) -> Result<BcryptHash, String> {
+        let fields: Vec<&str> = hash_string.split('$').collect();
+        if fields.len() != 4 || !fields[0].is_empty() || fields[3].len() != 53 || !fields[3].is_ascii() {
+            return Err("bcrypt hash must look like $2b$<cost>$<53 characters>".to_string());
+        }
+
+        let variant: BcryptVariant = BcryptVariant::from_prefix(fields[1])?;
+        if fields[2].len() != 2 {
+            return Err("bcrypt cost must be two digits".to_string());
+        }
+        let cost: u32 = fields[2].parse::<u32>().map_err(|_| "bcrypt cost must be two digits".to_string())?;
+        check_cost(cost)?;
+
+        let (salt, hash): (&str, &str) = fields[3].split_at(22);
+
+        return Ok(BcryptHash {
+            variant: variant,
+            cost: cost,
+            salt: radix64_decode(salt, BCRYPT_SALT_LENGTH)?,
+            hash: radix64_decode(hash, BCRYPT_HASH_LENGTH)?
+        });
+    }
+
+    pub fn to_modular_crypt(&self) -> String {
+        return format!("${}${:02}${}{}", self.variant.prefix(), self.cost, radix64_encode(&self.salt), radix64_encode(&self.hash));
+    }
+}
+
+fn check_cost(cost: u32) -> Result<(), String> {
+    if !(BCRYPT_MIN_COST..=BCRYPT_MAX_COST).contains(&cost) {
+        return Err(format!("bcrypt cost must be between {} and {}", BCRYPT_MIN_COST, BCRYPT_MAX_COST));
+    }
+    return Ok(());
+}
+
+
+
+
+// bcrypt's own base64: a different alphabet from RFC 4648 and no padding
+pub fn radix64_encode(bytes: &[u8]) -> String {
+    let mut encoded: String = String::new();
+
+    for chunk in bytes.chunks(3) {
+        let mut group: u32 = 0;
+        for (i, byte) in chunk.iter().enumerate() {
+            group |= (*byte as u32) << (16 - 8 * i);
+        }
+        for i in 0..chunk.len() + 1 {
+            encoded.push(BCRYPT_RADIX64_ALPHABET[((group >> (18 - 6 * i)) & 0x3f) as usize] as char);
+        }
+    }
+    return encoded;
+}
+
+
+
+
+pub fn radix64_decode(encoded: &str, length: usize) -> Result<Vec<u8>, String> {
+    let mut bits: u32 = 0;
+    let mut bit_count: u32 = 0;
+    let mut decoded: Vec<u8> = Vec::new();
+
+    for character in encoded.bytes() {
+        let value: usize = match BCRYPT_RADIX64_ALPHABET.iter().position(|c: &u8| *c == character) {
+            Some(value) => value,
+            None => return Err(format!("invalid bcrypt base64 character {:?}", character as char))
+        };
+
+        bits = (bits << 6) | value as u32;
+        bit_count += 6;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            decoded.push((bits >> bit_count) as u8);
+            bits &= (1 << bit_count) - 1;
+        }
+    }
+
+    if decoded.len() < length {
+        return Err(format!("bcrypt base64 value must decode to {} bytes", length));
+    }
+    decoded.truncate(length);
+    return Ok(decoded);
+}
+
+
+
+
+// Expensive key schedule: the salted expansion, then 2^cost rounds alternating between the key and the salt
+pub fn eks_blowfish_setup(cost: u32, salt: &[u8], key: &[u8]) -> Blowfish {
+    let mut state: Blowfish = Blowfish::initial_state();
+    state.expand_key(key, salt);
+
+    for _ in 0..1u64 << cost {
+        state.expand_key(key, &[]);
+        state.expand_key(salt, &[]);
+    }
+    return state;
+}
+
+
+
+
+pub fn bcrypt(password: &[u8], cost: u32, salt: &[u8]) -> Result<Vec<u8>, String> {
+    check_cost(cost)?;
+    if salt.len() != BCRYPT_SALT_LENGTH {
+        return Err(format!("bcrypt salt must be {} bytes", BCRYPT_SALT_LENGTH));
+    }
+
+    let mut key: Vec<u8> = [password, &[0]].concat();
+    key.truncate(BCRYPT_MAX_KEY_LENGTH);
+
+    let state: Blowfish = eks_blowfish_setup(cost, salt, &key);
+    let mut ctext: Vec<u32> = BCRYPT_MAGIC.chunks(4).map(|chunk: &[u8]| u32::from_be_bytes(chunk.try_into().unwrap())).collect();
+
+    for _ in 0..64 {
+        for i in (0..ctext.len()).step_by(2) {
+            (ctext[i], ctext[i + 1]) = state.encrypt_words(ctext[i], ctext[i + 1]);
+        }
+    }
+
+    let mut hash: Vec<u8> = ctext.iter().flat_map(|word: &u32| word.to_be_bytes()).collect();
+    hash.truncate(BCRYPT_HASH_LENGTH);
+
+    return Ok(hash);
+}
+
+
+
+
+pub fn hash_password(password: &str, cost: u32, salt: &[u8], variant: &BcryptVariant) -> Result<String, String> {
+    let hash: Vec<u8> = bcrypt(password.as_bytes(), cost, salt)?;
+
+    return Ok(BcryptHash { variant: variant.clone(), cost: cost, salt: salt.to_vec(), hash: hash }.to_modular_crypt());
+}
+
+
+
+
+pub fn verify_password(password: &str, hash_string: &str) -> Result<bool, String> {
+    let expected: BcryptHash = BcryptHash::parse(hash_string)?;
+    let hash: Vec<u8> = bcrypt(password.as_bytes(), expected.cost, &expected.salt)?;
+
+    return Ok(constant_time_eq(&hash, &expected.hash));
+}
+
+
+
+
+#[derive(Debug, Args, Clone)]
+pub struct BcryptAlg {
+    /// Hash a password or verify it against a bcrypt hash
+    #[arg(short, long)]
+    pub operation: BcryptOperations,
+    /// Password (only the first 72 bytes are used)
+    #[arg(short, long)]
+    pub password: String,
+    /// Cost factor, the key schedule runs 2^cost times
+    #[arg(short, long, default_value_t = 12)]
+    pub cost: u32,
+    /// Salt as 22 bcrypt base64 characters (a random salt is generated when omitted)
+    #[arg(short, long)]
+    pub salt: Option<String>,
+    /// Version prefix of the produced hash
+    #[arg(long, value_enum, default_value = "2b")]
+    pub variant: BcryptVariant,
+    /// bcrypt hash to verify the password against, e.g. $2b$12$...
+    #[arg(long, required_if_eq("operation", "verify"))]
+    pub hash: Option<String>
+}
+
+impl BcryptAlg {
+    pub fn execute(self) -> () {
+        match self.operation {
+            BcryptOperations::Hash => match self.hash_password() {
+                Ok(hash) => println!("{}", hash),
+                Err(error) => println!("{}", error)
+            },
+            BcryptOperations::Verify => match verify_password(&self.password, self.hash.as_deref().unwrap_or_default()) {
+                Ok(true) => println!("OK"),
+                Ok(false) => {
+                    println!("FAILED");
+                    std::process::exit(1);
+                }
+                Err(error) => {
+                    println!("{}", error);
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+
+    fn hash_password(&self) -> Result<String, String> {
+        let salt: Vec<u8> = match &self.salt {
+            Some(salt) if salt.len() == 22 => radix64_decode(salt, BCRYPT_SALT_LENGTH)?,
+            Some(_) => return Err("bcrypt salt must be 22 bcrypt base64 characters".to_string()),
+            None => random_salt(BCRYPT_SALT_LENGTH)
+        };
+
+        return hash_password(&self.password, self.cost, &salt, &self.variant);
+    }
+}
+
+#[cfg(test)]
+mod bcrypt_test {
+    use super::*;
+
+    fn hash_with_salt(password: &str, hash_string: &str) -> String {
+        let expected: BcryptHash = BcryptHash::parse(hash_string).unwrap();
+
+        return hash_password(password, expected.cost, &expected.salt, &expected.variant).unwrap();
+    }
+
+    #[test]
+    fn openwall_vectors_test() -> () {
+        let vectors: [(&str, &str); 5] = [
+            ("U*U", "$2a$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW"),
+            ("U*U*", "$2a$05$CCCCCCCCCCCCCCCCCCCCC.VGOzA784oUp/Z0DY336zx7pLYAy0lwK"),
+            ("U*U*U", "$2a$05$XXXXXXXXXXXXXXXXXXXXXOAcXxm9kjPGEMsLznoKqmqw7tc8WCx4a"),
+            ("", "$2a$05$CCCCCCCCCCCCCCCCCCCCC.7uG0VCzI2bS7j6ymqJi9CdcdxiRTWNy"),
+            ("0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789chars after 72 are ignored", "$2a$05$abcdefghijklmnopqrstuu5s2v8.iXieOjg/.AySBTTZIIVFJeBui")
+        ];
+
+        for (password, hash_string) in vectors {
+            assert_eq!(hash_with_salt(password, hash_string), hash_string);
+            assert!(verify_password(password, hash_string).unwrap());
+        }
+    }
+
+    #[test]
+    fn verify_test() -> () {
+        let hash_string: String = hash_password("correct horse", 4, &random_salt(BCRYPT_SALT_LENGTH), &BcryptVariant::V2b).unwrap();
+
+        assert!(hash_string.starts_with("$2b$04$"));
+        assert!(verify_password("correct horse", &hash_string).unwrap());
+        assert!(!verify_password("correct horse!", &hash_string).unwrap());
+        assert!(verify_password("U*U", "$2x$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW").is_err());
+        assert!(verify_password("U*U", "$2a$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOe").is_err());
+        assert!(verify_password("x", &format!("$2b$04$a{}", "é".repeat(26))).is_err());
+        assert!(bcrypt(b"password", 3, &[0; 16]).is_err());
+    }
+
+    #[test]
+    fn radix64_test() -> () {
+        let salt: Vec<u8> = random_salt(BCRYPT_SALT_LENGTH);
+
+        assert_eq!(radix64_encode(&salt).len(), 22);
+        assert_eq!(radix64_decode(&radix64_encode(&salt), BCRYPT_SALT_LENGTH).unwrap(), salt);
+        assert_eq!(radix64_encode(&[0; 23]).len(), 31);
+        assert!(radix64_decode("CCCC+CCC", 6).is_err());
+    }
+
+    #[test]
+    fn execute_hash_test() -> () {
+        let instance_1: BcryptAlg = BcryptAlg {
+            operation: BcryptOperations::Hash,
+            password: "U*U".to_string(),
+            cost: 5,
+            salt: Some("CCCCCCCCCCCCCCCCCCCCC.".to_string()),
+            variant: BcryptVariant::V2a,
+            hash: None
+        };
+        assert_eq!(instance_1.hash_password().unwrap(), "$2a$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW");
+
+        let instance_2: BcryptAlg = BcryptAlg {
+            salt: Some("short".to_string()),
+            ..instance_1.clone()
+        };
+        assert!(instance_2.hash_password().is_err());
+
+        let instance_3: BcryptAlg = BcryptAlg {
+            salt: None,
+            cost: 4,
+            variant: BcryptVariant::V2y,
+            ..instance_1.clone()
+        };
+        assert!(verify_password("U*U", &instance_3.hash_password().unwrap()).unwrap());
+    }
+}
@@ -0,0 +1,148 @@
+use clap::Args;
+use crate::{hashes::{hash_function::HashAlgorithms, hmac::hmac}, ops::{bytes_to_hex, decode_key, KeyFormat}};
+
+// HKDF-Extract: PRK = HMAC-Hash(salt, IKM), the salt defaulting to HashLen zero bytes
+pub fn hkdf_extract(algorithm: &HashAlgorithms, salt: &[u8], input_key: &[u8]) -> Vec<u8> {
+    if salt.is_empty() {
+        return hmac(algorithm, &vec![0; algorithm.hasher().output_size()], input_key);
+    }
+    return hmac(algorithm, salt, input_key);
+}
+
+
+
+
+// HKDF-Expand: T(i) = HMAC-Hash(PRK, T(i-1) || info || i), for at most 255 blocks
+pub fn hkdf_expand(algorithm: &HashAlgorithms, pseudorandom_key: &[u8], info: &[u8], length: usize) -> Result<Vec<u8>, String> {
+    let hash_length: usize = algorithm.hasher().output_size();
+    if length == 0 || length > 255 * hash_length {
+        return Err(format!("HKDF-{} output length must be between 1 and {} bytes", algorithm.name(), 255 * hash_length));
+    }
+
+    let mut output: Vec<u8> = Vec::with_capacity(length);
+    let mut block: Vec<u8> = Vec::new();
+
+    for counter in 1..=length.div_ceil(hash_length) as u8 {
+        block = hmac(algorithm, pseudorandom_key, &[block.as_slice(), info, &[counter]].concat());
+        output.extend_from_slice(&block);
+    }
+
+    output.truncate(length);
+    return Ok(output);
+}
+
+
+
+
+// RFC 5869
+pub fn hkdf(algorithm: &HashAlgorithms, input_key: &[u8], salt: &[u8], info: &[u8], length: usize) -> Result<Vec<u8>, String> {
+    return hkdf_expand(algorithm, &hkdf_extract(algorithm, salt, input_key), info, length);
+}
+
+
+
+
+#[derive(Debug, Args, Clone)]
+pub struct HkdfAlg {
+    /// Hash function used by HMAC
+    #[arg(short, long, default_value = "sha256")]
+    pub algorithm: HashAlgorithms,
+    /// Master secret (input keying material)
+    #[arg(short, long)]
+    pub key: String,
+    /// Key encoding
+    #[arg(long, value_enum, default_value = "hex")]
+    pub key_format: KeyFormat,
+    /// Salt (HashLen zero bytes when omitted)
+    #[arg(short, long)]
+    pub salt: Option<String>,
+    /// Encoding of the given salt
+    #[arg(long, value_enum, default_value = "hex")]
+    pub salt_format: KeyFormat,
+    /// Context and application specific info, repeat to derive one subkey per info
+    #[arg(short, long)]
+    pub info: Vec<String>,
+    /// Length in bytes of each derived key
+    #[arg(short, long, default_value_t = 32)]
+    pub length: usize
+}
+
+impl HkdfAlg {
+    pub fn execute(self) -> () {
+        match self.derive() {
+            Ok(keys) => {
+                for (info, key) in keys {
+                    println!("{}: {}", info, bytes_to_hex(&key));
+                }
+            }
+            Err(error) => println!("{}", error)
+        }
+    }
+
+    // The extract step runs once, every info string is expanded from the same PRK
+    fn derive(&self) -> Result<Vec<(String, Vec<u8>)>, String> {
+        let salt: Vec<u8> = match &self.salt {
+            Some(salt) => decode_key(salt, &self.salt_format)?,
+            None => Vec::new()
+        };
+        let pseudorandom_key: Vec<u8> = hkdf_extract(&self.algorithm, &salt, &decode_key(&self.key, &self.key_format)?);
+        let infos: Vec<String> = if self.info.is_empty() { vec![String::new()] } else { self.info.clone() };
+
+        let mut keys: Vec<(String, Vec<u8>)> = Vec::new();
+        for info in infos {
+            let key: Vec<u8> = hkdf_expand(&self.algorithm, &pseudorandom_key, info.as_bytes(), self.length)?;
+            keys.push((info, key));
+        }
+        return Ok(keys);
+    }
+}
+
+#[cfg(test)]
+mod hkdf_test {
+    use crate::ops::hex_to_bytes;
+    use super::*;
+
+    #[test]
+    fn rfc_5869_test() -> () {
+        let prk: Vec<u8> = hkdf_extract(&HashAlgorithms::Sha256, &hex_to_bytes("000102030405060708090a0b0c").unwrap(), &[0x0b; 22]);
+        assert_eq!(bytes_to_hex(&prk), "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5");
+        assert_eq!(bytes_to_hex(&hkdf_expand(&HashAlgorithms::Sha256, &prk, &hex_to_bytes("f0f1f2f3f4f5f6f7f8f9").unwrap(), 42).unwrap()),
+                   "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865");
+
+        assert_eq!(bytes_to_hex(&hkdf(&HashAlgorithms::Sha256, &[0x0b; 22], &[], &[], 42).unwrap()),
+                   "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8");
+        assert_eq!(bytes_to_hex(&hkdf(&HashAlgorithms::Sha1, &[0x0b; 11], &hex_to_bytes("000102030405060708090a0b0c").unwrap(), &hex_to_bytes("f0f1f2f3f4f5f6f7f8f9").unwrap(), 42).unwrap()),
+                   "085a01ea1b10f36933068b56efa5ad81a4f14b822f5b091568a9cdd4f155fda2c22e422478d305f3f896");
+    }
+
+    #[test]
+    fn output_length_test() -> () {
+        assert_eq!(hkdf(&HashAlgorithms::Sha256, b"secret", b"salt", b"", 255 * 32).unwrap().len(), 255 * 32);
+        assert!(hkdf(&HashAlgorithms::Sha256, b"secret", b"salt", b"", 255 * 32 + 1).is_err());
+        assert!(hkdf(&HashAlgorithms::Sha256, b"secret", b"salt", b"", 0).is_err());
+    }
+
+    #[test]
+    fn execute_derive_test() -> () {
+        let instance_1: HkdfAlg = HkdfAlg {
+            algorithm: HashAlgorithms::Sha256,
+            key: "0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b".to_string(),
+            key_format: KeyFormat::Hex,
+            salt: None,
+            salt_format: KeyFormat::Hex,
+            info: vec![],
+            length: 42
+        };
+        assert_eq!(bytes_to_hex(&instance_1.derive().unwrap()[0].1), "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8");
+
+        let instance_2: HkdfAlg = HkdfAlg {
+            info: vec!["encryption".to_string(), "authentication".to_string()],
+            length: 16,
+            ..instance_1.clone()
+        };
+        let keys: Vec<(String, Vec<u8>)> = instance_2.derive().unwrap();
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[1].0, "authentication");
+        assert_ne!(keys[0].1, keys[1].1);
+    }
+}
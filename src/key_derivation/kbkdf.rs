@@ -0,0 +1,198 @@
+use clap::{Args, ValueEnum};
+use crate::{algorithms::advanced_encryption_standard::Aes, hashes::{hash_function::HashAlgorithms, hmac::hmac}, macs::cmac::cmac, ops::{bytes_to_hex, decode_key, hex_to_bytes, KeyFormat}};
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum KbkdfMode {
+    Counter,
+    Feedback,
+    DoublePipeline
+}
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum KbkdfPrf {
+    HmacSha1,
+    HmacSha256,
+    HmacSha384,
+    HmacSha512,
+    CmacAes
+}
+impl KbkdfPrf {
+    fn output_size(&self) -> usize {
+        match self {
+            KbkdfPrf::HmacSha1 => 20,
+            KbkdfPrf::HmacSha256 => 32,
+            KbkdfPrf::HmacSha384 => 48,
+            KbkdfPrf::HmacSha512 => 64,
+            KbkdfPrf::CmacAes => 16
+        }
+    }
+
+    fn mac(&self, key: &[u8], data: &[u8]) -> Result<Vec<u8>, String> {
+        match self {
+            KbkdfPrf::HmacSha1 => Ok(hmac(&HashAlgorithms::Sha1, key, data)),
+            KbkdfPrf::HmacSha256 => Ok(hmac(&HashAlgorithms::Sha256, key, data)),
+            KbkdfPrf::HmacSha384 => Ok(hmac(&HashAlgorithms::Sha384, key, data)),
+            KbkdfPrf::HmacSha512 => Ok(hmac(&HashAlgorithms::Sha512, key, data)),
+            KbkdfPrf::CmacAes => Ok(cmac(&Aes::new(key)?, data))
+        }
+    }
+}
+
+// Label || 0x00 || Context || [L]_32, L being the output length in bits
+fn fixed_input(label: &[u8], context: &[u8], length: usize) -> Vec<u8> {
+    return [label, &[0], context, &((length * 8) as u32).to_be_bytes()].concat();
+}
+
+
+
+
+// NIST SP 800-108r1 with a 32 bit counter placed before the fixed input data:
+// counter mode    K(i) = PRF(K, [i] || fixed)
+// feedback mode   K(i) = PRF(K, K(i-1) || [i] || fixed), K(0) = IV
+// double pipeline K(i) = PRF(K, A(i) || [i] || fixed), A(i) = PRF(K, A(i-1)), A(0) = fixed
+pub fn kbkdf(mode: &KbkdfMode, prf: &KbkdfPrf, key: &[u8], label: &[u8], context: &[u8], iv: &[u8], length: usize) -> Result<Vec<u8>, String> {
+    if length == 0 || length > (u32::MAX / 8) as usize {
+        return Err(format!("KBKDF output length must be between 1 and {} bytes", u32::MAX / 8));
+    }
+    if !iv.is_empty() && mode != &KbkdfMode::Feedback {
+        return Err("an IV is only used by the feedback mode".to_string());
+    }
+
+    let fixed: Vec<u8> = fixed_input(label, context, length);
+    let mut output: Vec<u8> = Vec::with_capacity(length);
+    let mut previous: Vec<u8> = iv.to_vec();
+    let mut pipeline: Vec<u8> = fixed.clone();
+
+    for counter in 1..=length.div_ceil(prf.output_size()) as u32 {
+        let chaining: Vec<u8> = match mode {
+            KbkdfMode::Counter => Vec::new(),
+            KbkdfMode::Feedback => previous,
+            KbkdfMode::DoublePipeline => {
+                pipeline = prf.mac(key, &pipeline)?;
+                pipeline.clone()
+            }
+        };
+
+        previous = prf.mac(key, &[chaining.as_slice(), &counter.to_be_bytes(), &fixed].concat())?;
+        output.extend_from_slice(&previous);
+    }
+
+    output.truncate(length);
+    return Ok(output);
+}
+
+
+
+
+#[derive(Debug, Args, Clone)]
+pub struct KbkdfAlg {
+    /// KBKDF mode
+    #[arg(short, long, value_enum, default_value = "counter")]
+    pub mode: KbkdfMode,
+    /// Pseudorandom function (CMAC-AES takes a 16, 24 or 32 byte key)
+    #[arg(long, value_enum, default_value = "hmac-sha256")]
+    pub prf: KbkdfPrf,
+    /// Master secret (key derivation key)
+    #[arg(short, long)]
+    pub key: String,
+    /// Key encoding
+    #[arg(long, value_enum, default_value = "hex")]
+    pub key_format: KeyFormat,
+    /// Label identifying the purpose of the derived keys
+    #[arg(long, default_value = "")]
+    pub label: String,
+    /// Context of the derivation, repeat to derive one subkey per context
+    #[arg(short, long)]
+    pub context: Vec<String>,
+    /// IV in hex for the feedback mode (empty when omitted)
+    #[arg(long)]
+    pub iv: Option<String>,
+    /// Length in bytes of each derived key
+    #[arg(short, long, default_value_t = 32)]
+    pub length: usize
+}
+
+impl KbkdfAlg {
+    pub fn execute(self) -> () {
+        match self.derive() {
+            Ok(keys) => {
+                for (context, key) in keys {
+                    println!("{}: {}", context, bytes_to_hex(&key));
+                }
+            }
+            Err(error) => println!("{}", error)
+        }
+    }
+
+    fn derive(&self) -> Result<Vec<(String, Vec<u8>)>, String> {
+        let key: Vec<u8> = decode_key(&self.key, &self.key_format)?;
+        let iv: Vec<u8> = match &self.iv {
+            Some(iv) => hex_to_bytes(iv)?,
+            None => Vec::new()
+        };
+        let contexts: Vec<String> = if self.context.is_empty() { vec![String::new()] } else { self.context.clone() };
+
+        let mut keys: Vec<(String, Vec<u8>)> = Vec::new();
+        for context in contexts {
+            let derived: Vec<u8> = kbkdf(&self.mode, &self.prf, &key, self.label.as_bytes(), context.as_bytes(), &iv, self.length)?;
+            keys.push((context, derived));
+        }
+        return Ok(keys);
+    }
+}
+
+#[cfg(test)]
+mod kbkdf_test {
+    use super::*;
+
+    fn kbkdf_hex(mode: KbkdfMode, prf: KbkdfPrf, key: &[u8], iv: &[u8], length: usize) -> String {
+        return bytes_to_hex(&kbkdf(&mode, &prf, key, b"label", b"context", iv, length).unwrap());
+    }
+
+    #[test]
+    fn counter_mode_test() -> () {
+        assert_eq!(kbkdf_hex(KbkdfMode::Counter, KbkdfPrf::HmacSha256, b"master secret", &[], 48), "12627275d7a31458d8c617abf88b0287734614ac0f39d5515cc9cc9185c93e727e44d199e8961a12b2ef6307a9bd0967");
+        assert_eq!(kbkdf_hex(KbkdfMode::Counter, KbkdfPrf::CmacAes, &[0x2b; 16], &[], 40), "bebfb38b893111e6394fdd909427bb0813a8d6df4e39f8f6701da2a93d342eabd5aa3161379f5e7e");
+    }
+
+    #[test]
+    fn feedback_mode_test() -> () {
+        assert_eq!(kbkdf_hex(KbkdfMode::Feedback, KbkdfPrf::HmacSha256, b"master secret", &[0x11; 32], 48), "232d719260681d0de33a55936c74b47c5ad2d3bab31e6711090778c9825d368a745ec29c17afa350c8987888999345ad");
+        assert_eq!(kbkdf_hex(KbkdfMode::Feedback, KbkdfPrf::HmacSha1, b"master secret", &[], 48), "de5a60a7f2e35ee489fcd602d77420c31120cbb2d9241705e6073c249ed7767f3210d0452bf6e23f1b0a0e29dc04d5ff");
+    }
+
+    // OpenSSL has no double-pipeline mode, but its feedback mode seeded with A(1) computes K(1):
+    // openssl kdf -keylen 32 -kdfopt mode:FEEDBACK -kdfopt mac:HMAC -kdfopt digest:SHA256 -kdfopt salt:label
+    //             -kdfopt hexinfo:<context || [L]_32> -kdfopt use-l:0 -kdfopt hexseed:<A(1)> KBKDF
+    // with A(1) from openssl mac -digest SHA256 HMAC. K(2) is HMAC(K, A(2) || [2] || fixed) from openssl mac as well
+    #[test]
+    fn double_pipeline_mode_test() -> () {
+        assert_eq!(kbkdf_hex(KbkdfMode::DoublePipeline, KbkdfPrf::HmacSha256, b"master secret", &[], 32), "91a544b8a6c782fe4d1af5065a5d4aaf011ee87eeecd4e933039dc94c3ae0e65");
+        assert_eq!(kbkdf_hex(KbkdfMode::DoublePipeline, KbkdfPrf::HmacSha256, b"master secret", &[], 40), "d6651e3f7aa184e2685d00da8495ee590846cf955629d0d86bd205f88f7785af4e483c5431bb71f3");
+        assert!(kbkdf(&KbkdfMode::DoublePipeline, &KbkdfPrf::HmacSha256, b"key", b"", b"", &[0; 16], 32).is_err());
+        assert!(kbkdf(&KbkdfMode::Counter, &KbkdfPrf::CmacAes, b"short key", b"", b"", &[], 32).is_err());
+    }
+
+    #[test]
+    fn execute_derive_test() -> () {
+        let instance_1: KbkdfAlg = KbkdfAlg {
+            mode: KbkdfMode::Counter,
+            prf: KbkdfPrf::HmacSha256,
+            key: "master secret".to_string(),
+            key_format: KeyFormat::Text,
+            label: "label".to_string(),
+            context: vec!["context".to_string(), "other context".to_string()],
+            iv: None,
+            length: 48
+        };
+        let keys: Vec<(String, Vec<u8>)> = instance_1.derive().unwrap();
+        assert_eq!(bytes_to_hex(&keys[0].1), "12627275d7a31458d8c617abf88b0287734614ac0f39d5515cc9cc9185c93e727e44d199e8961a12b2ef6307a9bd0967");
+        assert_ne!(keys[0].1, keys[1].1);
+
+        let instance_2: KbkdfAlg = KbkdfAlg {
+            iv: Some("zz".to_string()),
+            ..instance_1.clone()
+        };
+        assert!(instance_2.derive().is_err());
+    }
+}
@@ -0,0 +1,259 @@
+use clap::{Args, ValueEnum};
+use rand::{rngs::OsRng, RngCore};
+use crate::{hashes::{hash_function::{HashAlgorithms, HashFunction}, hmac::Hmac}, key_derivation::{argon2::{argon2, Argon2Parameters, Argon2Variant, DEFAULT_MEMORY, DEFAULT_TIME, MAX_MEMORY}, scrypt::{scrypt, ScryptParameters, DEFAULT_LOG_N, MAX_LOG_N}}, ops::{bytes_to_hex, decode_key, KeyFormat}};
+
+// OWASP recommendation for PBKDF2-HMAC-SHA256
+pub const DEFAULT_ITERATIONS: u32 = 600000;
+pub const PASSWORD_SALT_LENGTH: usize = 16;
+// KDF id byte, 4 byte cost and salt
+pub const PASSWORD_HEADER_LENGTH: usize = 5 + PASSWORD_SALT_LENGTH;
+// Stored iteration counts are bounded so a crafted header can't keep decryption busy for minutes,
+// the scrypt and Argon2 costs are bounded by MAX_LOG_N and MAX_MEMORY
+const MAX_ITERATIONS: u32 = 10000000;
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum Pbkdf2Prf {
+    HmacSha1,
+    HmacSha256,
+    HmacSha512
+}
+impl Pbkdf2Prf {
+    fn hash_algorithm(&self) -> HashAlgorithms {
+        match self {
+            Pbkdf2Prf::HmacSha1 => HashAlgorithms::Sha1,
+            Pbkdf2Prf::HmacSha256 => HashAlgorithms::Sha256,
+            Pbkdf2Prf::HmacSha512 => HashAlgorithms::Sha512
+        }
+    }
+}
+
+fn prf(algorithm: &HashAlgorithms, password: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac: Hmac = Hmac::new(algorithm, password);
+    mac.update(data);
+
+    return mac.finalize();
+}
+
+
+
+
+// RFC 8018: block i is U_1 ^ U_2 ^ ... ^ U_c with U_1 = PRF(P, S || INT(i)) and U_j = PRF(P, U_{j-1})
+pub fn pbkdf2(prf_algorithm: &Pbkdf2Prf, password: &[u8], salt: &[u8], iterations: u32, length: usize) -> Result<Vec<u8>, String> {
+    if iterations == 0 {
+        return Err("PBKDF2 needs at least one iteration".to_string());
+    }
+    if length == 0 {
+        return Err("derived key length must be at least one byte".to_string());
+    }
+
+    let algorithm: HashAlgorithms = prf_algorithm.hash_algorithm();
+    let mut derived_key: Vec<u8> = Vec::new();
+
+    for block_index in 1..=length.div_ceil(algorithm.hasher().output_size()) as u32 {
+        let mut u: Vec<u8> = prf(&algorithm, password, &[salt, &block_index.to_be_bytes()].concat());
+        let mut block: Vec<u8> = u.clone();
+
+        for _ in 1..iterations {
+            u = prf(&algorithm, password, &u);
+            for (byte, u_byte) in block.iter_mut().zip(&u) {
+                *byte ^= u_byte;
+            }
+        }
+        derived_key.extend(block);
+    }
+
+    derived_key.truncate(length);
+    return Ok(derived_key);
+}
+
+
+
+
+pub fn random_salt(length: usize) -> Vec<u8> {
+    let mut salt: Vec<u8> = vec![0; length];
+    OsRng.fill_bytes(&mut salt);
+
+    return salt;
+}
+
+
+
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum PasswordKdf {
+    /// PBKDF2-HMAC-SHA256
+    Pbkdf2,
+    /// scrypt with N = 2^17, r = 8, p = 1
+    Scrypt,
+    /// Argon2id with 19 MiB, 2 passes, 1 lane
+    Argon2id
+}
+impl PasswordKdf {
+    fn id(&self) -> u8 {
+        match self {
+            PasswordKdf::Pbkdf2 => 1,
+            PasswordKdf::Scrypt => 2,
+            PasswordKdf::Argon2id => 3
+        }
+    }
+
+    fn from_id(id: u8) -> Result<PasswordKdf, String> {
+        match id {
+            1 => Ok(PasswordKdf::Pbkdf2),
+            2 => Ok(PasswordKdf::Scrypt),
+            3 => Ok(PasswordKdf::Argon2id),
+            _ => Err(format!("unknown password KDF id {}", id))
+        }
+    }
+}
+
+// Stored in front of the ciphertext by the ciphers' --password flag. The cost is the PBKDF2 iteration count,
+// the scrypt log2(N) or the Argon2id memory in KiB, so decryption doesn't depend on the command line defaults
+#[derive(Debug, Clone, PartialEq)]
+pub struct PasswordHeader {
+    pub kdf: PasswordKdf,
+    pub cost: u32,
+    pub salt: Vec<u8>
+}
+impl PasswordHeader {
+    pub fn new(kdf: &PasswordKdf, iterations: u32) -> PasswordHeader {
+        let cost: u32 = match kdf {
+            PasswordKdf::Pbkdf2 => iterations,
+            PasswordKdf::Scrypt => DEFAULT_LOG_N,
+            PasswordKdf::Argon2id => DEFAULT_MEMORY
+        };
+        return PasswordHeader { kdf: kdf.clone(), cost: cost, salt: random_salt(PASSWORD_SALT_LENGTH) };
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        return [&[self.kdf.id()][..], &self.cost.to_be_bytes(), &self.salt].concat();
+    }
+
+    pub fn split(data: &[u8]) -> Result<(PasswordHeader, &[u8]), String> {
+        if data.len() < PASSWORD_HEADER_LENGTH {
+            return Err(format!("password encrypted data starts with a {} byte KDF header", PASSWORD_HEADER_LENGTH));
+        }
+        let (header, rest): (&[u8], &[u8]) = data.split_at(PASSWORD_HEADER_LENGTH);
+
+        return Ok((PasswordHeader {
+            kdf: PasswordKdf::from_id(header[0])?,
+            cost: u32::from_be_bytes(header[1..5].try_into().unwrap()),
+            salt: header[5..].to_vec()
+        }, rest));
+    }
+
+    pub fn key(&self, password: &str, length: usize) -> Result<Vec<u8>, String> {
+        match self.kdf {
+            PasswordKdf::Pbkdf2 if self.cost > MAX_ITERATIONS => Err(format!("PBKDF2 iterations above {} are refused", MAX_ITERATIONS)),
+            PasswordKdf::Pbkdf2 => pbkdf2(&Pbkdf2Prf::HmacSha256, password.as_bytes(), &self.salt, self.cost, length),
+            PasswordKdf::Scrypt if self.cost > MAX_LOG_N => Err(format!("scrypt log2(N) above {} is refused", MAX_LOG_N)),
+            PasswordKdf::Scrypt => scrypt(password.as_bytes(), &self.salt, &ScryptParameters { log_n: self.cost, r: 8, p: 1, length: length }),
+            PasswordKdf::Argon2id if self.cost > MAX_MEMORY => Err(format!("Argon2 memory above {} KiB is refused", MAX_MEMORY)),
+            PasswordKdf::Argon2id => {
+                let parameters: Argon2Parameters = Argon2Parameters { variant: Argon2Variant::Argon2id, memory: self.cost, time: DEFAULT_TIME, parallelism: 1, length: length };
+                argon2(&parameters, password.as_bytes(), &self.salt, &[], &[])
+            }
+        }
+    }
+}
+
+
+
+
+#[derive(Debug, Args, Clone)]
+pub struct Pbkdf2Alg {
+    /// Password to derive the key from
+    #[arg(short, long)]
+    pub password: String,
+    /// Salt (a random 16 byte salt is generated when omitted)
+    #[arg(short, long)]
+    pub salt: Option<String>,
+    /// Encoding of the given salt
+    #[arg(long, value_enum, default_value = "text")]
+    pub salt_format: KeyFormat,
+    /// Pseudorandom function
+    #[arg(long, value_enum, default_value = "hmac-sha256")]
+    pub prf: Pbkdf2Prf,
+    /// Iteration count
+    #[arg(short, long, default_value_t = DEFAULT_ITERATIONS)]
+    pub iterations: u32,
+    /// Derived key length in bytes
+    #[arg(short, long, default_value_t = 32)]
+    pub length: usize
+}
+
+impl Pbkdf2Alg {
+    pub fn execute(self) -> () {
+        let salt: Vec<u8> = match &self.salt {
+            Some(salt) => match decode_key(salt, &self.salt_format) {
+                Ok(salt) => salt,
+                Err(error) => {
+                    println!("{}", error);
+                    return ();
+                }
+            },
+            None => random_salt(PASSWORD_SALT_LENGTH)
+        };
+
+        match pbkdf2(&self.prf, self.password.as_bytes(), &salt, self.iterations, self.length) {
+            Ok(key) => {
+                println!("salt: {}", bytes_to_hex(&salt));
+                println!("key: {}", bytes_to_hex(&key));
+            }
+            Err(error) => println!("{}", error)
+        }
+    }
+}
+
+#[cfg(test)]
+mod pbkdf2_test {
+    use super::*;
+
+    fn pbkdf2_hex(prf_algorithm: Pbkdf2Prf, password: &[u8], salt: &[u8], iterations: u32, length: usize) -> String {
+        return bytes_to_hex(&pbkdf2(&prf_algorithm, password, salt, iterations, length).unwrap());
+    }
+
+    #[test]
+    fn rfc_6070_test() -> () {
+        assert_eq!(pbkdf2_hex(Pbkdf2Prf::HmacSha1, b"password", b"salt", 1, 20), "0c60c80f961f0e71f3a9b524af6012062fe037a6");
+        assert_eq!(pbkdf2_hex(Pbkdf2Prf::HmacSha1, b"password", b"salt", 4096, 20), "4b007901b765489abead49d926f721d065a429c1");
+        assert_eq!(pbkdf2_hex(Pbkdf2Prf::HmacSha1, b"passwordPASSWORDpassword", b"saltSALTsaltSALTsaltSALTsaltSALTsalt", 4096, 25), "3d2eec4fe41c849b80c8d83662c0e44a8b291a964cf2f07038");
+        assert_eq!(pbkdf2_hex(Pbkdf2Prf::HmacSha1, b"pass\0word", b"sa\0lt", 4096, 16), "56fa6aa75548099dcc37d7f03425e0c3");
+    }
+
+    #[test]
+    fn sha2_prf_test() -> () {
+        assert_eq!(pbkdf2_hex(Pbkdf2Prf::HmacSha256, b"passwd", b"salt", 1, 64), "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783");
+        assert_eq!(pbkdf2_hex(Pbkdf2Prf::HmacSha512, b"password", b"salt", 2, 64), "e1d9c16aa681708a45f5c7c4e215ceb66e011a2e9f0040713f18aefdb866d53cf76cab2868a39b9f7840edce4fef5a82be67335c77a6068e04112754f27ccf4e");
+        assert!(pbkdf2(&Pbkdf2Prf::HmacSha256, b"password", b"salt", 0, 32).is_err());
+    }
+
+    #[test]
+    fn password_header_test() -> () {
+        assert_ne!(random_salt(PASSWORD_SALT_LENGTH), random_salt(PASSWORD_SALT_LENGTH));
+        assert!(PasswordHeader::split(&[1; PASSWORD_HEADER_LENGTH - 1]).is_err());
+        assert!(PasswordHeader::split(&[9; PASSWORD_HEADER_LENGTH]).is_err());
+
+        let header: PasswordHeader = PasswordHeader::new(&PasswordKdf::Argon2id, 10);
+        let data: Vec<u8> = [header.to_bytes(), vec![7; 4]].concat();
+        assert_eq!(header.cost, DEFAULT_MEMORY);
+        assert_eq!(PasswordHeader::split(&data), Ok((header, &[7; 4][..])));
+    }
+
+    #[test]
+    fn password_key_test() -> () {
+        let header_1: PasswordHeader = PasswordHeader { kdf: PasswordKdf::Pbkdf2, cost: 1, salt: b"salt".to_vec() };
+        assert_eq!(header_1.key("passwd", 64), pbkdf2(&Pbkdf2Prf::HmacSha256, b"passwd", b"salt", 1, 64));
+
+        let header_2: PasswordHeader = PasswordHeader { kdf: PasswordKdf::Scrypt, cost: 4, salt: b"somesalt".to_vec() };
+        assert_eq!(header_2.key("password", 32), scrypt(b"password", b"somesalt", &ScryptParameters { log_n: 4, r: 8, p: 1, length: 32 }));
+
+        let header_3: PasswordHeader = PasswordHeader { kdf: PasswordKdf::Argon2id, cost: 32, ..header_2.clone() };
+        let parameters: Argon2Parameters = Argon2Parameters { variant: Argon2Variant::Argon2id, memory: 32, time: DEFAULT_TIME, parallelism: 1, length: 32 };
+        assert_eq!(header_3.key("password", 32), argon2(&parameters, b"password", b"somesalt", &[], &[]));
+
+        assert!(PasswordHeader { cost: MAX_ITERATIONS + 1, ..header_1.clone() }.key("passwd", 64).is_err());
+        assert!(PasswordHeader { cost: MAX_LOG_N + 1, ..header_2.clone() }.key("password", 32).is_err());
+        assert!(PasswordHeader { cost: u32::MAX, ..header_3.clone() }.key("password", 32).is_err());
+    }
+}
@@ -0,0 +1,115 @@
+use crate::ops::{base64_to_bytes, bytes_to_base64};
+
+// PHC string format: $<id>[$v=<version>][$<param>=<value>(,<param>=<value>)*][$<salt>[$<hash>]]
+// with salt and hash in unpadded base64
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhcString {
+    pub algorithm: String,
+    pub version: Option<u32>,
+    pub parameters: Vec<(String, String)>,
+    pub salt: Vec<u8>,
+    pub hash: Vec<u8>
+}
+impl PhcString {
+    pub fn parse(phc: &str) -> Result<PhcString, String> {
+        let mut fields: Vec<&str> = phc.split('$').collect();
+        if fields.len() < 2 || !fields[0].is_empty() || fields[1].is_empty() {
+            return Err("PHC string must start with $<algorithm>".to_string());
+        }
+        fields.remove(0);
+
+        let algorithm: String = fields.remove(0).to_string();
+        let mut version: Option<u32> = None;
+        if let Some(field) = fields.first().and_then(|field: &&str| field.strip_prefix("v=")) {
+            version = Some(field.parse::<u32>().map_err(|_| format!("invalid PHC version {}", field))?);
+            fields.remove(0);
+        }
+
+        let mut parameters: Vec<(String, String)> = Vec::new();
+        if fields.first().is_some_and(|field: &&str| field.contains('=')) {
+            for parameter in fields.remove(0).split(',') {
+                match parameter.split_once('=') {
+                    Some((name, value)) if !name.is_empty() && !value.is_empty() => parameters.push((name.to_string(), value.to_string())),
+                    _ => return Err(format!("invalid PHC parameter {}", parameter))
+                }
+            }
+        }
+
+        if fields.len() != 2 {
+            return Err("PHC string must end with $<salt>$<hash>".to_string());
+        }
+
+        return Ok(PhcString {
+            algorithm: algorithm,
+            version: version,
+            parameters: parameters,
+            salt: decode_base64(fields[0])?,
+            hash: decode_base64(fields[1])?
+        });
+    }
+
+    pub fn parameter(&self, name: &str) -> Result<u32, String> {
+        return match self.parameters.iter().find(|(parameter, _): &&(String, String)| parameter == name) {
+            Some((_, value)) => value.parse::<u32>().map_err(|_| format!("PHC parameter {} must be a number", name)),
+            None => Err(format!("PHC string is missing the {} parameter", name))
+        };
+    }
+
+    pub fn encode(&self) -> String {
+        let mut phc: String = format!("${}", self.algorithm);
+
+        if let Some(version) = self.version {
+            phc += &format!("$v={}", version);
+        }
+        if !self.parameters.is_empty() {
+            let parameters: Vec<String> = self.parameters.iter().map(|(name, value): &(String, String)| format!("{}={}", name, value)).collect();
+            phc += &format!("${}", parameters.join(","));
+        }
+
+        return format!("{}${}${}", phc, encode_base64(&self.salt), encode_base64(&self.hash));
+    }
+}
+
+fn encode_base64(bytes: &[u8]) -> String {
+    return bytes_to_base64(bytes).trim_end_matches('=').to_string();
+}
+
+
+
+
+fn decode_base64(encoded: &str) -> Result<Vec<u8>, String> {
+    if encoded.is_empty() || encoded.contains('=') {
+        return Err("PHC salt and hash must be unpadded base64".to_string());
+    }
+    return base64_to_bytes(encoded);
+}
+
+
+
+
+#[cfg(test)]
+mod phc_test {
+    use super::*;
+
+    #[test]
+    fn parse_test() -> () {
+        let phc: PhcString = PhcString::parse("$argon2id$v=19$m=65536,t=2,p=1$c29tZXNhbHQ$RdescudvJCsgt3ub+b+dWRWJTmaaJObG").unwrap();
+
+        assert_eq!(phc.algorithm, "argon2id");
+        assert_eq!(phc.version, Some(19));
+        assert_eq!(phc.parameter("m").unwrap(), 65536);
+        assert_eq!(phc.salt, b"somesalt");
+        assert_eq!(phc.hash.len(), 24);
+        assert!(phc.parameter("x").is_err());
+        assert_eq!(phc.encode(), "$argon2id$v=19$m=65536,t=2,p=1$c29tZXNhbHQ$RdescudvJCsgt3ub+b+dWRWJTmaaJObG");
+    }
+
+    #[test]
+    fn malformed_test() -> () {
+        assert!(PhcString::parse("argon2id$v=19$m=8,t=1,p=1$c29tZXNhbHQ$aGFzaA").is_err());
+        assert!(PhcString::parse("$scrypt$ln=4,r=8,p=1$c29tZXNhbHQ").is_err());
+        assert!(PhcString::parse("$scrypt$ln=4,r=,p=1$c29tZXNhbHQ$aGFzaA").is_err());
+        assert!(PhcString::parse("$scrypt$ln=4,r=8,p=1$c29tZXNhbHQ=$aGFzaA").is_err());
+        assert_eq!(PhcString::parse("$scrypt$ln=4,r=8,p=1$c29tZXNhbHQ$aGFzaA").unwrap().version, None);
+    }
+}
@@ -0,0 +1,271 @@
+use clap::{Args, ValueEnum};
+use crate::{algorithms::salsa20_cipher::salsa20_core, key_derivation::{pbkdf2::{pbkdf2, random_salt, Pbkdf2Prf, PASSWORD_SALT_LENGTH}, phc::PhcString}, ops::{bytes_to_hex, constant_time_eq, decode_key, KeyFormat}};
+
+// OWASP recommendation: N = 2^17, r = 8, p = 1 (128 MiB)
+pub const DEFAULT_LOG_N: u32 = 17;
+// Costs read from a PHC string or a password header are bounded so crafted input can't allocate more than 1 GiB
+pub const MAX_LOG_N: u32 = 20;
+const MAX_MEMORY: u64 = 1 << 30;
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum ScryptOperations {
+    Hash,
+    Verify
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScryptParameters {
+    pub log_n: u32,
+    pub r: u32,
+    pub p: u32,
+    pub length: usize
+}
+impl ScryptParameters {
+    fn check(&self) -> Result<(), String> {
+        if self.log_n == 0 || self.log_n > 31 {
+            return Err("scrypt N must be a power of two between 2 and 2^31".to_string());
+        }
+        if self.r == 0 || self.p == 0 {
+            return Err("scrypt r and p must be at least 1".to_string());
+        }
+        if self.r as u64 * self.p as u64 >= 1 << 30 {
+            return Err("scrypt r * p must be below 2^30".to_string());
+        }
+        if self.length == 0 {
+            return Err("derived key length must be at least one byte".to_string());
+        }
+        return Ok(());
+    }
+}
+
+fn xor_words(x: &mut [u32], y: &[u32]) -> () {
+    for (a, b) in x.iter_mut().zip(y) {
+        *a ^= b;
+    }
+}
+
+
+
+
+// BlockMix with Salsa20/8: the 2r 64 byte blocks are mixed in a chain, even outputs first then odd ones
+fn block_mix(block: &[u32], r: usize) -> Vec<u32> {
+    let mut x: [u32; 16] = block[(2 * r - 1) * 16..].try_into().unwrap();
+    let mut output: Vec<u32> = vec![0; block.len()];
+
+    for i in 0..2 * r {
+        xor_words(&mut x, &block[i * 16..(i + 1) * 16]);
+        x = salsa20_core(&x, 8);
+
+        let position: usize = (i / 2 + (i % 2) * r) * 16;
+        output[position..position + 16].copy_from_slice(&x);
+    }
+    return output;
+}
+
+
+
+
+fn ro_mix(block: &mut [u32], r: usize, n: usize) -> () {
+    let mut x: Vec<u32> = block.to_vec();
+    let mut v: Vec<u32> = Vec::with_capacity(n * x.len());
+
+    for _ in 0..n {
+        v.extend_from_slice(&x);
+        x = block_mix(&x, r);
+    }
+    for _ in 0..n {
+        // Integerify: the first word of the last 64 byte block, N being a power of two below 2^32
+        let j: usize = x[(2 * r - 1) * 16] as usize & (n - 1);
+        xor_words(&mut x, &v[j * block.len()..(j + 1) * block.len()]);
+        x = block_mix(&x, r);
+    }
+    block.copy_from_slice(&x);
+}
+
+
+
+
+// RFC 7914
+pub fn scrypt(password: &[u8], salt: &[u8], parameters: &ScryptParameters) -> Result<Vec<u8>, String> {
+    parameters.check()?;
+
+    let r: usize = parameters.r as usize;
+    let block_length: usize = 128 * r;
+    let b: Vec<u8> = pbkdf2(&Pbkdf2Prf::HmacSha256, password, salt, 1, parameters.p as usize * block_length)?;
+    let mut mixed: Vec<u8> = Vec::with_capacity(b.len());
+
+    for chunk in b.chunks(block_length) {
+        let mut block: Vec<u32> = chunk.chunks(4).map(|word: &[u8]| u32::from_le_bytes(word.try_into().unwrap())).collect();
+        ro_mix(&mut block, r, 1 << parameters.log_n);
+        mixed.extend(block.iter().flat_map(|word: &u32| word.to_le_bytes()));
+    }
+
+    return pbkdf2(&Pbkdf2Prf::HmacSha256, password, &mixed, 1, parameters.length);
+}
+
+
+
+
+// $scrypt$ln=<log2 N>,r=<r>,p=<p>$<salt>$<hash>
+pub fn encode_phc(parameters: &ScryptParameters, salt: &[u8], hash: &[u8]) -> String {
+    return PhcString {
+        algorithm: "scrypt".to_string(),
+        version: None,
+        parameters: vec![("ln".to_string(), parameters.log_n.to_string()), ("r".to_string(), parameters.r.to_string()), ("p".to_string(), parameters.p.to_string())],
+        salt: salt.to_vec(),
+        hash: hash.to_vec()
+    }.encode();
+}
+
+
+
+
+pub fn verify_phc(password: &[u8], phc: &str) -> Result<bool, String> {
+    let phc: PhcString = PhcString::parse(phc)?;
+    if phc.algorithm != "scrypt" {
+        return Err(format!("expected a $scrypt$ PHC string, got ${}$", phc.algorithm));
+    }
+
+    let parameters: ScryptParameters = ScryptParameters {
+        log_n: phc.parameter("ln")?,
+        r: phc.parameter("r")?,
+        p: phc.parameter("p")?,
+        length: phc.hash.len()
+    };
+    // RoMix keeps N blocks of 128 * r bytes
+    if parameters.log_n > MAX_LOG_N || 128 * parameters.r as u64 * (1 << parameters.log_n) > MAX_MEMORY {
+        return Err(format!("scrypt parameters needing more than {} MiB or log2(N) above {} are refused", MAX_MEMORY >> 20, MAX_LOG_N));
+    }
+    return Ok(constant_time_eq(&scrypt(password, &phc.salt, &parameters)?, &phc.hash));
+}
+
+
+
+
+#[derive(Debug, Args, Clone)]
+pub struct ScryptAlg {
+    /// Derive a key and PHC string from the password, or verify the password against a PHC string
+    #[arg(short, long, value_enum, default_value = "hash")]
+    pub operation: ScryptOperations,
+    /// Password to derive the key from
+    #[arg(short, long)]
+    pub password: String,
+    /// Salt (a random 16 byte salt is generated when omitted)
+    #[arg(short, long)]
+    pub salt: Option<String>,
+    /// Encoding of the given salt
+    #[arg(long, value_enum, default_value = "text")]
+    pub salt_format: KeyFormat,
+    /// Cost parameter as log2(N)
+    #[arg(short = 'n', long, default_value_t = DEFAULT_LOG_N)]
+    pub log_n: u32,
+    /// Block size parameter r
+    #[arg(short = 'r', long, default_value_t = 8)]
+    pub block_size: u32,
+    /// Parallelization parameter p
+    #[arg(long, default_value_t = 1)]
+    pub parallelism: u32,
+    /// Derived key length in bytes
+    #[arg(short, long, default_value_t = 32)]
+    pub length: usize,
+    /// $scrypt$ PHC string to verify the password against
+    #[arg(long, required_if_eq("operation", "verify"))]
+    pub hash: Option<String>
+}
+
+impl ScryptAlg {
+    pub fn execute(self) -> () {
+        match self.operation {
+            ScryptOperations::Hash => match self.derive() {
+                Ok((salt, key)) => {
+                    println!("salt: {}", bytes_to_hex(&salt));
+                    println!("key: {}", bytes_to_hex(&key));
+                    println!("phc: {}", encode_phc(&self.parameters(), &salt, &key));
+                }
+                Err(error) => println!("{}", error)
+            },
+            ScryptOperations::Verify => match verify_phc(self.password.as_bytes(), self.hash.as_deref().unwrap_or_default()) {
+                Ok(true) => println!("OK"),
+                Ok(false) => {
+                    println!("FAILED");
+                    std::process::exit(1);
+                }
+                Err(error) => {
+                    println!("{}", error);
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+
+    fn parameters(&self) -> ScryptParameters {
+        return ScryptParameters { log_n: self.log_n, r: self.block_size, p: self.parallelism, length: self.length };
+    }
+
+    fn derive(&self) -> Result<(Vec<u8>, Vec<u8>), String> {
+        let salt: Vec<u8> = match &self.salt {
+            Some(salt) => decode_key(salt, &self.salt_format)?,
+            None => random_salt(PASSWORD_SALT_LENGTH)
+        };
+        let key: Vec<u8> = scrypt(self.password.as_bytes(), &salt, &self.parameters())?;
+
+        return Ok((salt, key));
+    }
+}
+
+#[cfg(test)]
+mod scrypt_test {
+    use super::*;
+
+    fn scrypt_hex(password: &[u8], salt: &[u8], log_n: u32, r: u32, p: u32, length: usize) -> String {
+        return bytes_to_hex(&scrypt(password, salt, &ScryptParameters { log_n: log_n, r: r, p: p, length: length }).unwrap());
+    }
+
+    #[test]
+    fn rfc_7914_test() -> () {
+        assert_eq!(scrypt_hex(b"", b"", 4, 1, 1, 64), "77d6576238657b203b19ca42c18a0497f16b4844e3074ae8dfdffa3fede21442fcd0069ded0948f8326a753a0fc81f17e8d3e0fb2e0d3628cf35e20c38d18906");
+        assert_eq!(scrypt_hex(b"password", b"NaCl", 10, 8, 16, 64), "fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b3731622eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640");
+    }
+
+    #[test]
+    fn parameters_test() -> () {
+        assert!(scrypt(b"password", b"salt", &ScryptParameters { log_n: 0, r: 8, p: 1, length: 32 }).is_err());
+        assert!(scrypt(b"password", b"salt", &ScryptParameters { log_n: 4, r: 0, p: 1, length: 32 }).is_err());
+        assert!(scrypt(b"password", b"salt", &ScryptParameters { log_n: 4, r: 8, p: 1, length: 0 }).is_err());
+    }
+
+    #[test]
+    fn phc_test() -> () {
+        let parameters: ScryptParameters = ScryptParameters { log_n: 4, r: 8, p: 2, length: 32 };
+        let phc: String = encode_phc(&parameters, b"somesalt", &scrypt(b"password", b"somesalt", &parameters).unwrap());
+
+        assert!(phc.starts_with("$scrypt$ln=4,r=8,p=2$c29tZXNhbHQ$"));
+        assert!(verify_phc(b"password", &phc).unwrap());
+        assert!(!verify_phc(b"passw0rd", &phc).unwrap());
+        assert!(verify_phc(b"password", &phc.replace("scrypt", "argon2id")).is_err());
+        assert!(verify_phc(b"password", &phc.replace("ln=4", "ln=30")).is_err());
+        assert!(verify_phc(b"password", &phc.replace("r=8", "r=1048576")).is_err());
+    }
+
+    #[test]
+    fn execute_derive_test() -> () {
+        let instance_1: ScryptAlg = ScryptAlg {
+            operation: ScryptOperations::Hash,
+            password: "".to_string(),
+            salt: Some("".to_string()),
+            salt_format: KeyFormat::Text,
+            log_n: 4,
+            block_size: 1,
+            parallelism: 1,
+            length: 16,
+            hash: None
+        };
+        assert_eq!(bytes_to_hex(&instance_1.derive().unwrap().1), "77d6576238657b203b19ca42c18a0497");
+
+        let instance_2: ScryptAlg = ScryptAlg {
+            salt: None,
+            ..instance_1.clone()
+        };
+        assert_eq!(instance_2.derive().unwrap().0.len(), PASSWORD_SALT_LENGTH);
+    }
+}
@@ -0,0 +1,554 @@
+use std::fs;
+use clap::{Args, ValueEnum};
+use crate::{algorithms::{block_cipher::BlockCipher, data_encryption_standard::DesCipher}, hashes::hash_function::{digest, HashAlgorithms}, key_derivation::{bcrypt, pbkdf2::random_salt}, ops::{bytes_into_bits, constant_time_eq}, tables::{DES_EXPANSION_TABLE, MD5_CRYPT_BYTE_ORDER, SHA256_CRYPT_BYTE_ORDER, SHA512_CRYPT_BYTE_ORDER}};
+
+const CRYPT_ALPHABET: &[u8; 64] = b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+const MD5_CRYPT_MAX_SALT: usize = 8;
+const SHA_CRYPT_MAX_SALT: usize = 16;
+const SHA_CRYPT_DEFAULT_ROUNDS: u32 = 5000;
+const SHA_CRYPT_MIN_ROUNDS: u32 = 1000;
+const SHA_CRYPT_MAX_ROUNDS: u32 = 999999999;
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum UnixCryptOperations {
+    Hash,
+    Verify
+}
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum CryptFormat {
+    Des,
+    Md5,
+    Sha256,
+    Sha512
+}
+impl CryptFormat {
+    fn salt_length(&self) -> usize {
+        match self {
+            CryptFormat::Des => 2,
+            CryptFormat::Md5 => MD5_CRYPT_MAX_SALT,
+            CryptFormat::Sha256 | CryptFormat::Sha512 => SHA_CRYPT_MAX_SALT
+        }
+    }
+}
+
+fn crypt_alphabet_index(character: u8) -> Result<u32, String> {
+    return match CRYPT_ALPHABET.iter().position(|c: &u8| *c == character) {
+        Some(index) => Ok(index as u32),
+        None => Err(format!("invalid crypt salt character {:?}", character as char))
+    };
+}
+
+
+
+
+pub fn random_crypt_salt(length: usize) -> String {
+    return random_salt(length).iter().map(|byte: &u8| CRYPT_ALPHABET[(byte % 64) as usize] as char).collect();
+}
+
+
+
+
+// Each group of up to three bytes is read big endian and written as 6 bit characters, least significant first
+fn crypt_base64(bytes: &[u8], order: &[usize]) -> String {
+    let mut encoded: String = String::new();
+
+    for group in order.chunks(3) {
+        let mut value: u32 = group.iter().fold(0, |value: u32, index: &usize| (value << 8) | bytes[*index] as u32);
+        for _ in 0..group.len() + 1 {
+            encoded.push(CRYPT_ALPHABET[(value & 0x3f) as usize] as char);
+            value >>= 6;
+        }
+    }
+    return encoded;
+}
+
+
+
+
+// Traditional crypt(3): 25 DES encryptions of a zero block keyed by the first 8 characters,
+// with each set bit of the 12 bit salt swapping two entries of the expansion table
+pub fn des_crypt(password: &[u8], salt: &str) -> Result<String, String> {
+    let salt: &str = salt.get(..2).ok_or("DES crypt salt must be 2 characters".to_string())?;
+    let salt_value: u32 = salt.bytes().rev().try_fold(0, |value: u32, character: u8| -> Result<u32, String> { Ok((value << 6) | crypt_alphabet_index(character)?) })?;
+
+    let mut expansion: Vec<u8> = DES_EXPANSION_TABLE.concat();
+    for bit in 0..12 {
+        if (salt_value >> bit) & 1 == 1 {
+            expansion.swap(bit, bit + 24);
+        }
+    }
+
+    let mut key: [u8; 8] = [0; 8];
+    for (key_byte, password_byte) in key.iter_mut().zip(password) {
+        *key_byte = password_byte << 1;
+    }
+
+    let cipher: DesCipher = DesCipher::with_expansion(&key, expansion)?;
+    let mut block: [u8; 8] = [0; 8];
+    for _ in 0..25 {
+        cipher.encrypt_block(&mut block);
+    }
+
+    let mut bits: Vec<u8> = bytes_into_bits(&block);
+    bits.extend([0, 0]);
+    let encoded: String = bits.chunks(6).map(|chunk: &[u8]| CRYPT_ALPHABET[chunk.iter().fold(0, |value: usize, bit: &u8| (value << 1) | *bit as usize)] as char).collect();
+
+    return Ok(format!("{}{}", salt, encoded));
+}
+
+
+
+
+// Repeats the digest up to length bytes
+fn stretch(digest: &[u8], length: usize) -> Vec<u8> {
+    return digest.iter().cycle().take(length).cloned().collect();
+}
+
+
+
+
+// $1$ scheme from FreeBSD
+pub fn md5_crypt(password: &[u8], salt: &str) -> Result<String, String> {
+    let salt: &[u8] = &salt.as_bytes()[..salt.len().min(MD5_CRYPT_MAX_SALT)];
+    let md5 = |data: &[u8]| -> Vec<u8> { digest(&HashAlgorithms::Md5, data) };
+
+    let alternate: Vec<u8> = md5(&[password, salt, password].concat());
+    let mut context: Vec<u8> = [password, b"$1$", salt].concat();
+
+    context.extend(stretch(&alternate, password.len()));
+    let mut length: usize = password.len();
+    while length > 0 {
+        context.push(if length & 1 == 1 { 0 } else { *password.first().unwrap_or(&0) });
+        length >>= 1;
+    }
+
+    let mut result: Vec<u8> = md5(&context);
+    for round in 0..1000 {
+        let mut round_input: Vec<u8> = if round & 1 == 1 { password.to_vec() } else { result.clone() };
+        if round % 3 != 0 {
+            round_input.extend_from_slice(salt);
+        }
+        if round % 7 != 0 {
+            round_input.extend_from_slice(password);
+        }
+        round_input.extend_from_slice(if round & 1 == 1 { &result } else { password });
+        result = md5(&round_input);
+    }
+
+    return Ok(format!("$1${}${}", String::from_utf8_lossy(salt), crypt_base64(&result, &MD5_CRYPT_BYTE_ORDER)));
+}
+
+
+
+
+// $5$ and $6$ schemes from Ulrich Drepper's specification; rounds outside 1000..=999999999 are clamped like glibc does
+pub fn sha_crypt(format: &CryptFormat, password: &[u8], salt: &str, rounds: Option<u32>) -> Result<String, String> {
+    let (algorithm, prefix, order): (HashAlgorithms, &str, &[usize]) = match format {
+        CryptFormat::Sha256 => (HashAlgorithms::Sha256, "$5$", &SHA256_CRYPT_BYTE_ORDER),
+        CryptFormat::Sha512 => (HashAlgorithms::Sha512, "$6$", &SHA512_CRYPT_BYTE_ORDER),
+        _ => return Err("SHA crypt is only defined for SHA-256 and SHA-512".to_string())
+    };
+    let hash = |data: &[u8]| -> Vec<u8> { digest(&algorithm, data) };
+    let salt: &[u8] = &salt.as_bytes()[..salt.len().min(SHA_CRYPT_MAX_SALT)];
+    let round_count: u32 = rounds.unwrap_or(SHA_CRYPT_DEFAULT_ROUNDS).clamp(SHA_CRYPT_MIN_ROUNDS, SHA_CRYPT_MAX_ROUNDS);
+
+    let alternate: Vec<u8> = hash(&[password, salt, password].concat());
+    let mut context: Vec<u8> = [password, salt, &stretch(&alternate, password.len())].concat();
+    let mut length: usize = password.len();
+    while length > 0 {
+        context.extend_from_slice(if length & 1 == 1 { &alternate } else { password });
+        length >>= 1;
+    }
+    let mut result: Vec<u8> = hash(&context);
+
+    let password_sequence: Vec<u8> = stretch(&hash(&password.repeat(password.len())), password.len());
+    let salt_sequence: Vec<u8> = stretch(&hash(&salt.repeat(16 + result[0] as usize)), salt.len());
+
+    for round in 0..round_count {
+        let mut round_input: Vec<u8> = if round & 1 == 1 { password_sequence.clone() } else { result.clone() };
+        if round % 3 != 0 {
+            round_input.extend_from_slice(&salt_sequence);
+        }
+        if round % 7 != 0 {
+            round_input.extend_from_slice(&password_sequence);
+        }
+        round_input.extend_from_slice(if round & 1 == 1 { &result } else { &password_sequence });
+        result = hash(&round_input);
+    }
+
+    let rounds_field: String = if rounds.is_some() { format!("rounds={}$", round_count) } else { String::new() };
+    return Ok(format!("{}{}{}${}", prefix, rounds_field, String::from_utf8_lossy(salt), crypt_base64(&result, order)));
+}
+
+
+
+
+// Hashes the password with the scheme, salt and rounds of the setting, which may be a complete hash
+pub fn crypt(password: &[u8], setting: &str) -> Result<String, String> {
+    if let Some(rest) = setting.strip_prefix("$1$") {
+        return md5_crypt(password, rest.split('$').next().unwrap_or_default());
+    }
+
+    let format: CryptFormat = match setting.get(..3) {
+        Some("$5$") => CryptFormat::Sha256,
+        Some("$6$") => CryptFormat::Sha512,
+        _ if setting.starts_with('$') => return Err(format!("unsupported crypt scheme {}", setting.split('$').nth(1).map(|id: &str| format!("${}$", id)).unwrap_or_default())),
+        _ => return des_crypt(password, setting)
+    };
+
+    let mut fields: std::str::Split<char> = setting[3..].split('$');
+    let mut salt: &str = fields.next().unwrap_or_default();
+    let mut rounds: Option<u32> = None;
+    if let Some(count) = salt.strip_prefix("rounds=") {
+        rounds = Some(count.parse::<u32>().map_err(|_| format!("invalid rounds count {}", count))?);
+        salt = fields.next().unwrap_or_default();
+    }
+    return sha_crypt(&format, password, salt, rounds);
+}
+
+
+
+
+pub fn verify_crypt(password: &[u8], hash: &str) -> Result<bool, String> {
+    if hash.starts_with("$2") {
+        return bcrypt::verify_password(&String::from_utf8_lossy(password), hash);
+    }
+    if !hash.starts_with('$') && hash.len() != 13 {
+        return Err("DES crypt hash must be 13 characters".to_string());
+    }
+    return Ok(constant_time_eq(crypt(password, hash)?.as_bytes(), hash.as_bytes()));
+}
+
+
+
+
+// Checks the layout of a crypt(3) or bcrypt hash without hashing anything, a malformed hash would otherwise never match
+pub fn check_crypt_hash(hash: &str) -> Result<(), String> {
+    if hash.starts_with("$2") {
+        return bcrypt::BcryptHash::parse(hash).map(|_| ());
+    }
+
+    let (salt, encoded, max_salt, length): (&str, &str, usize, usize) = match hash.get(..3) {
+        Some("$1$") => {
+            let (salt, encoded): (&str, &str) = hash[3..].split_once('$').ok_or("MD5 crypt hash must look like $1$<salt>$<hash>")?;
+            (salt, encoded, MD5_CRYPT_MAX_SALT, 22)
+        }
+        Some(prefix @ ("$5$" | "$6$")) => {
+            let mut rest: &str = &hash[3..];
+            if let Some((count, after)) = rest.strip_prefix("rounds=").and_then(|rounds: &str| rounds.split_once('$')) {
+                let rounds: u32 = count.parse::<u32>().map_err(|_| format!("invalid rounds count {}", count))?;
+                if !(SHA_CRYPT_MIN_ROUNDS..=SHA_CRYPT_MAX_ROUNDS).contains(&rounds) {
+                    return Err(format!("SHA crypt rounds must be between {} and {}", SHA_CRYPT_MIN_ROUNDS, SHA_CRYPT_MAX_ROUNDS));
+                }
+                rest = after;
+            }
+            let (salt, encoded): (&str, &str) = rest.split_once('$').ok_or("SHA crypt hash must look like $5$[rounds=<n>$]<salt>$<hash>")?;
+            (salt, encoded, SHA_CRYPT_MAX_SALT, if prefix == "$5$" { 43 } else { 86 })
+        }
+        _ if hash.starts_with('$') => return Err(format!("unsupported crypt scheme {}", hash.split('$').nth(1).map(|id: &str| format!("${}$", id)).unwrap_or_default())),
+        _ if hash.len() != 13 || !hash.is_ascii() => return Err("DES crypt hash must be 13 characters".to_string()),
+        _ => {
+            hash.bytes().take(2).try_for_each(|character: u8| crypt_alphabet_index(character).map(|_| ()))?;
+            (&hash[..2], &hash[2..], 2, 11)
+        }
+    };
+
+    if salt.len() > max_salt {
+        return Err(format!("crypt salt is limited to {} characters", max_salt));
+    }
+    if encoded.len() != length || !encoded.bytes().all(|character: u8| CRYPT_ALPHABET.contains(&character)) {
+        return Err(format!("crypt hash must end with {} characters of [./0-9A-Za-z]", length));
+    }
+    return Ok(());
+}
+
+
+
+
+// user:hash:lastchange:... lines; hashes starting with ! or * belong to locked accounts
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShadowEntry {
+    pub user: String,
+    pub hash: String
+}
+
+pub fn parse_shadow_line(line: &str) -> Option<ShadowEntry> {
+    let line: &str = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let mut fields: std::str::Split<char> = line.split(':');
+    return Some(ShadowEntry {
+        user: fields.next()?.to_string(),
+        hash: fields.next()?.to_string()
+    });
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShadowStatus {
+    Ok,
+    Failed,
+    Locked,
+    Error(String)
+}
+
+pub fn check_shadow_entry(password: &[u8], entry: &ShadowEntry) -> ShadowStatus {
+    if entry.hash.is_empty() || entry.hash.starts_with('!') || entry.hash.starts_with('*') {
+        return ShadowStatus::Locked;
+    }
+    return match verify_crypt(password, &entry.hash) {
+        Ok(true) => ShadowStatus::Ok,
+        Ok(false) => ShadowStatus::Failed,
+        Err(error) => ShadowStatus::Error(error)
+    };
+}
+
+
+
+
+#[derive(Debug, Args, Clone)]
+pub struct UnixCryptAlg {
+    /// Hash a password, or verify it against a crypt hash or an /etc/shadow style file
+    #[arg(short, long, value_enum, default_value = "hash")]
+    pub operation: UnixCryptOperations,
+    /// Hash scheme: traditional DES, $1$ MD5, $5$ SHA-256 or $6$ SHA-512
+    #[arg(short, long, value_enum, default_value = "sha512")]
+    pub format: CryptFormat,
+    /// Password
+    #[arg(short, long)]
+    pub password: String,
+    /// Salt in the ./0-9A-Za-z alphabet (a random salt is generated when omitted)
+    #[arg(short, long)]
+    pub salt: Option<String>,
+    /// Rounds for SHA crypt (5000 when omitted)
+    #[arg(short, long)]
+    pub rounds: Option<u32>,
+    /// Crypt hash to verify the password against
+    #[arg(long, conflicts_with = "shadow")]
+    pub hash: Option<String>,
+    /// File in /etc/shadow format to verify the password against
+    #[arg(long)]
+    pub shadow: Option<String>,
+    /// Only check this user of the shadow file
+    #[arg(short, long, requires = "shadow")]
+    pub user: Option<String>
+}
+
+impl UnixCryptAlg {
+    pub fn execute(self) -> () {
+        match self.operation {
+            UnixCryptOperations::Hash => match self.hash_password() {
+                Ok(hash) => println!("{}", hash),
+                Err(error) => println!("{}", error)
+            },
+            UnixCryptOperations::Verify => match (&self.hash, &self.shadow) {
+                (Some(hash), _) => match verify_crypt(self.password.as_bytes(), hash) {
+                    Ok(true) => println!("OK"),
+                    Ok(false) => {
+                        println!("FAILED");
+                        std::process::exit(1);
+                    }
+                    Err(error) => {
+                        println!("{}", error);
+                        std::process::exit(1);
+                    }
+                },
+                (None, Some(path)) => self.verify_shadow(path),
+                (None, None) => {
+                    println!("verify needs --hash or --shadow");
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+
+    fn hash_password(&self) -> Result<String, String> {
+        let salt: String = match &self.salt {
+            Some(salt) => salt.clone(),
+            None => random_crypt_salt(self.format.salt_length())
+        };
+        if salt.contains('$') || salt.contains(':') {
+            return Err("crypt salt can not contain $ or :".to_string());
+        }
+
+        return match self.format {
+            CryptFormat::Des => des_crypt(self.password.as_bytes(), &salt),
+            CryptFormat::Md5 => md5_crypt(self.password.as_bytes(), &salt),
+            CryptFormat::Sha256 | CryptFormat::Sha512 => sha_crypt(&self.format, self.password.as_bytes(), &salt, self.rounds)
+        };
+    }
+
+    // Exits with an error unless the password matches at least one of the checked accounts
+    fn verify_shadow(&self, path: &str) -> () {
+        let statuses: Vec<(String, ShadowStatus)> = match self.check_shadow(path) {
+            Ok(statuses) => statuses,
+            Err(error) => {
+                println!("{}", error);
+                std::process::exit(1);
+            }
+        };
+
+        for (user, status) in &statuses {
+            match status {
+                ShadowStatus::Ok => println!("{}: OK", user),
+                ShadowStatus::Failed => println!("{}: FAILED", user),
+                ShadowStatus::Locked => println!("{}: locked or no password", user),
+                ShadowStatus::Error(error) => println!("{}: {}", user, error)
+            }
+        }
+        if !statuses.iter().any(|(_, status): &(String, ShadowStatus)| *status == ShadowStatus::Ok) {
+            std::process::exit(1);
+        }
+    }
+
+    fn check_shadow(&self, path: &str) -> Result<Vec<(String, ShadowStatus)>, String> {
+        let contents: String = fs::read_to_string(path).map_err(|e| format!("could not read shadow file {}: {}", path, e))?;
+        let statuses: Vec<(String, ShadowStatus)> = contents.lines()
+            .filter_map(parse_shadow_line)
+            .filter(|entry: &ShadowEntry| self.user.as_ref().is_none_or(|user: &String| *user == entry.user))
+            .map(|entry: ShadowEntry| (entry.user.clone(), check_shadow_entry(self.password.as_bytes(), &entry)))
+            .collect();
+
+        if statuses.is_empty() {
+            return Err(match &self.user {
+                Some(user) => format!("no entry for {} in {}", user, path),
+                None => format!("no entries in {}", path)
+            });
+        }
+        return Ok(statuses);
+    }
+}
+
+#[cfg(test)]
+mod unix_crypt_test {
+    use std::env;
+    use super::*;
+
+    #[test]
+    fn des_crypt_test() -> () {
+        assert_eq!(des_crypt(b"foob", "ar").unwrap(), "arlEKn0OzVJn.");
+        assert_eq!(des_crypt(b"", "..").unwrap(), "..X8NBuQ4l6uQ");
+        assert_eq!(des_crypt(b"passwordlongerthan8", "ab").unwrap(), des_crypt(b"password", "ab").unwrap());
+        assert_eq!(des_crypt(b"password", "ab").unwrap(), "abJnggxhB/yWI");
+        assert!(des_crypt(b"password", "a").is_err());
+        assert!(des_crypt(b"password", "a!").is_err());
+    }
+
+    #[test]
+    fn md5_crypt_test() -> () {
+        assert_eq!(md5_crypt(b"password", "saltsalt").unwrap(), "$1$saltsalt$qjXMvbEw8oaL.CzflDtaK/");
+        assert_eq!(md5_crypt(b"test", "saltsalt").unwrap(), "$1$saltsalt$tTWg0JeO/sYmHvtKmZE8c.");
+        assert_eq!(md5_crypt(b"", "").unwrap(), "$1$$qRPK7m23GJusamGpoGLby/");
+        assert_eq!(crypt(b"a much longer password that spans", "$1$12345678901$").unwrap(), "$1$12345678$Ih92EwXnScCe8nX7Z4zOF0");
+    }
+
+    #[test]
+    fn sha_crypt_test() -> () {
+        assert_eq!(crypt(b"Hello world!", "$5$saltstring").unwrap(), "$5$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc5");
+        assert_eq!(crypt(b"Hello world!", "$5$rounds=10000$saltstringsaltstring").unwrap(), "$5$rounds=10000$saltstringsaltst$3xv.VbSHBb41AL9AvLeujZkZRBAwqFMz2.opqey6IcA");
+        assert_eq!(crypt(b"Hello world!", "$6$saltstring").unwrap(), "$6$saltstring$svn8UoSVapNtMuq1ukKS4tPQd8iKwSMHWjl/O817G3uBnIFNjnQJuesI68u4OTLiBFdcbYEdFCoEOfaS35inz1");
+        assert_eq!(crypt(b"Hello world!", "$6$rounds=10000$saltstringsaltstring").unwrap(), "$6$rounds=10000$saltstringsaltst$OW1/O6BYHV6BcXZu8QVeXbDWra3Oeqh0sbHbbMCVNSnCM/UrjmM0Dp8vOuZeHBy/YTBmSK6H9qs/y3RnOaw5v.");
+        assert_eq!(crypt(b"pw", "$5$rounds=5000$abc").unwrap(), "$5$rounds=5000$abc$/LKlXd6vuvdFX3oWbf2vGk3DgflzzsvOtMZsiOxqqY5");
+        assert!(crypt(b"short", "$6$rounds=10$roundstoolow").unwrap().starts_with("$6$rounds=1000$roundstoolow$"));
+        assert!(crypt(b"pw", "$y$j9T$salt").is_err());
+    }
+
+    #[test]
+    fn verify_crypt_test() -> () {
+        assert!(verify_crypt(b"foob", "arlEKn0OzVJn.").unwrap());
+        assert!(!verify_crypt(b"fooc", "arlEKn0OzVJn.").unwrap());
+        assert!(verify_crypt(b"test", "$1$saltsalt$tTWg0JeO/sYmHvtKmZE8c.").unwrap());
+        assert!(verify_crypt(b"Hello world!", "$5$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc5").unwrap());
+        assert!(!verify_crypt(b"Hello world", "$5$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc5").unwrap());
+        assert!(verify_crypt(b"U*U", "$2a$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW").unwrap());
+        assert!(verify_crypt(b"foob", "ar").is_err());
+    }
+
+    #[test]
+    fn check_crypt_hash_test() -> () {
+        assert!(check_crypt_hash("arlEKn0OzVJn.").is_ok());
+        assert!(check_crypt_hash("$1$saltsalt$tTWg0JeO/sYmHvtKmZE8c.").is_ok());
+        assert!(check_crypt_hash("$5$rounds=10000$saltstringsaltst$3xv.VbSHBb41AL9AvLeujZkZRBAwqFMz2.opqey6IcA").is_ok());
+        assert!(check_crypt_hash("$2a$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW").is_ok());
+
+        assert!(check_crypt_hash("arlEKn0OzVJn").is_err());
+        assert!(check_crypt_hash("a!lEKn0OzVJn.").is_err());
+        assert!(check_crypt_hash("$1$saltsalt$tTWg0JeO/sYmHvtKmZE8c").is_err());
+        assert!(check_crypt_hash("$1$saltsalt").is_err());
+        assert!(check_crypt_hash("$5$rounds=10$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc5").is_err());
+        assert!(check_crypt_hash("$6$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc5").is_err());
+        assert!(check_crypt_hash("$2a$99$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW").is_err());
+        assert!(check_crypt_hash("$2a$05$CCCC").is_err());
+    }
+
+    #[test]
+    fn shadow_test() -> () {
+        assert_eq!(parse_shadow_line("alice:$1$saltsalt$tTWg0JeO/sYmHvtKmZE8c.:19000:0:99999:7:::"), Some(ShadowEntry { user: "alice".to_string(), hash: "$1$saltsalt$tTWg0JeO/sYmHvtKmZE8c.".to_string() }));
+        assert_eq!(parse_shadow_line("   "), None);
+        assert_eq!(parse_shadow_line("daemon"), None);
+        assert_eq!(check_shadow_entry(b"test", &ShadowEntry { user: "bin".to_string(), hash: "*".to_string() }), ShadowStatus::Locked);
+        assert_eq!(check_shadow_entry(b"test", &ShadowEntry { user: "bob".to_string(), hash: "!$1$saltsalt$tTWg0JeO/sYmHvtKmZE8c.".to_string() }), ShadowStatus::Locked);
+        assert_eq!(check_shadow_entry(b"foob", &ShadowEntry { user: "carol".to_string(), hash: "arlEKn0OzVJn.".to_string() }), ShadowStatus::Ok);
+    }
+
+    #[test]
+    fn execute_verify_shadow_test() -> () {
+        let path: String = env::temp_dir().join(format!("unix_crypt_test_{}.shadow", std::process::id())).to_string_lossy().to_string();
+        fs::write(&path, "root:*:19000:0:99999:7:::\nalice:$1$saltsalt$tTWg0JeO/sYmHvtKmZE8c.:19000:0:99999:7:::\ncarol:arlEKn0OzVJn.:19000::::::\n").unwrap();
+
+        let instance_1: UnixCryptAlg = UnixCryptAlg {
+            operation: UnixCryptOperations::Verify,
+            format: CryptFormat::Sha512,
+            password: "test".to_string(),
+            salt: None,
+            rounds: None,
+            hash: None,
+            shadow: Some(path.clone()),
+            user: None
+        };
+        assert_eq!(instance_1.check_shadow(&path).unwrap(), vec![("root".to_string(), ShadowStatus::Locked), ("alice".to_string(), ShadowStatus::Ok), ("carol".to_string(), ShadowStatus::Failed)]);
+
+        let instance_2: UnixCryptAlg = UnixCryptAlg {
+            user: Some("dave".to_string()),
+            ..instance_1.clone()
+        };
+        assert!(instance_2.check_shadow(&path).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn execute_hash_test() -> () {
+        let instance_1: UnixCryptAlg = UnixCryptAlg {
+            operation: UnixCryptOperations::Hash,
+            format: CryptFormat::Sha256,
+            password: "pw".to_string(),
+            salt: Some("abc".to_string()),
+            rounds: Some(5000),
+            hash: None,
+            shadow: None,
+            user: None
+        };
+        assert_eq!(instance_1.hash_password().unwrap(), "$5$rounds=5000$abc$/LKlXd6vuvdFX3oWbf2vGk3DgflzzsvOtMZsiOxqqY5");
+
+        let instance_2: UnixCryptAlg = UnixCryptAlg {
+            format: CryptFormat::Md5,
+            salt: None,
+            ..instance_1.clone()
+        };
+        let hash: String = instance_2.hash_password().unwrap();
+        assert_eq!(hash.len(), 3 + 8 + 1 + 22);
+        assert!(verify_crypt(b"pw", &hash).unwrap());
+
+        let instance_3: UnixCryptAlg = UnixCryptAlg {
+            salt: Some("a$b".to_string()),
+            ..instance_1.clone()
+        };
+        assert!(instance_3.hash_password().is_err());
+    }
+}
@@ -0,0 +1,88 @@
+use crate::algorithms::block_cipher::BlockCipher;
+
+// Constants of the irreducible polynomials used by SP 800-38B to double a subkey
+const CMAC_RB_128: u8 = 0x87;
+const CMAC_RB_64: u8 = 0x1b;
+
+// Multiplication by x in GF(2^b): a one bit left shift, reduced when the top bit falls off
+fn double(block: &[u8], rb: u8) -> Vec<u8> {
+    let mut doubled: Vec<u8> = block.iter().zip(block.iter().skip(1).chain([0].iter())).map(|(byte, next): (&u8, &u8)| (byte << 1) | (next >> 7)).collect();
+
+    if block[0] & 0x80 == 0x80 {
+        *doubled.last_mut().unwrap() ^= rb;
+    }
+    return doubled;
+}
+
+
+
+
+fn xor_into(block: &mut [u8], data: &[u8]) -> () {
+    for (byte, data_byte) in block.iter_mut().zip(data) {
+        *byte ^= data_byte;
+    }
+}
+
+
+
+
+// CMAC (OMAC1): CBC-MAC whose last block is masked with K1 when complete, or padded and masked with K2
+pub fn cmac(cipher: &dyn BlockCipher, message: &[u8]) -> Vec<u8> {
+    let block_size: usize = cipher.block_size();
+    let rb: u8 = if block_size == 16 { CMAC_RB_128 } else { CMAC_RB_64 };
+
+    let mut l: Vec<u8> = vec![0; block_size];
+    cipher.encrypt_block(&mut l);
+    let k1: Vec<u8> = double(&l, rb);
+    let k2: Vec<u8> = double(&k1, rb);
+
+    let last_start: usize = if message.is_empty() { 0 } else { (message.len() - 1) / block_size * block_size };
+    let mut last: Vec<u8> = message[last_start..].to_vec();
+
+    if last.len() == block_size {
+        xor_into(&mut last, &k1);
+    } else {
+        last.push(0x80);
+        last.resize(block_size, 0);
+        xor_into(&mut last, &k2);
+    }
+
+    let mut state: Vec<u8> = vec![0; block_size];
+    for block in message[..last_start].chunks(block_size).chain([last.as_slice()]) {
+        xor_into(&mut state, block);
+        cipher.encrypt_block(&mut state);
+    }
+    return state;
+}
+
+#[cfg(test)]
+mod cmac_test {
+    use crate::{algorithms::{advanced_encryption_standard::Aes, data_encryption_standard::TripleDes}, ops::{bytes_to_hex, hex_to_bytes}};
+    use super::*;
+
+    const SP_800_38B_MESSAGE: &str = "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710";
+
+    fn cmac_hex(cipher: &dyn BlockCipher, length: usize) -> String {
+        return bytes_to_hex(&cmac(cipher, &hex_to_bytes(SP_800_38B_MESSAGE).unwrap()[..length]));
+    }
+
+    #[test]
+    fn cmac_aes_test() -> () {
+        let cipher: Aes = Aes::new(&hex_to_bytes("2b7e151628aed2a6abf7158809cf4f3c").unwrap()).unwrap();
+
+        assert_eq!(cmac_hex(&cipher, 0), "bb1d6929e95937287fa37d129b756746");
+        assert_eq!(cmac_hex(&cipher, 16), "070a16b46b4d4144f79bdd9dd04a287c");
+        assert_eq!(cmac_hex(&cipher, 40), "dfa66747de9ae63030ca32611497c827");
+        assert_eq!(cmac_hex(&cipher, 64), "51f0bebf7e3b9d92fc49741779363cfe");
+    }
+
+    #[test]
+    fn cmac_triple_des_test() -> () {
+        let cipher: TripleDes = TripleDes::new(&hex_to_bytes("8aa83bf8cbda10620bc1bf19fbb6cd58bc313d4a371ca8b5").unwrap()).unwrap();
+
+        assert_eq!(cmac_hex(&cipher, 0), "b7a688e122ffaf95");
+        assert_eq!(cmac_hex(&cipher, 16), "286d394673448197");
+        assert_eq!(cmac_hex(&cipher, 20), "743ddbe0ce2dc2ed");
+        assert_eq!(cmac_hex(&cipher, 32), "33e6b1092400eae5");
+    }
+}
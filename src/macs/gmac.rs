@@ -0,0 +1,92 @@
+use crate::algorithms::block_cipher::BlockCipher;
+
+// x^128 + x^7 + x^2 + x + 1 in the bit reflected order of GCM
+const GHASH_R: u128 = 0xe1 << 120;
+
+fn gf_multiply(x: u128, y: u128) -> u128 {
+    let mut product: u128 = 0;
+    let mut v: u128 = y;
+
+    for i in 0..128 {
+        if (x >> (127 - i)) & 1 == 1 {
+            product ^= v;
+        }
+        v = if v & 1 == 1 { (v >> 1) ^ GHASH_R } else { v >> 1 };
+    }
+    return product;
+}
+
+
+
+
+fn pad_to_block(data: &[u8]) -> Vec<u8> {
+    let mut padded: Vec<u8> = data.to_vec();
+    padded.resize(data.len().div_ceil(16) * 16, 0);
+
+    return padded;
+}
+
+
+
+
+pub fn ghash(h: u128, data: &[u8]) -> u128 {
+    return pad_to_block(data)
+        .chunks(16)
+        .fold(0, |y: u128, block: &[u8]| gf_multiply(y ^ u128::from_be_bytes(block.try_into().unwrap()), h));
+}
+
+
+
+
+// GCM over an empty plaintext, so the tag only authenticates the additional data
+pub fn gmac(cipher: &dyn BlockCipher, iv: &[u8], message: &[u8]) -> Result<Vec<u8>, String> {
+    if cipher.block_size() != 16 {
+        return Err("GMAC requires a 128 bit block cipher".to_string());
+    }
+    if iv.is_empty() {
+        return Err("GMAC requires a non empty IV".to_string());
+    }
+
+    let mut hash_key: [u8; 16] = [0; 16];
+    cipher.encrypt_block(&mut hash_key);
+    let h: u128 = u128::from_be_bytes(hash_key);
+
+    let mut j0: [u8; 16] = if iv.len() == 12 {
+        [iv, &[0, 0, 0, 1]].concat().try_into().unwrap()
+    } else {
+        ghash(h, &[pad_to_block(iv), (8 * iv.len() as u128).to_be_bytes().to_vec()].concat()).to_be_bytes()
+    };
+
+    let lengths: u128 = (8 * message.len() as u128) << 64;
+    let s: u128 = ghash(h, &[pad_to_block(message), lengths.to_be_bytes().to_vec()].concat());
+
+    cipher.encrypt_block(&mut j0);
+    return Ok((u128::from_be_bytes(j0) ^ s).to_be_bytes().to_vec());
+}
+
+#[cfg(test)]
+mod gmac_test {
+    use crate::{algorithms::advanced_encryption_standard::Aes, ops::{bytes_to_hex, hex_to_bytes}};
+    use super::*;
+
+    fn gmac_hex(key: &str, iv: &str, message: &str) -> String {
+        let cipher: Aes = Aes::new(&hex_to_bytes(key).unwrap()).unwrap();
+
+        return bytes_to_hex(&gmac(&cipher, &hex_to_bytes(iv).unwrap(), &hex_to_bytes(message).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn gcm_spec_test() -> () {
+        let aad: &str = "feedfacedeadbeeffeedfacedeadbeefabaddad2";
+
+        assert_eq!(gmac_hex("00000000000000000000000000000000", "000000000000000000000000", ""), "58e2fccefa7e3061367f1d57a4e7455a");
+        assert_eq!(gmac_hex("feffe9928665731c6d6a8f9467308308", "cafebabefacedbaddecaf888", aad), "346434fd51d5cd0c5887ec63e39b907a");
+        assert_eq!(gmac_hex("feffe9928665731c6d6a8f9467308308", "cafebabefacedbad", aad), "ef6995e531e81a01f5b2f7762cc60bd2");
+        assert_eq!(gmac_hex("feffe9928665731c6d6a8f9467308308", "9313225df88406e555909c5aff5269aa6a7a9538534f7da1e4c303d2a318a728c3c0c95156809539fcf0e2429a6b525416aedbf5a0de6a57a637b39b", aad), "7be5178ff2b73c7d6f8b4dfdde8437ec");
+    }
+
+    #[test]
+    fn empty_iv_test() -> () {
+        assert!(gmac(&Aes::new(&[0; 16]).unwrap(), b"", b"data").is_err());
+    }
+}
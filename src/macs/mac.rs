@@ -0,0 +1,161 @@
+use std::io::Read;
+use clap::{Args, ValueEnum};
+use crate::{algorithms::{advanced_encryption_standard::Aes, data_encryption_standard::TripleDes}, macs::{cmac::cmac, gmac::gmac, poly1305::poly1305}, ops::{bytes_to_hex, constant_time_eq, hex_to_bytes, open_input}};
+
+// SP 800-38B advises against CMAC tags shorter than 64 bits
+const MIN_TRUNCATED_TAG: usize = 8;
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum MacOperations {
+    Mac,
+    Verify
+}
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum MacAlgorithms {
+    CmacAes,
+    #[value(name = "cmac-3des")]
+    Cmac3des,
+    Gmac,
+    Poly1305
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct MacAlg {
+    /// Compute a tag or verify one
+    #[arg(short, long)]
+    pub operation: MacOperations,
+    /// MAC algorithm
+    #[arg(short, long)]
+    pub algorithm: MacAlgorithms,
+    /// Secret key in hex (16/24/32 bytes for AES, 16/24 for 3DES, 32 one-time bytes for Poly1305)
+    #[arg(short, long)]
+    pub key: String,
+    /// IV in hex, required by GMAC (12 bytes recommended)
+    #[arg(short, long)]
+    pub iv: Option<String>,
+    /// The message to authenticate
+    #[arg(short, long, conflicts_with = "file")]
+    pub message: Option<String>,
+    /// File to authenticate ("-" or no message for stdin)
+    #[arg(short, long)]
+    pub file: Option<String>,
+    /// Tag in hex to verify
+    #[arg(short, long, required_if_eq("operation", "verify"))]
+    pub tag: Option<String>
+}
+
+impl MacAlg {
+    pub fn execute(self) -> () {
+        let result: Result<Vec<u8>, String> = self.message().and_then(|message: Vec<u8>| self.compute(&message));
+
+        match (result, &self.operation) {
+            (Ok(tag), MacOperations::Mac) => println!("{}", bytes_to_hex(&tag)),
+            (Ok(tag), MacOperations::Verify) => match self.verify(&tag) {
+                Ok(true) => println!("OK"),
+                Ok(false) => {
+                    println!("FAILED");
+                    std::process::exit(1);
+                }
+                Err(error) => {
+                    println!("{}", error);
+                    std::process::exit(1);
+                }
+            }
+            (Err(error), _) => {
+                println!("{}", error);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    fn message(&self) -> Result<Vec<u8>, String> {
+        if let Some(message) = &self.message {
+            return Ok(message.as_bytes().to_vec());
+        }
+
+        let mut data: Vec<u8> = Vec::new();
+        open_input(self.file.as_deref())?.read_to_end(&mut data).map_err(|e| format!("could not read input: {}", e))?;
+        return Ok(data);
+    }
+
+    fn compute(&self, message: &[u8]) -> Result<Vec<u8>, String> {
+        let key: Vec<u8> = hex_to_bytes(&self.key)?;
+
+        match self.algorithm {
+            MacAlgorithms::CmacAes => Ok(cmac(&Aes::new(&key)?, message)),
+            MacAlgorithms::Cmac3des => Ok(cmac(&TripleDes::new(&key)?, message)),
+            MacAlgorithms::Gmac => gmac(&Aes::new(&key)?, &hex_to_bytes(self.iv.as_ref().ok_or("GMAC requires an IV")?)?, message),
+            MacAlgorithms::Poly1305 => poly1305(&key, message)
+        }
+    }
+
+    // A shorter tag is compared against the leading bytes, as SP 800-38B allows for truncated CMAC tags
+    fn verify(&self, tag: &[u8]) -> Result<bool, String> {
+        let expected: Vec<u8> = hex_to_bytes(self.tag.as_ref().ok_or("a tag is required to verify")?)?;
+
+        if expected.len() > tag.len() || expected.len() < MIN_TRUNCATED_TAG {
+            return Err(format!("tag must be between {} and {} bytes", MIN_TRUNCATED_TAG, tag.len()));
+        }
+        return Ok(constant_time_eq(&tag[..expected.len()], &expected));
+    }
+}
+
+#[cfg(test)]
+mod mac_test {
+    use super::*;
+
+    #[test]
+    fn mac_and_verify_test() -> () {
+        let instance_1: MacAlg = MacAlg {
+            operation: MacOperations::Mac,
+            algorithm: MacAlgorithms::CmacAes,
+            key: "2b7e151628aed2a6abf7158809cf4f3c".to_string(),
+            iv: None,
+            message: Some("".to_string()),
+            file: None,
+            tag: None
+        };
+        assert_eq!(bytes_to_hex(&instance_1.compute(b"").unwrap()), "bb1d6929e95937287fa37d129b756746");
+
+        let instance_2: MacAlg = MacAlg {
+            operation: MacOperations::Verify,
+            tag: Some("bb1d6929e95937287fa37d129b756746".to_string()),
+            ..instance_1.clone()
+        };
+        let tag: Vec<u8> = instance_2.compute(b"").unwrap();
+        assert_eq!(instance_2.verify(&tag), Ok(true));
+
+        let instance_3: MacAlg = MacAlg {
+            tag: Some("bb1d6929e95937287fa37d129b756747".to_string()),
+            ..instance_2.clone()
+        };
+        assert_eq!(instance_3.verify(&tag), Ok(false));
+
+        let instance_4: MacAlg = MacAlg {
+            tag: Some("bb1d6929".to_string()),
+            ..instance_2.clone()
+        };
+        assert!(instance_4.verify(&tag).is_err());
+    }
+
+    #[test]
+    fn gmac_requires_iv_test() -> () {
+        let instance_1: MacAlg = MacAlg {
+            operation: MacOperations::Mac,
+            algorithm: MacAlgorithms::Gmac,
+            key: "00000000000000000000000000000000".to_string(),
+            iv: None,
+            message: None,
+            file: None,
+            tag: None
+        };
+        assert!(instance_1.compute(b"").is_err());
+
+        let instance_2: MacAlg = MacAlg {
+            iv: Some("000000000000000000000000".to_string()),
+            ..instance_1.clone()
+        };
+        assert_eq!(bytes_to_hex(&instance_2.compute(b"").unwrap()), "58e2fccefa7e3061367f1d57a4e7455a");
+    }
+}
@@ -0,0 +1,123 @@
+const LIMB_MASK: u64 = 0x3ffffff;
+
+fn read_u32(bytes: &[u8]) -> u64 {
+    return u32::from_le_bytes(bytes[0..4].try_into().unwrap()) as u64;
+}
+
+
+
+
+// 130 bit numbers are kept in five 26 bit limbs, so limb products fit in a u64
+fn into_limbs(bytes: &[u8; 17]) -> [u64; 5] {
+    return [
+        read_u32(&bytes[0..]) & LIMB_MASK,
+        (read_u32(&bytes[3..]) >> 2) & LIMB_MASK,
+        (read_u32(&bytes[6..]) >> 4) & LIMB_MASK,
+        (read_u32(&bytes[9..]) >> 6) & LIMB_MASK,
+        (read_u32(&bytes[12..]) >> 8) | ((bytes[16] as u64) << 24)
+    ];
+}
+
+
+
+
+fn carry(h: &mut [u64; 5]) -> () {
+    for i in 1..5 {
+        h[i] += h[i - 1] >> 26;
+        h[i - 1] &= LIMB_MASK;
+    }
+    // 2^130 = 5 modulo 2^130 - 5
+    h[0] += (h[4] >> 26) * 5;
+    h[4] &= LIMB_MASK;
+    h[1] += h[0] >> 26;
+    h[0] &= LIMB_MASK;
+}
+
+
+
+
+// One-time authenticator of RFC 8439: the key is r (clamped) followed by s, and must never be reused
+pub fn poly1305(key: &[u8], message: &[u8]) -> Result<Vec<u8>, String> {
+    if key.len() != 32 {
+        return Err("Poly1305 key must be 32 bytes".to_string());
+    }
+
+    let mut r_bytes: [u8; 17] = [0; 17];
+    r_bytes[..16].copy_from_slice(&key[..16]);
+    for i in [3, 7, 11, 15] {
+        r_bytes[i] &= 0x0f;
+    }
+    for i in [4, 8, 12] {
+        r_bytes[i] &= 0xfc;
+    }
+    let r: [u64; 5] = into_limbs(&r_bytes);
+
+    let mut h: [u64; 5] = [0; 5];
+    for chunk in message.chunks(16) {
+        let mut block: [u8; 17] = [0; 17];
+        block[..chunk.len()].copy_from_slice(chunk);
+        block[chunk.len()] = 1;
+
+        for (h_i, m_i) in h.iter_mut().zip(into_limbs(&block)) {
+            *h_i += m_i;
+        }
+
+        let mut product: [u64; 5] = [0; 5];
+        for i in 0..5 {
+            for j in 0..5 {
+                product[i] += h[j] * if j <= i { r[i - j] } else { 5 * r[i + 5 - j] };
+            }
+        }
+        h = product;
+        carry(&mut h);
+    }
+    carry(&mut h);
+
+    // Subtract the prime when h + 5 overflows 2^130, without branching on the secret value
+    let mut g: [u64; 5] = h;
+    g[0] += 5;
+    for i in 1..5 {
+        g[i] += g[i - 1] >> 26;
+        g[i - 1] &= LIMB_MASK;
+    }
+    let select: u64 = 0u64.wrapping_sub(g[4] >> 26);
+    g[4] &= LIMB_MASK;
+    for i in 0..5 {
+        h[i] = (h[i] & !select) | (g[i] & select);
+    }
+
+    let accumulator: u128 = h.iter().enumerate().fold(0, |acc: u128, (i, limb): (usize, &u64)| acc | ((*limb as u128) << (26 * i)));
+    let s: u128 = u128::from_le_bytes(key[16..32].try_into().unwrap());
+
+    return Ok(accumulator.wrapping_add(s).to_le_bytes().to_vec());
+}
+
+#[cfg(test)]
+mod poly1305_test {
+    use crate::ops::{bytes_to_hex, hex_to_bytes};
+    use super::*;
+
+    const RFC_8439_KEY: &str = "85d6be7857556d337f4452fe42d506a80103808afb0db2fd4abff6af4149f51b";
+
+    fn poly1305_hex(key: &str, message: &[u8]) -> String {
+        return bytes_to_hex(&poly1305(&hex_to_bytes(key).unwrap(), message).unwrap());
+    }
+
+    #[test]
+    fn rfc_8439_test() -> () {
+        assert_eq!(poly1305_hex(RFC_8439_KEY, b"Cryptographic Forum Research Group"), "a8061dc1305136c6c22b8baf0c0127a9");
+        assert_eq!(poly1305_hex(RFC_8439_KEY, b""), "0103808afb0db2fd4abff6af4149f51b");
+        assert_eq!(poly1305_hex(RFC_8439_KEY, &(0..64).collect::<Vec<u8>>()), "2a7bebadae829f595bbde2cb6cca72a9");
+    }
+
+    #[test]
+    fn final_reduction_test() -> () {
+        let key: String = format!("02{}", "00".repeat(31));
+        assert_eq!(poly1305_hex(&key, &[0xff; 16]), "03000000000000000000000000000000");
+    }
+
+    #[test]
+    fn key_length_test() -> () {
+        assert!(poly1305(&[0; 16], b"message").is_err());
+    }
+}
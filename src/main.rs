@@ -1,18 +1,83 @@
+#![allow(clippy::needless_return, clippy::unused_unit, clippy::zero_prefixed_literal, clippy::redundant_field_names, clippy::new_without_default, clippy::needless_range_loop)]
+
 pub mod alphabet;
 pub mod args;
+pub mod bignum;
 pub mod ops;
 pub mod tables;
 pub mod algorithms {
+    pub mod adfgvx_cipher;
     pub mod advanced_encryption_standard;
+    pub mod bifid_cipher;
+    pub mod block_cipher;
+    pub mod blowfish;
     pub mod cesar_cipher;
     pub mod data_encryption_standard;
+    pub mod nihilist_cipher;
+    pub mod one_time_pad;
+    pub mod polybius_square;
+    pub mod rc4_cipher;
+    pub mod salsa20_cipher;
     pub mod transposition_cipher;
+    pub mod trifid_cipher;
     pub mod vigenere_cipher;
     pub mod xor_cipher;
 }
+pub mod asymmetric {
+    pub mod der;
+    pub mod diffie_hellman;
+    pub mod rsa;
+}
+pub mod attacks {
+    pub mod crack_hash;
+    pub mod length_extension;
+}
+pub mod hashes {
+    pub mod blake2;
+    pub mod blake3;
+    pub mod checksum;
+    pub mod crc;
+    pub mod fletcher;
+    pub mod fnv;
+    pub mod hash_function;
+    pub mod hmac;
+    pub mod md4;
+    pub mod md5;
+    pub mod merkle_damgard;
+    pub mod non_crypto_hash;
+    pub mod ripemd160;
+    pub mod sha1;
+    pub mod sha2;
+    pub mod sha3;
+    pub mod siphash;
+    pub mod sm3;
+    pub mod tiger;
+    pub mod whirlpool;
+}
+pub mod key_derivation {
+    pub mod argon2;
+    pub mod bcrypt;
+    pub mod hkdf;
+    pub mod kbkdf;
+    pub mod pbkdf2;
+    pub mod phc;
+    pub mod scrypt;
+    pub mod unix_crypt;
+}
+pub mod macs {
+    pub mod cmac;
+    pub mod gmac;
+    pub mod mac;
+    pub mod poly1305;
+}
 
-use algorithms::{advanced_encryption_standard::AdvancedEncryptionStandardAlg, cesar_cipher::CesarCipherAlg, transposition_cipher::TranspositionCipherAlg, vigenere_cipher::VigenereCipherAlg, xor_cipher::XorCipherAlg, data_encryption_standard::DataEncryptionStandardAlg};
+use algorithms::{adfgvx_cipher::AdfgvxCipherAlg, advanced_encryption_standard::AdvancedEncryptionStandardAlg, bifid_cipher::BifidCipherAlg, nihilist_cipher::NihilistCipherAlg, one_time_pad::OneTimePadAlg, polybius_square::PolybiusSquareAlg, rc4_cipher::Rc4CipherAlg, salsa20_cipher::Salsa20CipherAlg, trifid_cipher::TrifidCipherAlg, cesar_cipher::CesarCipherAlg, transposition_cipher::TranspositionCipherAlg, vigenere_cipher::VigenereCipherAlg, xor_cipher::XorCipherAlg, data_encryption_standard::DataEncryptionStandardAlg};
 use args::{Algorithms, CriptographyCliArgs};
+use asymmetric::{diffie_hellman::DhAlg, rsa::RsaAlg};
+use attacks::{crack_hash::CrackHashAlg, length_extension::LengthExtensionAlg};
+use hashes::{checksum::ChecksumAlg, hash_function::HashAlg, hmac::HmacAlg, non_crypto_hash::NonCryptoHashAlg};
+use key_derivation::{argon2::Argon2Alg, bcrypt::BcryptAlg, hkdf::HkdfAlg, kbkdf::KbkdfAlg, pbkdf2::Pbkdf2Alg, scrypt::ScryptAlg, unix_crypt::UnixCryptAlg};
+use macs::mac::MacAlg;
 use clap::{Parser, ValueEnum};
 
 #[derive(Debug, Clone, ValueEnum, PartialEq)]
@@ -35,8 +100,8 @@ fn main() {
             vigenere_cipher_instance.execute();
         }
 
-        Algorithms::XorCipher(XorCipherAlg {operation, key, message}) => {
-            let xor_cipher_instance = XorCipherAlg {operation, key, message};
+        Algorithms::XorCipher(XorCipherAlg {operation, key, password, kdf, iterations, key_format, key_length, key_output, message}) => {
+            let xor_cipher_instance = XorCipherAlg {operation, key, password, kdf, iterations, key_format, key_length, key_output, message};
             xor_cipher_instance.execute();
         }
 
@@ -45,14 +110,134 @@ fn main() {
             transposition_cipher_instance.execute();
         }
         
-        Algorithms::DataEncryptionStandard(DataEncryptionStandardAlg {operation, key, message}) => {
-            let data_encryption_standard_instance = DataEncryptionStandardAlg {operation, key, message};
+        Algorithms::DataEncryptionStandard(DataEncryptionStandardAlg {operation, key, password, kdf, iterations, key_length, message}) => {
+            let data_encryption_standard_instance = DataEncryptionStandardAlg {operation, key, password, kdf, iterations, key_length, message};
             data_encryption_standard_instance.execute();
         }
 
-        Algorithms::AdvancedEncryptionStandard(AdvancedEncryptionStandardAlg {}) => {
-            let advanced_encryption_standard_instance = AdvancedEncryptionStandardAlg {};
+        Algorithms::AdvancedEncryptionStandard(AdvancedEncryptionStandardAlg {operation, key, key_format, password, kdf, iterations, key_size, message}) => {
+            let advanced_encryption_standard_instance = AdvancedEncryptionStandardAlg {operation, key, key_format, password, kdf, iterations, key_size, message};
             advanced_encryption_standard_instance.execute();
         }
+
+        Algorithms::PolybiusSquare(PolybiusSquareAlg {operation, key, message}) => {
+            let polybius_square_instance = PolybiusSquareAlg {operation, key, message};
+            polybius_square_instance.execute();
+        }
+
+        Algorithms::BifidCipher(BifidCipherAlg {operation, key, period, message}) => {
+            let bifid_cipher_instance = BifidCipherAlg {operation, key, period, message};
+            bifid_cipher_instance.execute();
+        }
+
+        Algorithms::TrifidCipher(TrifidCipherAlg {operation, key, period, message}) => {
+            let trifid_cipher_instance = TrifidCipherAlg {operation, key, period, message};
+            trifid_cipher_instance.execute();
+        }
+
+        Algorithms::AdfgvxCipher(AdfgvxCipherAlg {operation, variant, square_key, key, message}) => {
+            let adfgvx_cipher_instance = AdfgvxCipherAlg {operation, variant, square_key, key, message};
+            adfgvx_cipher_instance.execute();
+        }
+
+        Algorithms::NihilistCipher(NihilistCipherAlg {operation, square_key, key, message}) => {
+            let nihilist_cipher_instance = NihilistCipherAlg {operation, square_key, key, message};
+            nihilist_cipher_instance.execute();
+        }
+
+        Algorithms::OneTimePad(OneTimePadAlg {operation, pad, size, offset, message}) => {
+            let one_time_pad_instance = OneTimePadAlg {operation, pad, size, offset, message};
+            one_time_pad_instance.execute();
+        }
+
+        Algorithms::Rc4Cipher(Rc4CipherAlg {operation, key, key_format, drop, samples, message}) => {
+            let rc4_cipher_instance = Rc4CipherAlg {operation, key, key_format, drop, samples, message};
+            rc4_cipher_instance.execute();
+        }
+
+        Algorithms::Salsa20Cipher(Salsa20CipherAlg {operation, variant, key, nonce, offset, length, message, file, output}) => {
+            let salsa20_cipher_instance = Salsa20CipherAlg {operation, variant, key, nonce, offset, length, message, file, output};
+            salsa20_cipher_instance.execute();
+        }
+
+        Algorithms::Rsa(RsaAlg {operation, key, bits, padding, label, scheme, algorithm, salt_length, message, file, signature}) => {
+            let rsa_instance = RsaAlg {operation, key, bits, padding, label, scheme, algorithm, salt_length, message, file, signature};
+            rsa_instance.execute();
+        }
+
+        Algorithms::Dh(DhAlg {operation, group, key, peer, salt, info, length}) => {
+            let dh_instance = DhAlg {operation, group, key, peer, salt, info, length};
+            dh_instance.execute();
+        }
+
+        Algorithms::Hash(HashAlg {algorithm, message, file, length, function_name, customization, key, key_format, salt, personalization, context}) => {
+            let hash_instance = HashAlg {algorithm, message, file, length, function_name, customization, key, key_format, salt, personalization, context};
+            hash_instance.execute();
+        }
+
+        Algorithms::Hmac(HmacAlg {algorithm, key, key_file, key_format, message, file, expected}) => {
+            let hmac_instance = HmacAlg {algorithm, key, key_file, key_format, message, file, expected};
+            hmac_instance.execute();
+        }
+
+        Algorithms::NonCryptoHash(NonCryptoHashAlg {algorithm, message, file, key, key_format, polynomial, init, xor_out, reflect}) => {
+            let non_crypto_hash_instance = NonCryptoHashAlg {algorithm, message, file, key, key_format, polynomial, init, xor_out, reflect};
+            non_crypto_hash_instance.execute();
+        }
+
+        Algorithms::Checksum(ChecksumAlg {algorithm, paths, recursive, jobs, format, check, quiet}) => {
+            let checksum_instance = ChecksumAlg {algorithm, paths, recursive, jobs, format, check, quiet};
+            checksum_instance.execute();
+        }
+
+        Algorithms::LengthExtension(LengthExtensionAlg {algorithm, digest, message, secret_length, suffix, secret}) => {
+            let length_extension_instance = LengthExtensionAlg {algorithm, digest, message, secret_length, suffix, secret};
+            length_extension_instance.execute();
+        }
+
+        Algorithms::CrackHash(CrackHashAlg {hash, hash_file, algorithm, wordlist, rules, digits, jobs}) => {
+            let crack_hash_instance = CrackHashAlg {hash, hash_file, algorithm, wordlist, rules, digits, jobs};
+            crack_hash_instance.execute();
+        }
+
+        Algorithms::Mac(MacAlg {operation, algorithm, key, iv, message, file, tag}) => {
+            let mac_instance = MacAlg {operation, algorithm, key, iv, message, file, tag};
+            mac_instance.execute();
+        }
+
+        Algorithms::Pbkdf2(Pbkdf2Alg {password, salt, salt_format, prf, iterations, length}) => {
+            let pbkdf2_instance = Pbkdf2Alg {password, salt, salt_format, prf, iterations, length};
+            pbkdf2_instance.execute();
+        }
+
+        Algorithms::Bcrypt(BcryptAlg {operation, password, cost, salt, variant, hash}) => {
+            let bcrypt_instance = BcryptAlg {operation, password, cost, salt, variant, hash};
+            bcrypt_instance.execute();
+        }
+
+        Algorithms::Scrypt(ScryptAlg {operation, password, salt, salt_format, log_n, block_size, parallelism, length, hash}) => {
+            let scrypt_instance = ScryptAlg {operation, password, salt, salt_format, log_n, block_size, parallelism, length, hash};
+            scrypt_instance.execute();
+        }
+
+        Algorithms::Argon2(Argon2Alg {operation, variant, password, salt, salt_format, memory, time, parallelism, length, hash}) => {
+            let argon2_instance = Argon2Alg {operation, variant, password, salt, salt_format, memory, time, parallelism, length, hash};
+            argon2_instance.execute();
+        }
+
+        Algorithms::Hkdf(HkdfAlg {algorithm, key, key_format, salt, salt_format, info, length}) => {
+            let hkdf_instance = HkdfAlg {algorithm, key, key_format, salt, salt_format, info, length};
+            hkdf_instance.execute();
+        }
+
+        Algorithms::Kbkdf(KbkdfAlg {mode, prf, key, key_format, label, context, iv, length}) => {
+            let kbkdf_instance = KbkdfAlg {mode, prf, key, key_format, label, context, iv, length};
+            kbkdf_instance.execute();
+        }
+
+        Algorithms::UnixCrypt(UnixCryptAlg {operation, format, password, salt, rounds, hash, shadow, user}) => {
+            let unix_crypt_instance = UnixCryptAlg {operation, format, password, salt, rounds, hash, shadow, user};
+            unix_crypt_instance.execute();
+        }
     }
 }
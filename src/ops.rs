@@ -1,3 +1,6 @@
+use std::{fs::File, io::{self, Read}};
+use clap::ValueEnum;
+
 fn bits_to_byte(bits: &[u8; 8]) -> u8 {
     let mut byte: u8 = 0;
 
@@ -22,6 +25,20 @@ fn byte_to_bits(byte: u8) -> [u8; 8] {
 
 
 
+pub fn bytes_into_bits(bytes: &[u8]) -> Vec<u8> {
+    return bytes.iter().flat_map(|byte: &u8| byte_to_bits(*byte)).collect();
+}
+
+
+
+
+pub fn bits_into_bytes(bits: &[u8]) -> Vec<u8> {
+    return bits.chunks(8).map(|bits_i: &[u8]| bits_to_byte(&is_a_8bit_chunk(bits_i))).collect();
+}
+
+
+
+
 pub fn char_to_bits(c: char) -> Vec<[u8; 8]> {
     let bits: Vec<[u8; 8]> = 
         c.to_string()
@@ -39,7 +56,7 @@ pub fn char_to_bits(c: char) -> Vec<[u8; 8]> {
 pub fn bits_to_char(bits: Vec<[u8; 8]>) -> char {
     let bytes: Vec<u8> = bits.iter().map(|bits: &[u8; 8]| bits_to_byte(bits)).collect();
     
-    return String::from_utf8(bytes).unwrap_or(" ".to_string()).chars().nth(0).unwrap_or(' ');
+    return String::from_utf8(bytes).unwrap_or(" ".to_string()).chars().next().unwrap_or(' ');
 }
 
 
@@ -102,24 +119,23 @@ pub fn is_a_7bit_chunk(chunk: &[u8]) -> [u8; 7] {
 
 pub fn is_a_xbit_chunk(chunk: &[u8], x_bits_must_have: usize) -> Vec<u8> {
     let mut chunk_x: Vec<u8> = chunk.into();
-    let error_chunk: Vec<u8> = {(0..x_bits_must_have).push(0)}
+    let error_chunk: Vec<u8> = vec![0; x_bits_must_have];
 
-    if chunk.len() > 7 {
+    if chunk.len() > x_bits_must_have {
         return error_chunk;
     }
 
-    while chunk_x.len() < 7 {
+    while chunk_x.len() < x_bits_must_have {
         chunk_x.push(0);
     }
-    return chunk_x.try_into().unwrap_or(error_chunk);
+    return chunk_x;
 }
 
 
 
 
-
 pub fn is_a_8bytes_block(block: Vec<[u8; 8]>) -> [[u8; 8]; 8] {
-    let mut block_x: Vec<[u8; 8]> = block.clone().into();
+    let mut block_x: Vec<[u8; 8]> = block.clone();
     let fill_block: [u8; 8] = [0; 8]; 
     let error_block: [[u8; 8]; 8] = [[0; 8]; 8];
 
@@ -174,7 +190,7 @@ pub fn permutation(block: Vec<u8>, concatenated_permut_table: Vec<u8>) -> Vec<u8
             .iter()
             .map(|pos:&u8 | 
                 {
-                    block[pos.clone() as usize - 1]
+                    block[*pos as usize - 1]
                 })
             .collect::<Vec<u8>>();
 } 
@@ -195,6 +211,150 @@ pub fn left_shift_vec(bits_vec: Vec<u8>, shift: usize) -> Vec<u8> {
 
 
 
+
+pub fn bytes_to_hex(bytes: &[u8]) -> String {
+    return bytes.iter().map(|byte: &u8| format!("{:02x}", byte)).collect::<Vec<String>>().concat();
+}
+
+
+
+
+pub fn hex_to_bytes(hex: &str) -> Result<Vec<u8>, String> {
+    let digits: Vec<char> = hex.chars().filter(|c: &char| !c.is_whitespace()).collect();
+
+    if !digits.len().is_multiple_of(2) {
+        return Err("hex string must have an even number of digits".to_string());
+    }
+
+    return digits.chunks(2)
+                 .map(|pair: &[char]| {
+                    let high: Option<u32> = pair[0].to_digit(16);
+                    let low: Option<u32> = pair[1].to_digit(16);
+
+                    match (high, low) {
+                        (Some(h), Some(l)) => Ok((h * 16 + l) as u8),
+                        _ => Err(format!("invalid hex digits {}{}", pair[0], pair[1]))
+                    }
+                 })
+                 .collect();
+}
+
+
+
+
+// create_new refuses to overwrite an existing file, and mode restricts who can read secrets such as keys and pads
+pub fn write_new_file(path: &str, content: &[u8], mode: u32) -> Result<(), String> {
+    let mut options: std::fs::OpenOptions = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, mode);
+    #[cfg(not(unix))]
+    let _ = mode;
+
+    let mut file: std::fs::File = options.open(path).map_err(|e| format!("could not create {}: {}", path, e))?;
+    return std::io::Write::write_all(&mut file, content).map_err(|e| format!("could not write {}: {}", path, e));
+}
+
+
+
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub fn bytes_to_base64(bytes: &[u8]) -> String {
+    let mut encoded: String = String::new();
+
+    for chunk in bytes.chunks(3) {
+        let group: u32 = (chunk[0] as u32) << 16 | (*chunk.get(1).unwrap_or(&0) as u32) << 8 | *chunk.get(2).unwrap_or(&0) as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(BASE64_ALPHABET[((group >> (18 - 6 * i)) & 63) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    return encoded;
+}
+
+
+
+
+pub fn base64_to_bytes(base64: &str) -> Result<Vec<u8>, String> {
+    let mut bytes: Vec<u8> = Vec::new();
+    let mut group: u32 = 0;
+    let mut bits: u32 = 0;
+
+    for c in base64.trim_end_matches('=').chars().filter(|c: &char| !c.is_whitespace()) {
+        let value: usize = BASE64_ALPHABET.iter().position(|x: &u8| *x as char == c).ok_or(format!("invalid base64 character {}", c))?;
+
+        group = (group << 6) | value as u32;
+        bits += 6;
+
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((group >> bits) as u8);
+            group &= (1 << bits) - 1;
+        }
+    }
+    return Ok(bytes);
+}
+
+
+
+
+#[derive(Debug, Clone, ValueEnum, PartialEq)]
+pub enum KeyFormat {
+    Text,
+    Hex,
+    Base64
+}
+
+pub fn decode_key(key: &str, format: &KeyFormat) -> Result<Vec<u8>, String> {
+    match format {
+        KeyFormat::Text => Ok(key.as_bytes().to_vec()),
+        KeyFormat::Hex => hex_to_bytes(key),
+        KeyFormat::Base64 => base64_to_bytes(key)
+    }
+}
+
+pub fn encode_key(key: &[u8], format: &KeyFormat) -> String {
+    match format {
+        KeyFormat::Text => String::from_utf8_lossy(key).to_string(),
+        KeyFormat::Hex => bytes_to_hex(key),
+        KeyFormat::Base64 => bytes_to_base64(key)
+    }
+}
+
+
+
+
+pub fn open_input(file: Option<&str>) -> Result<Box<dyn Read>, String> {
+    match file {
+        None | Some("-") => Ok(Box::new(io::stdin())),
+        Some(path) => {
+            let opened: File = File::open(path).map_err(|e| format!("could not open {}: {}", path, e))?;
+            Ok(Box::new(opened))
+        }
+    }
+}
+
+
+
+
+// Compares every byte regardless of where the first difference is, so timing does not leak the match length
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let difference: u8 = a.iter().zip(b).fold(0, |acc: u8, (x, y): (&u8, &u8)| acc | (x ^ y));
+    return difference == 0;
+}
+
+
+
+
 #[cfg(test)]
 mod test_ops {
     use crate::tables::{DES_FINAL_PERMUTATION_TABLE, DES_INITIAL_PERMUTATION_TABLE};
@@ -383,7 +543,7 @@ mod test_ops {
         assert_eq!(byte_parity_verify([1,1,1,1,1,1,1,1]), [1,1,1,1,1,1,1,0]);
         assert_eq!(byte_parity_verify([1,1,1,1,0,0,0,0]), [1,1,1,1,0,0,0,1]);
         assert_eq!(byte_parity_verify([1,1,1,0,0,0,0,0]), [1,1,1,0,0,0,0,0]);
-        assert_eq!(byte_parity_verify([1,1,1,0,0,1,1,0]), [1,1,1,1,0,1,1,0]);
+        assert_eq!(byte_parity_verify([1,1,1,0,0,1,1,0]), [1,1,1,0,0,1,1,0]);
         assert_eq!(byte_parity_verify([1,1,1,1,1,1,1,0]), [1,1,1,1,1,1,1,0]);
     }
 
@@ -409,4 +569,48 @@ mod test_ops {
         assert_eq!(left_shift_vec(vec![1, 0, 1, 0, 1, 0], 1), vec![0, 1, 0, 1, 0, 0]);
         assert_eq!(left_shift_vec(vec![1, 0, 1, 0, 1, 0], 0), vec![1, 0, 1, 0, 1, 0]); 
     }
+
+    #[test]
+    fn test_hex() -> () {
+        assert_eq!(bytes_to_hex(&[0, 1, 171, 255]), "0001abff");
+        assert_eq!(hex_to_bytes("0001abff"), Ok(vec![0, 1, 171, 255]));
+        assert_eq!(hex_to_bytes("00 01 AB FF"), Ok(vec![0, 1, 171, 255]));
+        assert!(hex_to_bytes("abc").is_err());
+        assert!(hex_to_bytes("zz").is_err());
+    }
+
+    #[test]
+    fn test_base64() -> () {
+        assert_eq!(bytes_to_base64(b""), "");
+        assert_eq!(bytes_to_base64(b"f"), "Zg==");
+        assert_eq!(bytes_to_base64(b"fo"), "Zm8=");
+        assert_eq!(bytes_to_base64(b"foo"), "Zm9v");
+        assert_eq!(bytes_to_base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64_to_bytes("Zg=="), Ok(b"f".to_vec()));
+        assert_eq!(base64_to_bytes("Zm8="), Ok(b"fo".to_vec()));
+        assert_eq!(base64_to_bytes("Zm9vYmFy"), Ok(b"foobar".to_vec()));
+        assert!(base64_to_bytes("Zm9v!").is_err());
+    }
+
+    #[test]
+    fn test_key_format() -> () {
+        assert_eq!(decode_key("AAAA", &KeyFormat::Text), Ok(vec![65, 65, 65, 65]));
+        assert_eq!(decode_key("41414141", &KeyFormat::Hex), Ok(vec![65, 65, 65, 65]));
+        assert_eq!(decode_key("QUFBQQ==", &KeyFormat::Base64), Ok(vec![65, 65, 65, 65]));
+        assert_eq!(encode_key(&[65, 65, 65, 65], &KeyFormat::Base64), "QUFBQQ==");
+    }
+
+    #[test]
+    fn test_bytes_bits() -> () {
+        assert_eq!(bytes_into_bits(&[0x80, 0x05]), vec![1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 1]);
+        assert_eq!(bits_into_bytes(&bytes_into_bits(b"DES")), b"DES".to_vec());
+        assert_eq!(is_a_xbit_chunk(&[1, 1], 6), vec![1, 1, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_constant_time_eq() -> () {
+        assert!(constant_time_eq(b"tag", b"tag"));
+        assert!(!constant_time_eq(b"tag", b"taG"));
+        assert!(!constant_time_eq(b"tag", b"tags"));
+    }
 }